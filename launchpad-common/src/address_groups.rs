@@ -0,0 +1,188 @@
+dharitri_sc::imports!();
+dharitri_sc::derive_imports!();
+
+use crate::permissions::Role;
+
+pub const NO_ADDRESS_GROUP: usize = 0;
+
+/// Addresses known to belong to the same entity, which share the ticket limits
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct AddressGroup {
+    pub max_confirmed_tickets: usize,
+    pub max_winning_tickets: usize,
+}
+
+/// The tickets of a grouped address that remain after filtering, in ticket ID order
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct GroupedTicketRange {
+    pub first_ticket_id: usize,
+    pub last_ticket_id: usize,
+    pub group_id: usize,
+}
+
+#[dharitri_sc::module]
+pub trait AddressGroupsModule:
+    crate::launch_stage::LaunchStageModule
+    + crate::config::ConfigModule
+    + crate::permissions::PermissionsModule
+    + crate::common_events::CommonEventsModule
+{
+    /// Returns the new group ID.
+    /// Only exposed by the contracts without guaranteed tickets,
+    /// since the guaranteed tickets distribution doesn't apply the group limits.
+    fn add_address_group(
+        &self,
+        max_confirmed_tickets: usize,
+        max_winning_tickets: usize,
+        addresses: MultiValueEncoded<ManagedAddress>,
+    ) -> usize {
+        self.require_role(Role::TicketManager);
+        self.require_add_tickets_period();
+        require!(
+            max_confirmed_tickets > 0 && max_winning_tickets > 0,
+            "Invalid address group limits"
+        );
+        require!(!addresses.is_empty(), "No addresses");
+
+        let group_id = self.address_groups().push(&AddressGroup {
+            max_confirmed_tickets,
+            max_winning_tickets,
+        });
+        let mut members_mapper = self.address_group_members(group_id);
+        let mut group_addresses = ManagedVec::new();
+        for address in addresses {
+            let user_group_mapper = self.user_address_group(&address);
+            require!(user_group_mapper.is_empty(), "Address already in a group");
+
+            user_group_mapper.set(group_id);
+            let _ = members_mapper.insert(address.clone());
+            group_addresses.push(address);
+        }
+
+        self.emit_add_address_group_event(
+            group_id,
+            max_confirmed_tickets,
+            max_winning_tickets,
+            group_addresses,
+        );
+
+        group_id
+    }
+
+    fn add_group_confirmed_tickets(&self, address: &ManagedAddress, nr_tickets: usize) {
+        let group_id = self.user_address_group(address).get();
+        if group_id == NO_ADDRESS_GROUP {
+            return;
+        }
+
+        let group = self.address_groups().get(group_id);
+        let nr_confirmed_tickets_mapper = self.group_nr_confirmed_tickets(group_id);
+        let nr_confirmed_tickets = nr_confirmed_tickets_mapper.get() + nr_tickets;
+        require!(
+            nr_confirmed_tickets <= group.max_confirmed_tickets,
+            "Address group may not confirm that many tickets"
+        );
+
+        nr_confirmed_tickets_mapper.set(nr_confirmed_tickets);
+    }
+
+    fn remove_group_confirmed_tickets(&self, address: &ManagedAddress, nr_tickets: usize) {
+        let group_id = self.user_address_group(address).get();
+        if group_id == NO_ADDRESS_GROUP {
+            return;
+        }
+
+        self.group_nr_confirmed_tickets(group_id)
+            .update(|nr_confirmed_tickets| *nr_confirmed_tickets -= nr_tickets);
+    }
+
+    fn add_grouped_ticket_range(&self, address: &ManagedAddress, first_id: usize, last_id: usize) {
+        let group_id = self.user_address_group(address).get();
+        if group_id == NO_ADDRESS_GROUP {
+            return;
+        }
+
+        let _ = self.grouped_ticket_ranges().push(&GroupedTicketRange {
+            first_ticket_id: first_id,
+            last_ticket_id: last_id,
+            group_id,
+        });
+    }
+
+    /// Returns false if the group of the ticket owner already reached its winning tickets limit
+    fn try_add_group_winning_ticket(&self, ticket_id: usize) -> bool {
+        let group_id = self.get_ticket_address_group(ticket_id);
+        if group_id == NO_ADDRESS_GROUP {
+            return true;
+        }
+
+        let group = self.address_groups().get(group_id);
+        let nr_winning_tickets_mapper = self.group_nr_winning_tickets(group_id);
+        let nr_winning_tickets = nr_winning_tickets_mapper.get();
+        if nr_winning_tickets >= group.max_winning_tickets {
+            return false;
+        }
+
+        nr_winning_tickets_mapper.set(nr_winning_tickets + 1);
+
+        true
+    }
+
+    fn remove_group_winning_tickets(&self, address: &ManagedAddress, nr_tickets: usize) {
+        let group_id = self.user_address_group(address).get();
+        if group_id == NO_ADDRESS_GROUP || nr_tickets == 0 {
+            return;
+        }
+
+        self.group_nr_winning_tickets(group_id)
+            .update(|nr_winning_tickets| *nr_winning_tickets -= nr_tickets);
+    }
+
+    /// Binary search through the grouped ticket ranges, which are sorted by ticket ID
+    fn get_ticket_address_group(&self, ticket_id: usize) -> usize {
+        let ranges_mapper = self.grouped_ticket_ranges();
+        let mut low = 1;
+        let mut high = ranges_mapper.len();
+        while low <= high {
+            let mid = (low + high) / 2;
+            let range = ranges_mapper.get(mid);
+            if ticket_id < range.first_ticket_id {
+                high = mid - 1;
+            } else if ticket_id > range.last_ticket_id {
+                low = mid + 1;
+            } else {
+                return range.group_id;
+            }
+        }
+
+        NO_ADDRESS_GROUP
+    }
+
+    #[inline]
+    fn has_address_groups(&self) -> bool {
+        !self.address_groups().is_empty()
+    }
+
+    #[view(getAddressGroups)]
+    #[storage_mapper("addressGroups")]
+    fn address_groups(&self) -> VecMapper<AddressGroup>;
+
+    #[view(getAddressGroupMembers)]
+    #[storage_mapper("addressGroupMembers")]
+    fn address_group_members(&self, group_id: usize) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[view(getUserAddressGroup)]
+    #[storage_mapper("userAddressGroup")]
+    fn user_address_group(&self, address: &ManagedAddress) -> SingleValueMapper<usize>;
+
+    #[view(getGroupNumberOfConfirmedTickets)]
+    #[storage_mapper("groupNrConfirmedTickets")]
+    fn group_nr_confirmed_tickets(&self, group_id: usize) -> SingleValueMapper<usize>;
+
+    #[view(getGroupNumberOfWinningTickets)]
+    #[storage_mapper("groupNrWinningTickets")]
+    fn group_nr_winning_tickets(&self, group_id: usize) -> SingleValueMapper<usize>;
+
+    #[storage_mapper("groupedTicketRanges")]
+    fn grouped_ticket_ranges(&self) -> VecMapper<GroupedTicketRange>;
+}
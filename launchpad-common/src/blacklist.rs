@@ -1,46 +1,106 @@
 dharitri_sc::imports!();
+dharitri_sc::derive_imports!();
+
+use crate::permissions::Role;
+
+pub const LEGACY_BLACKLIST_REASON: u32 = 0;
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct BlacklistEntry<M: ManagedTypeApi> {
+    pub reason: u32,
+    pub blacklisted_by: ManagedAddress<M>,
+    pub block: u64,
+}
 
 #[dharitri_sc::module]
 pub trait BlacklistModule:
     crate::permissions::PermissionsModule
+    + crate::address_groups::AddressGroupsModule
     + crate::launch_stage::LaunchStageModule
     + crate::tickets::TicketsModule
     + crate::token_send::TokenSendModule
     + crate::config::ConfigModule
     + crate::common_events::CommonEventsModule
 {
-    fn add_users_to_blacklist(&self, users_list: &ManagedVec<ManagedAddress>) {
-        self.require_extended_permissions();
+    fn add_users_to_blacklist(&self, users_list: &ManagedVec<ManagedAddress>, reason: u32) {
+        self.require_role(Role::Compliance);
         self.require_before_winner_selection();
 
-        let blacklist_mapper = self.blacklist();
         for address in users_list {
-            require!(
-                !blacklist_mapper.contains(&address),
-                "User already blacklisted"
-            );
-            let confirmed_tickets_mapper = self.nr_confirmed_tickets(&address);
-            let nr_confirmed_tickets = confirmed_tickets_mapper.get();
-            if nr_confirmed_tickets > 0 {
-                self.refund_ticket_payment(&address, nr_confirmed_tickets);
-                confirmed_tickets_mapper.clear();
-            }
+            self.blacklist_user(&address, reason);
+        }
 
-            blacklist_mapper.add(&address);
+        self.emit_add_users_to_blacklist_event(users_list.clone(), reason);
+    }
+
+    /// Refunds all the confirmed tickets of the user
+    fn blacklist_user(&self, address: &ManagedAddress, reason: u32) {
+        require!(
+            !self.is_user_blacklisted(address),
+            "User already blacklisted"
+        );
+
+        let confirmed_tickets_mapper = self.nr_confirmed_tickets(address);
+        let nr_confirmed_tickets = confirmed_tickets_mapper.get();
+        if nr_confirmed_tickets > 0 {
+            self.refund_ticket_payment(address, nr_confirmed_tickets);
+            confirmed_tickets_mapper.clear();
+            self.total_confirmed_tickets()
+                .update(|total| *total -= nr_confirmed_tickets);
+            self.refresh_dutch_auction_sold_out();
+            self.remove_group_confirmed_tickets(address, nr_confirmed_tickets);
         }
+
+        self.blacklist().add(address);
+        let _ = self.blacklisted_users().insert(address.clone());
+        self.blacklist_entry(address).set(BlacklistEntry {
+            reason,
+            blacklisted_by: self.blockchain().get_caller(),
+            block: self.blockchain().get_block_nonce(),
+        });
     }
 
     fn remove_users_from_blacklist(&self, users_list: MultiValueEncoded<ManagedAddress>) {
-        self.require_extended_permissions();
+        self.require_role(Role::Compliance);
         self.require_before_winner_selection();
 
         let blacklist_mapper = self.blacklist();
-        for address in users_list {
+        let mut blacklisted_users_mapper = self.blacklisted_users();
+        let users_vec = users_list.to_vec();
+        for address in &users_vec {
             require!(
                 blacklist_mapper.contains(&address),
                 "User is not blacklisted"
             );
             blacklist_mapper.remove(&address);
+            let _ = blacklisted_users_mapper.swap_remove(&address);
+            self.blacklist_entry(&address).clear();
+        }
+
+        self.emit_remove_users_from_blacklist_event(users_vec);
+    }
+
+    /// Contracts deployed before the blacklist entries were introduced only kept the blacklisted
+    /// addresses in a set that can't be iterated, so they are passed by the owner on upgrade
+    fn migrate_blacklisted_users(&self, users_list: MultiValueEncoded<ManagedAddress>) {
+        let blacklist_mapper = self.blacklist();
+        let mut blacklisted_users_mapper = self.blacklisted_users();
+        let owner = self.blockchain().get_owner_address();
+        let current_block = self.blockchain().get_block_nonce();
+        for address in users_list {
+            require!(
+                blacklist_mapper.contains(&address),
+                "User is not blacklisted"
+            );
+            if !blacklisted_users_mapper.insert(address.clone()) {
+                continue;
+            }
+
+            self.blacklist_entry(&address).set(BlacklistEntry {
+                reason: LEGACY_BLACKLIST_REASON,
+                blacklisted_by: owner.clone(),
+                block: current_block,
+            });
         }
     }
 
@@ -49,6 +109,42 @@ pub trait BlacklistModule:
         self.blacklist().contains(address)
     }
 
+    #[view(getBlacklistedUsers)]
+    fn get_blacklisted_users(
+        &self,
+        start_index: usize,
+        max_results: usize,
+    ) -> MultiValueEncoded<MultiValue2<ManagedAddress, BlacklistEntry<Self::Api>>> {
+        let mut result = MultiValueEncoded::new();
+        let blacklisted_users_mapper = self.blacklisted_users();
+        let end_index = core::cmp::min(
+            start_index.saturating_add(max_results),
+            blacklisted_users_mapper.len(),
+        );
+        for index in start_index..end_index {
+            let address = blacklisted_users_mapper.get_by_index(index + 1);
+            let entry = self.blacklist_entry(&address).get();
+            result.push((address, entry).into());
+        }
+
+        result
+    }
+
+    #[view(getNrBlacklistedUsers)]
+    fn get_nr_blacklisted_users(&self) -> usize {
+        self.blacklisted_users().len()
+    }
+
     #[storage_mapper("blacklisted")]
     fn blacklist(&self) -> WhitelistMapper<Self::Api, ManagedAddress>;
+
+    #[storage_mapper("blacklistedUsers")]
+    fn blacklisted_users(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[view(getBlacklistEntry)]
+    #[storage_mapper("blacklistEntry")]
+    fn blacklist_entry(
+        &self,
+        address: &ManagedAddress,
+    ) -> SingleValueMapper<BlacklistEntry<Self::Api>>;
 }
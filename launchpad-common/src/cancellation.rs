@@ -0,0 +1,82 @@
+dharitri_sc::imports!();
+
+use crate::{launch_stage::LaunchStage, permissions::Role};
+
+#[dharitri_sc::module]
+pub trait CancellationModule:
+    crate::launch_stage::LaunchStageModule
+    + crate::config::ConfigModule
+    + crate::tickets::TicketsModule
+    + crate::token_send::TokenSendModule
+    + crate::permissions::PermissionsModule
+    + crate::common_events::CommonEventsModule
+{
+    /// Once cancelled, users may get their ticket payments back through `claimRefund`
+    #[endpoint(cancelLaunchpad)]
+    fn cancel_launchpad(&self) {
+        self.require_role(Role::Operator);
+
+        let launch_stage = self.get_launch_stage();
+        require!(
+            launch_stage != LaunchStage::Cancelled,
+            "Launchpad already cancelled"
+        );
+        require!(
+            launch_stage < LaunchStage::Claim,
+            "Cannot cancel after claim period started"
+        );
+
+        self.launchpad_cancelled().set(true);
+    }
+
+    #[endpoint(withdrawLaunchpadTokens)]
+    fn withdraw_launchpad_tokens(&self) {
+        self.require_role(Role::Treasurer);
+        self.require_cancelled_period();
+
+        let launchpad_tokens_amount = self.total_launchpad_tokens_deposited().take();
+        require!(
+            launchpad_tokens_amount > 0,
+            "No launchpad tokens to withdraw"
+        );
+
+        let owner = self.blockchain().get_owner_address();
+        let launchpad_token_id = self.launchpad_token_id().get();
+        self.send()
+            .direct_dcdt(&owner, &launchpad_token_id, 0, &launchpad_tokens_amount);
+    }
+
+    /// The launchpad is cancelled if fewer tickets than the soft cap were confirmed
+    fn try_cancel_below_soft_cap(&self) -> bool {
+        let total_confirmed_tickets = self.total_confirmed_tickets().get();
+        let soft_cap = self.soft_cap().get();
+        if total_confirmed_tickets >= soft_cap {
+            return false;
+        }
+
+        self.launchpad_cancelled().set(true);
+        self.emit_soft_cap_not_reached_event(total_confirmed_tickets, soft_cap);
+
+        true
+    }
+
+    fn claim_cancellation_refund(&self) {
+        let were_tickets_refunded = self.try_claim_cancellation_refund();
+        require!(were_tickets_refunded, "Nothing to refund");
+    }
+
+    /// Returns false if the caller has no confirmed tickets to refund
+    fn try_claim_cancellation_refund(&self) -> bool {
+        self.require_cancelled_period();
+
+        let caller = self.blockchain().get_caller();
+        let nr_confirmed_tickets = self.nr_confirmed_tickets(&caller).take();
+        if nr_confirmed_tickets == 0 {
+            return false;
+        }
+
+        self.refund_ticket_payment(&caller, nr_confirmed_tickets);
+
+        true
+    }
+}
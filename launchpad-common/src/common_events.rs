@@ -1,6 +1,8 @@
 dharitri_sc::imports!();
 dharitri_sc::derive_imports!();
 
+use crate::{permissions::Role, timelock::PendingAdminAction};
+
 #[derive(TypeAbi, TopEncode)]
 pub struct RefundTicketPaymentEvent<M: ManagedTypeApi> {
     user: ManagedAddress<M>,
@@ -29,6 +31,23 @@ pub struct ConfirmTicketsEvent<M: ManagedTypeApi> {
     token_payment: RewaOrDcdtTokenPayment<M>,
 }
 
+#[derive(TypeAbi, TopEncode)]
+pub struct UnconfirmTicketsEvent<M: ManagedTypeApi> {
+    user: ManagedAddress<M>,
+    block: u64,
+    epoch: u64,
+    tickets_unconfirmed: usize,
+    total_confirmed: usize,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct RegisterTicketsEvent<M: ManagedTypeApi> {
+    user: ManagedAddress<M>,
+    block: u64,
+    epoch: u64,
+    nr_tickets: usize,
+}
+
 #[derive(TypeAbi, TopEncode)]
 pub struct FilterTicketsCompletedEvent<M: ManagedTypeApi> {
     user: ManagedAddress<M>,
@@ -45,6 +64,87 @@ pub struct SelectWinnersCompletedEvent<M: ManagedTypeApi> {
     total_winning_tickets: usize,
 }
 
+#[derive(TypeAbi, TopEncode)]
+pub struct SelectWaitlistWinnersCompletedEvent<M: ManagedTypeApi> {
+    user: ManagedAddress<M>,
+    block: u64,
+    epoch: u64,
+    forfeited_tickets: usize,
+    waitlist_winning_tickets: usize,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct RedrawWinnersCompletedEvent<M: ManagedTypeApi> {
+    user: ManagedAddress<M>,
+    block: u64,
+    epoch: u64,
+    freed_winning_tickets: usize,
+    redrawn_winning_tickets: usize,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct SoftCapNotReachedEvent<M: ManagedTypeApi> {
+    user: ManagedAddress<M>,
+    block: u64,
+    epoch: u64,
+    total_confirmed_tickets: usize,
+    soft_cap: usize,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct SeedNotRevealedEvent<M: ManagedTypeApi> {
+    user: ManagedAddress<M>,
+    block: u64,
+    epoch: u64,
+    reveal_deadline: u64,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct UpdateRoleEvent<M: ManagedTypeApi> {
+    user: ManagedAddress<M>,
+    block: u64,
+    epoch: u64,
+    role: Role,
+    addresses: ManagedVec<M, ManagedAddress<M>>,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct AddUsersToBlacklistEvent<M: ManagedTypeApi> {
+    admin: ManagedAddress<M>,
+    block: u64,
+    epoch: u64,
+    users: ManagedVec<M, ManagedAddress<M>>,
+    reason: u32,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct RemoveUsersFromBlacklistEvent<M: ManagedTypeApi> {
+    admin: ManagedAddress<M>,
+    block: u64,
+    epoch: u64,
+    users: ManagedVec<M, ManagedAddress<M>>,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct AdminActionEvent<M: ManagedTypeApi> {
+    user: ManagedAddress<M>,
+    block: u64,
+    epoch: u64,
+    action_id: u64,
+    pending_action: PendingAdminAction<M>,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct AddAddressGroupEvent<M: ManagedTypeApi> {
+    user: ManagedAddress<M>,
+    block: u64,
+    epoch: u64,
+    group_id: usize,
+    max_confirmed_tickets: usize,
+    max_winning_tickets: usize,
+    addresses: ManagedVec<M, ManagedAddress<M>>,
+}
+
 #[dharitri_sc::module]
 pub trait CommonEventsModule {
     fn emit_refund_ticket_payment_event(
@@ -112,6 +212,41 @@ pub trait CommonEventsModule {
         )
     }
 
+    fn emit_unconfirm_tickets_event(&self, tickets_unconfirmed: usize, total_confirmed: usize) {
+        let user = self.blockchain().get_caller();
+        let block = self.blockchain().get_block_nonce();
+        let epoch = self.blockchain().get_block_epoch();
+        self.unconfirm_tickets_event(
+            user.clone(),
+            block,
+            epoch,
+            UnconfirmTicketsEvent {
+                user,
+                block,
+                epoch,
+                tickets_unconfirmed,
+                total_confirmed,
+            },
+        )
+    }
+
+    fn emit_register_tickets_event(&self, nr_tickets: usize) {
+        let user = self.blockchain().get_caller();
+        let block = self.blockchain().get_block_nonce();
+        let epoch = self.blockchain().get_block_epoch();
+        self.register_tickets_event(
+            user.clone(),
+            block,
+            epoch,
+            RegisterTicketsEvent {
+                user,
+                block,
+                epoch,
+                nr_tickets,
+            },
+        )
+    }
+
     fn emit_filter_tickets_completed_event(&self, total_tickets_after_filtering: usize) {
         let user = self.blockchain().get_caller();
         let block = self.blockchain().get_block_nonce();
@@ -146,6 +281,248 @@ pub trait CommonEventsModule {
         )
     }
 
+    fn emit_select_waitlist_winners_completed_event(
+        &self,
+        forfeited_tickets: usize,
+        waitlist_winning_tickets: usize,
+    ) {
+        let user = self.blockchain().get_caller();
+        let block = self.blockchain().get_block_nonce();
+        let epoch = self.blockchain().get_block_epoch();
+        self.select_waitlist_winners_completed_event(
+            user.clone(),
+            block,
+            epoch,
+            SelectWaitlistWinnersCompletedEvent {
+                user,
+                block,
+                epoch,
+                forfeited_tickets,
+                waitlist_winning_tickets,
+            },
+        )
+    }
+
+    fn emit_redraw_winners_completed_event(
+        &self,
+        freed_winning_tickets: usize,
+        redrawn_winning_tickets: usize,
+    ) {
+        let user = self.blockchain().get_caller();
+        let block = self.blockchain().get_block_nonce();
+        let epoch = self.blockchain().get_block_epoch();
+        self.redraw_winners_completed_event(
+            user.clone(),
+            block,
+            epoch,
+            RedrawWinnersCompletedEvent {
+                user,
+                block,
+                epoch,
+                freed_winning_tickets,
+                redrawn_winning_tickets,
+            },
+        )
+    }
+
+    fn emit_soft_cap_not_reached_event(&self, total_confirmed_tickets: usize, soft_cap: usize) {
+        let user = self.blockchain().get_caller();
+        let block = self.blockchain().get_block_nonce();
+        let epoch = self.blockchain().get_block_epoch();
+        self.soft_cap_not_reached_event(
+            user.clone(),
+            block,
+            epoch,
+            SoftCapNotReachedEvent {
+                user,
+                block,
+                epoch,
+                total_confirmed_tickets,
+                soft_cap,
+            },
+        )
+    }
+
+    fn emit_seed_not_revealed_event(&self, reveal_deadline: u64) {
+        let user = self.blockchain().get_caller();
+        let block = self.blockchain().get_block_nonce();
+        let epoch = self.blockchain().get_block_epoch();
+        self.seed_not_revealed_event(
+            user.clone(),
+            block,
+            epoch,
+            SeedNotRevealedEvent {
+                user,
+                block,
+                epoch,
+                reveal_deadline,
+            },
+        )
+    }
+
+    fn emit_grant_role_event(&self, role: Role, addresses: ManagedVec<ManagedAddress>) {
+        let user = self.blockchain().get_caller();
+        let block = self.blockchain().get_block_nonce();
+        let epoch = self.blockchain().get_block_epoch();
+        self.grant_role_event(
+            user.clone(),
+            block,
+            epoch,
+            UpdateRoleEvent {
+                user,
+                block,
+                epoch,
+                role,
+                addresses,
+            },
+        )
+    }
+
+    fn emit_revoke_role_event(&self, role: Role, addresses: ManagedVec<ManagedAddress>) {
+        let user = self.blockchain().get_caller();
+        let block = self.blockchain().get_block_nonce();
+        let epoch = self.blockchain().get_block_epoch();
+        self.revoke_role_event(
+            user.clone(),
+            block,
+            epoch,
+            UpdateRoleEvent {
+                user,
+                block,
+                epoch,
+                role,
+                addresses,
+            },
+        )
+    }
+
+    fn emit_propose_admin_action_event(
+        &self,
+        action_id: u64,
+        pending_action: PendingAdminAction<Self::Api>,
+    ) {
+        let user = self.blockchain().get_caller();
+        let block = self.blockchain().get_block_nonce();
+        let epoch = self.blockchain().get_block_epoch();
+        self.propose_admin_action_event(
+            user.clone(),
+            block,
+            epoch,
+            AdminActionEvent {
+                user,
+                block,
+                epoch,
+                action_id,
+                pending_action,
+            },
+        )
+    }
+
+    fn emit_cancel_admin_action_event(
+        &self,
+        action_id: u64,
+        pending_action: PendingAdminAction<Self::Api>,
+    ) {
+        let user = self.blockchain().get_caller();
+        let block = self.blockchain().get_block_nonce();
+        let epoch = self.blockchain().get_block_epoch();
+        self.cancel_admin_action_event(
+            user.clone(),
+            block,
+            epoch,
+            AdminActionEvent {
+                user,
+                block,
+                epoch,
+                action_id,
+                pending_action,
+            },
+        )
+    }
+
+    fn emit_execute_admin_action_event(
+        &self,
+        action_id: u64,
+        pending_action: PendingAdminAction<Self::Api>,
+    ) {
+        let user = self.blockchain().get_caller();
+        let block = self.blockchain().get_block_nonce();
+        let epoch = self.blockchain().get_block_epoch();
+        self.execute_admin_action_event(
+            user.clone(),
+            block,
+            epoch,
+            AdminActionEvent {
+                user,
+                block,
+                epoch,
+                action_id,
+                pending_action,
+            },
+        )
+    }
+
+    fn emit_add_users_to_blacklist_event(&self, users: ManagedVec<ManagedAddress>, reason: u32) {
+        let admin = self.blockchain().get_caller();
+        let block = self.blockchain().get_block_nonce();
+        let epoch = self.blockchain().get_block_epoch();
+        self.add_users_to_blacklist_event(
+            admin.clone(),
+            block,
+            epoch,
+            AddUsersToBlacklistEvent {
+                admin,
+                block,
+                epoch,
+                users,
+                reason,
+            },
+        )
+    }
+
+    fn emit_remove_users_from_blacklist_event(&self, users: ManagedVec<ManagedAddress>) {
+        let admin = self.blockchain().get_caller();
+        let block = self.blockchain().get_block_nonce();
+        let epoch = self.blockchain().get_block_epoch();
+        self.remove_users_from_blacklist_event(
+            admin.clone(),
+            block,
+            epoch,
+            RemoveUsersFromBlacklistEvent {
+                admin,
+                block,
+                epoch,
+                users,
+            },
+        )
+    }
+
+    fn emit_add_address_group_event(
+        &self,
+        group_id: usize,
+        max_confirmed_tickets: usize,
+        max_winning_tickets: usize,
+        addresses: ManagedVec<ManagedAddress>,
+    ) {
+        let user = self.blockchain().get_caller();
+        let block = self.blockchain().get_block_nonce();
+        let epoch = self.blockchain().get_block_epoch();
+        self.add_address_group_event(
+            user.clone(),
+            block,
+            epoch,
+            AddAddressGroupEvent {
+                user,
+                block,
+                epoch,
+                group_id,
+                max_confirmed_tickets,
+                max_winning_tickets,
+                addresses,
+            },
+        )
+    }
+
     #[event("refundTicketPayment")]
     fn refund_ticket_payment_event(
         &self,
@@ -173,6 +550,24 @@ pub trait CommonEventsModule {
         confirm_tickets_event: ConfirmTicketsEvent<Self::Api>,
     );
 
+    #[event("unconfirmTickets")]
+    fn unconfirm_tickets_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] block: u64,
+        #[indexed] epoch: u64,
+        unconfirm_tickets_event: UnconfirmTicketsEvent<Self::Api>,
+    );
+
+    #[event("registerTickets")]
+    fn register_tickets_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] block: u64,
+        #[indexed] epoch: u64,
+        register_tickets_event: RegisterTicketsEvent<Self::Api>,
+    );
+
     #[event("filterTicketsCompleted")]
     fn filter_tickets_completed_event(
         &self,
@@ -190,4 +585,112 @@ pub trait CommonEventsModule {
         #[indexed] epoch: u64,
         select_winners_completed_event: SelectWinnersCompletedEvent<Self::Api>,
     );
+
+    #[event("selectWaitlistWinnersCompleted")]
+    fn select_waitlist_winners_completed_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] block: u64,
+        #[indexed] epoch: u64,
+        select_waitlist_winners_completed_event: SelectWaitlistWinnersCompletedEvent<Self::Api>,
+    );
+
+    #[event("redrawWinnersCompleted")]
+    fn redraw_winners_completed_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] block: u64,
+        #[indexed] epoch: u64,
+        redraw_winners_completed_event: RedrawWinnersCompletedEvent<Self::Api>,
+    );
+
+    #[event("softCapNotReached")]
+    fn soft_cap_not_reached_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] block: u64,
+        #[indexed] epoch: u64,
+        soft_cap_not_reached_event: SoftCapNotReachedEvent<Self::Api>,
+    );
+
+    #[event("seedNotRevealed")]
+    fn seed_not_revealed_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] block: u64,
+        #[indexed] epoch: u64,
+        seed_not_revealed_event: SeedNotRevealedEvent<Self::Api>,
+    );
+
+    #[event("grantRole")]
+    fn grant_role_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] block: u64,
+        #[indexed] epoch: u64,
+        grant_role_event: UpdateRoleEvent<Self::Api>,
+    );
+
+    #[event("revokeRole")]
+    fn revoke_role_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] block: u64,
+        #[indexed] epoch: u64,
+        revoke_role_event: UpdateRoleEvent<Self::Api>,
+    );
+
+    #[event("proposeAdminAction")]
+    fn propose_admin_action_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] block: u64,
+        #[indexed] epoch: u64,
+        propose_admin_action_event: AdminActionEvent<Self::Api>,
+    );
+
+    #[event("cancelAdminAction")]
+    fn cancel_admin_action_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] block: u64,
+        #[indexed] epoch: u64,
+        cancel_admin_action_event: AdminActionEvent<Self::Api>,
+    );
+
+    #[event("executeAdminAction")]
+    fn execute_admin_action_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] block: u64,
+        #[indexed] epoch: u64,
+        execute_admin_action_event: AdminActionEvent<Self::Api>,
+    );
+
+    #[event("addUsersToBlacklist")]
+    fn add_users_to_blacklist_event(
+        &self,
+        #[indexed] admin: ManagedAddress,
+        #[indexed] block: u64,
+        #[indexed] epoch: u64,
+        add_users_to_blacklist_event: AddUsersToBlacklistEvent<Self::Api>,
+    );
+
+    #[event("removeUsersFromBlacklist")]
+    fn remove_users_from_blacklist_event(
+        &self,
+        #[indexed] admin: ManagedAddress,
+        #[indexed] block: u64,
+        #[indexed] epoch: u64,
+        remove_users_from_blacklist_event: RemoveUsersFromBlacklistEvent<Self::Api>,
+    );
+
+    #[event("addAddressGroup")]
+    fn add_address_group_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] block: u64,
+        #[indexed] epoch: u64,
+        add_address_group_event: AddAddressGroupEvent<Self::Api>,
+    );
 }
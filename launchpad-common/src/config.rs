@@ -1,12 +1,48 @@
 dharitri_sc::imports!();
 dharitri_sc::derive_imports!();
 
+use crate::registration::RegistrationRule;
+
 #[derive(TypeAbi, TopEncode, TopDecode)]
 pub struct TokenAmountPair<M: ManagedTypeApi> {
     pub token_id: RewaOrDcdtTokenIdentifier<M>,
     pub amount: BigUint<M>,
 }
 
+/// Ticket price in a reference unit (e.g. USD), with `decimals` decimals
+#[derive(TypeAbi, TopEncode, TopDecode)]
+pub struct ReferenceTicketPrice<M: ManagedTypeApi> {
+    pub ticker: ManagedBuffer<M>,
+    pub amount: BigUint<M>,
+    pub decimals: u32,
+}
+
+/// Price per ticket paid by a user in a single token, in order of confirmation
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, ManagedVecItem)]
+pub struct TicketPayment<M: ManagedTypeApi> {
+    pub token_id: RewaOrDcdtTokenIdentifier<M>,
+    pub price_per_ticket: BigUint<M>,
+    pub nr_tickets: usize,
+}
+
+/// The ticket price is in the ticket payment token
+#[derive(TypeAbi, TopEncode, TopDecode)]
+pub struct TicketTier<M: ManagedTypeApi> {
+    pub ticket_price: BigUint<M>,
+    pub launchpad_tokens_per_ticket: BigUint<M>,
+}
+
+/// The ticket price descends linearly from the start price to the floor price between the two points,
+/// which are block nonces or block timestamps, depending on the `TimelineMode`
+#[derive(TypeAbi, TopEncode, TopDecode)]
+pub struct DutchAuction<M: ManagedTypeApi> {
+    pub start_price: BigUint<M>,
+    pub floor_price: BigUint<M>,
+    pub start_point: u64,
+    pub end_point: u64,
+}
+
+/// Start points are block nonces or block timestamps, depending on the `TimelineMode`
 #[derive(TypeAbi, TopEncode, TopDecode)]
 pub struct TimelineConfig {
     pub confirmation_period_start_block: u64,
@@ -14,6 +50,13 @@ pub struct TimelineConfig {
     pub claim_start_block: u64,
 }
 
+#[derive(TypeAbi, TopEncode, TopDecode, PartialEq, Clone, Copy, Default)]
+pub enum TimelineMode {
+    #[default]
+    BlockNonce,
+    Timestamp,
+}
+
 #[dharitri_sc::module]
 pub trait ConfigModule {
     #[inline]
@@ -21,10 +64,184 @@ pub trait ConfigModule {
         self.launchpad_tokens_deposited().get()
     }
 
+    fn get_current_timeline_point(&self) -> u64 {
+        match self.timeline_mode().get() {
+            TimelineMode::BlockNonce => self.blockchain().get_block_nonce(),
+            TimelineMode::Timestamp => self.blockchain().get_block_timestamp(),
+        }
+    }
+
+    fn require_timeline_mode(&self, timeline_mode: TimelineMode) {
+        require!(
+            self.timeline_mode().get() == timeline_mode,
+            "Invalid timeline mode"
+        );
+    }
+
+    fn get_ticket_price_for_token(&self, token_id: &RewaOrDcdtTokenIdentifier) -> BigUint {
+        let ticket_price: TokenAmountPair<Self::Api> = self.ticket_price().get();
+        if &ticket_price.token_id == token_id {
+            return ticket_price.amount;
+        }
+
+        match self.additional_ticket_prices().get(token_id) {
+            Some(price) => price,
+            None => sc_panic!("Wrong payment token used"),
+        }
+    }
+
+    /// True if the price paid per ticket may differ between users,
+    /// in which case ticket payments are settled as each user claims
+    fn has_variable_ticket_payments(&self) -> bool {
+        !self.additional_ticket_prices().is_empty() || !self.reference_ticket_price().is_empty()
+    }
+
+    #[inline]
+    fn has_ticket_tiers(&self) -> bool {
+        !self.ticket_tiers().is_empty()
+    }
+
+    fn has_registration_rule(&self) -> bool {
+        !matches!(self.registration_rule().get(), RegistrationRule::Disabled)
+    }
+
+    /// Tickets of each tier occupy a contiguous ID range, in ascending tier order
+    fn get_ticket_tier_id(&self, ticket_id: usize) -> usize {
+        let nr_tiers = self.ticket_tiers().len();
+        for tier_id in 1..=nr_tiers {
+            if self.tier_last_ticket_id(tier_id).get() >= ticket_id {
+                return tier_id;
+            }
+        }
+
+        sc_panic!("Ticket has no tier");
+    }
+
+    fn get_ticket_price_for_user(
+        &self,
+        address: &ManagedAddress,
+        token_id: &RewaOrDcdtTokenIdentifier,
+    ) -> BigUint {
+        let tier_id = self.user_ticket_tier(address).get();
+        if tier_id == 0 && !self.has_dutch_auction() {
+            return self.get_ticket_price_for_token(token_id);
+        }
+
+        let ticket_price: TokenAmountPair<Self::Api> = self.ticket_price().get();
+        require!(
+            &ticket_price.token_id == token_id,
+            "Wrong payment token used"
+        );
+
+        if tier_id == 0 {
+            return self.get_current_ticket_price();
+        }
+
+        self.ticket_tiers().get(tier_id).ticket_price
+    }
+
+    #[inline]
+    fn has_dutch_auction(&self) -> bool {
+        !self.dutch_auction().is_empty()
+    }
+
+    #[view(getCurrentTicketPrice)]
+    fn get_current_ticket_price(&self) -> BigUint {
+        if !self.has_dutch_auction() {
+            let ticket_price: TokenAmountPair<Self::Api> = self.ticket_price().get();
+            return ticket_price.amount;
+        }
+        if self.dutch_auction_sold_out().get() {
+            return self.clearing_ticket_price().get();
+        }
+
+        let auction: DutchAuction<Self::Api> = self.dutch_auction().get();
+        let current_point = self.get_current_timeline_point();
+        if current_point <= auction.start_point {
+            return auction.start_price;
+        }
+        if current_point >= auction.end_point {
+            return auction.floor_price;
+        }
+
+        let price_drop = (&auction.start_price - &auction.floor_price)
+            * (current_point - auction.start_point)
+            / (auction.end_point - auction.start_point);
+
+        auction.start_price - price_drop
+    }
+
+    /// With a Dutch auction, all the winning tickets are paid at the clearing price,
+    /// which is the price of the latest confirmation
+    fn get_winning_ticket_price(&self) -> BigUint {
+        if self.has_dutch_auction() {
+            self.clearing_ticket_price().get()
+        } else {
+            let ticket_price: TokenAmountPair<Self::Api> = self.ticket_price().get();
+            ticket_price.amount
+        }
+    }
+
+    fn get_launchpad_tokens_per_ticket_for_user(&self, address: &ManagedAddress) -> BigUint {
+        let tier_id = self.user_ticket_tier(address).get();
+        if tier_id == 0 {
+            return self.launchpad_tokens_per_winning_ticket().get();
+        }
+
+        self.ticket_tiers().get(tier_id).launchpad_tokens_per_ticket
+    }
+
+    /// Returns the ticket payment and the launchpad tokens for the winning tickets of all tiers
+    fn get_tier_winning_tickets_value(&self) -> (BigUint, BigUint) {
+        let mut ticket_payment = BigUint::zero();
+        let mut launchpad_tokens = BigUint::zero();
+        for (tier_index, tier) in self.ticket_tiers().iter().enumerate() {
+            let nr_winning_tickets = self.tier_nr_winning_tickets(tier_index + 1).get();
+            ticket_payment += tier.ticket_price * nr_winning_tickets as u32;
+            launchpad_tokens += tier.launchpad_tokens_per_ticket * nr_winning_tickets as u32;
+        }
+
+        (ticket_payment, launchpad_tokens)
+    }
+
+    /// Losing tickets are kept until the waitlist draw, which follows the claim deadline
+    fn is_waitlist_draw_pending(&self) -> bool {
+        !self.claim_deadline().is_empty() && !self.waitlist_winners_selected().get()
+    }
+
     #[view(getConfiguration)]
     #[storage_mapper("configuration")]
     fn configuration(&self) -> SingleValueMapper<TimelineConfig>;
 
+    #[view(getTimelineMode)]
+    #[storage_mapper("timelineMode")]
+    fn timeline_mode(&self) -> SingleValueMapper<TimelineMode>;
+
+    /// Block nonce or block timestamp, depending on the `TimelineMode`
+    #[view(getClaimDeadline)]
+    #[storage_mapper("claimDeadline")]
+    fn claim_deadline(&self) -> SingleValueMapper<u64>;
+
+    #[view(wereWaitlistWinnersSelected)]
+    #[storage_mapper("waitlistWinnersSelected")]
+    fn waitlist_winners_selected(&self) -> SingleValueMapper<bool>;
+
+    /// Winning tickets freed by blacklisting winners, the claim period waits for their redraw
+    #[view(getNrWinningTicketsToRedraw)]
+    #[storage_mapper("nrWinningTicketsToRedraw")]
+    fn nr_winning_tickets_to_redraw(&self) -> SingleValueMapper<usize>;
+
+    #[view(getDutchAuction)]
+    #[storage_mapper("dutchAuction")]
+    fn dutch_auction(&self) -> SingleValueMapper<DutchAuction<Self::Api>>;
+
+    #[view(getClearingTicketPrice)]
+    #[storage_mapper("clearingTicketPrice")]
+    fn clearing_ticket_price(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("dutchAuctionSoldOut")]
+    fn dutch_auction_sold_out(&self) -> SingleValueMapper<bool>;
+
     #[view(getLaunchpadTokenId)]
     #[storage_mapper("launchpadTokenId")]
     fn launchpad_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
@@ -37,10 +254,45 @@ pub trait ConfigModule {
     #[storage_mapper("ticketPrice")]
     fn ticket_price(&self) -> SingleValueMapper<TokenAmountPair<Self::Api>>;
 
+    #[view(getAdditionalTicketPrices)]
+    #[storage_mapper("additionalTicketPrices")]
+    fn additional_ticket_prices(&self) -> MapMapper<RewaOrDcdtTokenIdentifier, BigUint>;
+
+    #[view(getReferenceTicketPrice)]
+    #[storage_mapper("referenceTicketPrice")]
+    fn reference_ticket_price(&self) -> SingleValueMapper<ReferenceTicketPrice<Self::Api>>;
+
+    /// Tier IDs start from 1
+    #[view(getTicketTiers)]
+    #[storage_mapper("ticketTiers")]
+    fn ticket_tiers(&self) -> VecMapper<TicketTier<Self::Api>>;
+
+    #[view(getUserTicketTier)]
+    #[storage_mapper("userTicketTier")]
+    fn user_ticket_tier(&self, address: &ManagedAddress) -> SingleValueMapper<usize>;
+
+    #[storage_mapper("tierLastTicketId")]
+    fn tier_last_ticket_id(&self, tier_id: usize) -> SingleValueMapper<usize>;
+
+    #[view(getTierNumberOfWinningTickets)]
+    #[storage_mapper("tierNrWinningTickets")]
+    fn tier_nr_winning_tickets(&self, tier_id: usize) -> SingleValueMapper<usize>;
+
     #[view(getNumberOfWinningTickets)]
     #[storage_mapper("nrWinningTickets")]
     fn nr_winning_tickets(&self) -> SingleValueMapper<usize>;
 
+    #[storage_mapper("registrationRule")]
+    fn registration_rule(&self) -> SingleValueMapper<RegistrationRule>;
+
+    #[view(isProRataAllocation)]
+    #[storage_mapper("proRataAllocation")]
+    fn pro_rata_allocation(&self) -> SingleValueMapper<bool>;
+
+    #[view(getSoftCap)]
+    #[storage_mapper("softCap")]
+    fn soft_cap(&self) -> SingleValueMapper<usize>;
+
     #[view(getTotalLaunchpadTokensDeposited)]
     #[storage_mapper("totalLaunchpadTokensDeposited")]
     fn total_launchpad_tokens_deposited(&self) -> SingleValueMapper<BigUint>;
@@ -50,4 +302,14 @@ pub trait ConfigModule {
 
     #[storage_mapper("claimableTicketPayment")]
     fn claimable_ticket_payment(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("claimableTicketPaymentPerToken")]
+    fn claimable_ticket_payment_per_token(&self) -> MapMapper<RewaOrDcdtTokenIdentifier, BigUint>;
+
+    #[view(getUserTicketPayments)]
+    #[storage_mapper("userTicketPayments")]
+    fn user_ticket_payments(
+        &self,
+        address: &ManagedAddress,
+    ) -> SingleValueMapper<ManagedVec<TicketPayment<Self::Api>>>;
 }
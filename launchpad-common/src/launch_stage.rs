@@ -9,6 +9,7 @@ pub enum LaunchStage {
     Confirm,
     WinnerSelection,
     Claim,
+    Cancelled,
 }
 
 #[derive(TypeAbi, TopEncode, TopDecode, Default)]
@@ -22,24 +23,31 @@ pub struct Flags {
 #[dharitri_sc::module]
 pub trait LaunchStageModule: crate::config::ConfigModule {
     fn get_launch_stage(&self) -> LaunchStage {
-        let current_block = self.blockchain().get_block_nonce();
+        if self.launchpad_cancelled().get() {
+            return LaunchStage::Cancelled;
+        }
+
+        let current_point = self.get_current_timeline_point();
         let config: TimelineConfig = self.configuration().get();
         let flags: Flags = self.flags().get();
 
-        if current_block < config.confirmation_period_start_block {
+        if current_point < config.confirmation_period_start_block {
             return LaunchStage::AddTickets;
         }
-        if current_block < config.winner_selection_start_block {
+        if current_point < config.winner_selection_start_block {
             return LaunchStage::Confirm;
         }
 
-        let both_selection_steps_completed =
-            flags.were_winners_selected && flags.was_additional_step_completed;
-        if current_block >= config.winner_selection_start_block && !both_selection_steps_completed {
+        // no winners are selected with pro-rata allocation
+        let both_selection_steps_completed = self.pro_rata_allocation().get()
+            || (flags.were_winners_selected
+                && flags.was_additional_step_completed
+                && self.nr_winning_tickets_to_redraw().get() == 0);
+        if current_point >= config.winner_selection_start_block && !both_selection_steps_completed {
             return LaunchStage::WinnerSelection;
         }
-        if current_block >= config.winner_selection_start_block
-            && current_block < config.claim_start_block
+        if current_point >= config.winner_selection_start_block
+            && current_point < config.claim_start_block
         {
             return LaunchStage::WinnerSelection;
         }
@@ -79,6 +87,14 @@ pub trait LaunchStageModule: crate::config::ConfigModule {
         );
     }
 
+    #[inline]
+    fn require_before_claim_period(&self) {
+        require!(
+            self.get_launch_stage() < LaunchStage::Claim,
+            "Claim period has started"
+        );
+    }
+
     #[inline]
     fn require_claim_period(&self) {
         require!(
@@ -87,7 +103,19 @@ pub trait LaunchStageModule: crate::config::ConfigModule {
         );
     }
 
+    #[inline]
+    fn require_cancelled_period(&self) {
+        require!(
+            self.get_launch_stage() == LaunchStage::Cancelled,
+            "Launchpad not cancelled"
+        );
+    }
+
     #[view(getLaunchStageFlags)]
     #[storage_mapper("flags")]
     fn flags(&self) -> SingleValueMapper<Flags>;
+
+    #[view(isLaunchpadCancelled)]
+    #[storage_mapper("launchpadCancelled")]
+    fn launchpad_cancelled(&self) -> SingleValueMapper<bool>;
 }
@@ -3,20 +3,26 @@
 dharitri_sc::imports!();
 dharitri_sc::derive_imports!();
 
+pub mod address_groups;
 pub mod blacklist;
+pub mod cancellation;
 pub mod common_events;
 pub mod config;
 pub mod launch_stage;
 pub mod ongoing_operation;
 pub mod permissions;
+pub mod price_oracle;
+pub mod pro_rata;
 pub mod random;
+pub mod registration;
 pub mod setup;
 pub mod tickets;
+pub mod timelock;
 pub mod token_send;
 pub mod user_interactions;
 pub mod winner_selection;
 
-use config::TimelineConfig;
+use config::{TimelineConfig, TimelineMode};
 use launch_stage::Flags;
 use tickets::FIRST_TICKET_ID;
 
@@ -29,8 +35,10 @@ pub trait LaunchpadMain:
     + winner_selection::WinnerSelectionModule
     + ongoing_operation::OngoingOperationModule
     + permissions::PermissionsModule
+    + address_groups::AddressGroupsModule
     + blacklist::BlacklistModule
     + token_send::TokenSendModule
+    + price_oracle::PriceOracleModule
     + common_events::CommonEventsModule
     + user_interactions::UserInteractionsModule
     + dharitri_sc_modules::pause::PauseModule
@@ -47,6 +55,7 @@ pub trait LaunchpadMain:
         winner_selection_start_block: u64,
         claim_start_block: u64,
         flags: Flags,
+        timeline_mode: TimelineMode,
     ) {
         if ticket_payment_token.is_dcdt() {
             require!(
@@ -61,6 +70,8 @@ pub trait LaunchpadMain:
         self.try_set_ticket_price(ticket_payment_token, ticket_price);
         self.try_set_nr_winning_tickets(nr_winning_tickets);
 
+        self.timeline_mode().set(timeline_mode);
+
         let config = TimelineConfig {
             confirmation_period_start_block,
             winner_selection_start_block,
@@ -69,8 +80,5 @@ pub trait LaunchpadMain:
         self.require_valid_time_periods(&config);
         self.configuration().set(&config);
         self.flags().set_if_empty(flags);
-
-        let caller = self.blockchain().get_caller();
-        self.support_address().set_if_empty(&caller);
     }
 }
@@ -18,10 +18,21 @@ pub enum OngoingOperationType<M: ManagedTypeApi + CryptoApi> {
     SelectWinners {
         rng: Random<M>,
         ticket_position: usize,
+        nr_selected: usize,
     },
     AdditionalSelection {
         encoded_data: ManagedBuffer<M>,
     },
+    SelectWaitlistWinners {
+        rng: Random<M>,
+        ticket_position: usize,
+        nr_selected: usize,
+    },
+    RedrawWinners {
+        rng: Random<M>,
+        ticket_position: usize,
+        nr_redrawn: usize,
+    },
 }
 
 pub type LoopOp = bool;
@@ -94,14 +105,52 @@ pub trait OngoingOperationModule {
         }
     }
 
-    fn load_select_winners_operation(&self) -> (Random<Self::Api>, usize) {
+    fn load_select_winners_operation<InitRngFn: FnOnce() -> Random<Self::Api>>(
+        &self,
+        init_rng_fn: InitRngFn,
+    ) -> (Random<Self::Api>, usize, usize) {
         let ongoing_operation = self.current_ongoing_operation().get();
         match ongoing_operation {
-            OngoingOperationType::None => (Random::default(), FIRST_TICKET_ID),
+            OngoingOperationType::None => (init_rng_fn(), FIRST_TICKET_ID, 0),
             OngoingOperationType::SelectWinners {
                 rng,
                 ticket_position,
-            } => (rng, ticket_position),
+                nr_selected,
+            } => (rng, ticket_position, nr_selected),
+            _ => sc_panic!(ANOTHER_OP_ERR_MSG),
+        }
+    }
+
+    fn load_select_waitlist_winners_operation<InitRngFn: FnOnce() -> Random<Self::Api>>(
+        &self,
+        first_ticket_position: usize,
+        init_rng_fn: InitRngFn,
+    ) -> (Random<Self::Api>, usize, usize) {
+        let ongoing_operation = self.current_ongoing_operation().get();
+        match ongoing_operation {
+            OngoingOperationType::None => (init_rng_fn(), first_ticket_position, 0),
+            OngoingOperationType::SelectWaitlistWinners {
+                rng,
+                ticket_position,
+                nr_selected,
+            } => (rng, ticket_position, nr_selected),
+            _ => sc_panic!(ANOTHER_OP_ERR_MSG),
+        }
+    }
+
+    fn load_redraw_winners_operation<InitRngFn: FnOnce() -> Random<Self::Api>>(
+        &self,
+        first_ticket_position: usize,
+        init_rng_fn: InitRngFn,
+    ) -> (Random<Self::Api>, usize, usize) {
+        let ongoing_operation = self.current_ongoing_operation().get();
+        match ongoing_operation {
+            OngoingOperationType::None => (init_rng_fn(), first_ticket_position, 0),
+            OngoingOperationType::RedrawWinners {
+                rng,
+                ticket_position,
+                nr_redrawn,
+            } => (rng, ticket_position, nr_redrawn),
             _ => sc_panic!(ANOTHER_OP_ERR_MSG),
         }
     }
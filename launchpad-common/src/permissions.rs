@@ -1,25 +1,84 @@
 dharitri_sc::imports!();
+dharitri_sc::derive_imports!();
+
+/// The owner implicitly holds every role
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Copy)]
+pub enum Role {
+    /// Adds tickets and manages ticket pricing and registration
+    TicketManager,
+    /// Manages the blacklist
+    Compliance,
+    /// Deposits launchpad tokens and claims ticket payments
+    Treasurer,
+    /// Runs the launchpad timeline, winner selection steps and token setup
+    Operator,
+}
 
 #[dharitri_sc::module]
-pub trait PermissionsModule {
+pub trait PermissionsModule: crate::common_events::CommonEventsModule {
     #[only_owner]
-    #[endpoint(setSupportAddress)]
-    fn add_support_address(&self, address: ManagedAddress) {
-        self.support_address().set(&address);
+    #[endpoint(grantRole)]
+    fn grant_role(&self, role: Role, addresses: MultiValueEncoded<ManagedAddress>) {
+        let mut role_members = self.role_members(role);
+        let mut granted_addresses = ManagedVec::new();
+        for address in addresses {
+            require!(!address.is_zero(), "Invalid address");
+
+            if role_members.insert(address.clone()) {
+                granted_addresses.push(address);
+            }
+        }
+
+        self.emit_grant_role_event(role, granted_addresses);
     }
 
-    fn require_extended_permissions(&self) {
+    #[only_owner]
+    #[endpoint(revokeRole)]
+    fn revoke_role(&self, role: Role, addresses: MultiValueEncoded<ManagedAddress>) {
+        let mut role_members = self.role_members(role);
+        let mut revoked_addresses = ManagedVec::new();
+        for address in addresses {
+            if role_members.swap_remove(&address) {
+                revoked_addresses.push(address);
+            }
+        }
+
+        self.emit_revoke_role_event(role, revoked_addresses);
+    }
+
+    fn require_role(&self, role: Role) {
         let caller = self.blockchain().get_caller();
-        let owner = self.blockchain().get_owner_address();
-        let support_address = self.support_address().get();
+        require!(self.has_role(role, caller), "Permission denied");
+    }
+
+    /// Contracts deployed before roles were introduced had a single support address,
+    /// which keeps managing the blacklist as a Compliance member
+    fn migrate_support_address(&self) {
+        let support_address_mapper = self.legacy_support_address();
+        if support_address_mapper.is_empty() {
+            return;
+        }
 
-        require!(
-            caller == owner || caller == support_address,
-            "Permission denied"
-        );
+        let support_address = support_address_mapper.take();
+        let mut compliance_members = self.role_members(Role::Compliance);
+        if compliance_members.insert(support_address.clone()) {
+            self.emit_grant_role_event(
+                Role::Compliance,
+                ManagedVec::from_single_item(support_address),
+            );
+        }
     }
 
-    #[view(getSupportAddress)]
+    #[view(hasRole)]
+    fn has_role(&self, role: Role, address: ManagedAddress) -> bool {
+        address == self.blockchain().get_owner_address()
+            || self.role_members(role).contains(&address)
+    }
+
+    #[view(getRoleMembers)]
+    #[storage_mapper("roleMembers")]
+    fn role_members(&self, role: Role) -> UnorderedSetMapper<ManagedAddress>;
+
     #[storage_mapper("supportAddress")]
-    fn support_address(&self) -> SingleValueMapper<ManagedAddress>;
+    fn legacy_support_address(&self) -> SingleValueMapper<ManagedAddress>;
 }
@@ -0,0 +1,170 @@
+dharitri_sc::imports!();
+dharitri_sc::derive_imports!();
+
+use crate::{
+    config::{ReferenceTicketPrice, TokenAmountPair},
+    permissions::Role,
+};
+
+pub mod price_feed_proxy {
+    dharitri_sc::imports!();
+
+    pub type PriceFeedResult<M> =
+        MultiValue6<u32, ManagedBuffer<M>, ManagedBuffer<M>, u64, BigUint<M>, u8>;
+
+    #[dharitri_sc::proxy]
+    pub trait PriceFeedProxy {
+        #[view(latestPriceFeedOptional)]
+        fn latest_price_feed_optional(
+            &self,
+            from: ManagedBuffer,
+            to: ManagedBuffer,
+        ) -> OptionalValue<PriceFeedResult<Self::Api>>;
+    }
+}
+
+#[derive(TypeAbi, TopEncode, TopDecode)]
+pub struct OraclePaymentToken<M: ManagedTypeApi> {
+    pub ticker: ManagedBuffer<M>,
+    pub decimals: u32,
+}
+
+#[dharitri_sc::module]
+pub trait PriceOracleModule:
+    crate::launch_stage::LaunchStageModule
+    + crate::config::ConfigModule
+    + crate::permissions::PermissionsModule
+{
+    /// The ticket price is converted into the payment token at confirmation time,
+    /// using the `token_ticker/reference_ticker` pair of the price feed.
+    /// Prices older than `max_price_age` seconds are rejected.
+    #[endpoint(setReferenceTicketPrice)]
+    fn set_reference_ticket_price(
+        &self,
+        price_feed_address: ManagedAddress,
+        ticker: ManagedBuffer,
+        amount: BigUint,
+        decimals: u32,
+        max_price_age: u64,
+    ) {
+        self.require_role(Role::TicketManager);
+        self.require_add_tickets_period();
+        require!(
+            !price_feed_address.is_zero()
+                && self.blockchain().is_smart_contract(&price_feed_address),
+            "Invalid SC address"
+        );
+        require!(!ticker.is_empty(), "Invalid ticker");
+        require!(amount > 0, "Ticket price must be higher than 0");
+        require!(max_price_age > 0, "Invalid max price age");
+        require!(
+            !self.has_ticket_tiers(),
+            "Ticket tiers cannot be used with variable ticket payments"
+        );
+        require!(
+            !self.has_dutch_auction(),
+            "Dutch auction cannot be used with variable ticket payments"
+        );
+
+        self.price_feed_address().set(&price_feed_address);
+        self.max_oracle_price_age().set(max_price_age);
+        self.reference_ticket_price().set(&ReferenceTicketPrice {
+            ticker,
+            amount,
+            decimals,
+        });
+    }
+
+    #[endpoint(setOraclePaymentToken)]
+    fn set_oracle_payment_token(
+        &self,
+        token_id: RewaOrDcdtTokenIdentifier,
+        ticker: ManagedBuffer,
+        decimals: u32,
+    ) {
+        self.require_role(Role::TicketManager);
+        self.require_add_tickets_period();
+        require!(
+            !self.reference_ticket_price().is_empty(),
+            "Reference ticket price not set"
+        );
+        require!(!ticker.is_empty(), "Invalid ticker");
+
+        let ticket_price: TokenAmountPair<Self::Api> = self.ticket_price().get();
+        require!(
+            token_id == ticket_price.token_id
+                || self.additional_ticket_prices().contains_key(&token_id),
+            "Token not accepted for ticket payment"
+        );
+
+        self.oracle_payment_token(&token_id)
+            .set(&OraclePaymentToken { ticker, decimals });
+    }
+
+    #[endpoint(removeOraclePaymentToken)]
+    fn remove_oracle_payment_token(&self, token_id: RewaOrDcdtTokenIdentifier) {
+        self.require_role(Role::TicketManager);
+        self.require_add_tickets_period();
+
+        self.oracle_payment_token(&token_id).clear();
+    }
+
+    fn get_oracle_ticket_price(&self, token_id: &RewaOrDcdtTokenIdentifier) -> Option<BigUint> {
+        let oracle_token_mapper = self.oracle_payment_token(token_id);
+        if oracle_token_mapper.is_empty() {
+            return None;
+        }
+
+        let oracle_token = oracle_token_mapper.get();
+        let reference_price = self.reference_ticket_price().get();
+        let price_feed_address = self.price_feed_address().get();
+        let opt_price_feed: OptionalValue<price_feed_proxy::PriceFeedResult<Self::Api>> = self
+            .price_feed_proxy_builder(price_feed_address)
+            .latest_price_feed_optional(oracle_token.ticker, reference_price.ticker)
+            .execute_on_dest_context();
+
+        let (_, _, _, price_timestamp, token_price, price_decimals) = match opt_price_feed {
+            OptionalValue::Some(price_feed) => price_feed.into_tuple(),
+            OptionalValue::None => sc_panic!("Price not available"),
+        };
+        require!(token_price > 0, "Invalid oracle price");
+
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        let max_price_age = self.max_oracle_price_age().get();
+        require!(
+            current_timestamp <= price_timestamp + max_price_age,
+            "Oracle price is stale"
+        );
+
+        // amount * 10^token_decimals * 10^price_decimals / (token_price * 10^reference_decimals),
+        // rounded up
+        let numerator = reference_price.amount
+            * BigUint::from(10u32).pow(oracle_token.decimals)
+            * BigUint::from(10u32).pow(price_decimals as u32);
+        let denominator = token_price * BigUint::from(10u32).pow(reference_price.decimals);
+        let ticket_price = (numerator + &denominator - 1u32) / denominator;
+
+        Some(ticket_price)
+    }
+
+    #[view(getPriceFeedAddress)]
+    #[storage_mapper("priceFeedAddress")]
+    fn price_feed_address(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getMaxOraclePriceAge)]
+    #[storage_mapper("maxOraclePriceAge")]
+    fn max_oracle_price_age(&self) -> SingleValueMapper<u64>;
+
+    #[view(getOraclePaymentToken)]
+    #[storage_mapper("oraclePaymentToken")]
+    fn oracle_payment_token(
+        &self,
+        token_id: &RewaOrDcdtTokenIdentifier,
+    ) -> SingleValueMapper<OraclePaymentToken<Self::Api>>;
+
+    #[proxy]
+    fn price_feed_proxy_builder(
+        &self,
+        sc_address: ManagedAddress,
+    ) -> price_feed_proxy::Proxy<Self::Api>;
+}
@@ -0,0 +1,143 @@
+dharitri_sc::imports!();
+
+use crate::permissions::Role;
+
+#[dharitri_sc::module]
+pub trait ProRataModule:
+    crate::launch_stage::LaunchStageModule
+    + crate::config::ConfigModule
+    + crate::tickets::TicketsModule
+    + crate::token_send::TokenSendModule
+    + crate::user_interactions::UserInteractionsModule
+    + crate::cancellation::CancellationModule
+    + crate::permissions::PermissionsModule
+    + crate::common_events::CommonEventsModule
+{
+    /// Instead of drawing winners, every confirmed ticket receives an equal share
+    /// of the deposited launchpad tokens, and the payment for the excess is refunded
+    #[endpoint(setProRataAllocation)]
+    fn set_pro_rata_allocation(&self, enabled: bool) {
+        self.require_role(Role::TicketManager);
+        self.require_add_tickets_period();
+        if enabled {
+            require!(
+                !self.has_ticket_tiers()
+                    && !self.weighted_lottery().get()
+                    && self.claim_deadline().is_empty()
+                    && !self.has_dutch_auction(),
+                "Pro-rata allocation cannot be used with ticket tiers, the weighted lottery, a claim deadline or a Dutch auction"
+            );
+        }
+
+        self.pro_rata_allocation().set(enabled);
+    }
+
+    fn claim_pro_rata_launchpad_tokens<
+        SendLaunchpadTokensFn: Fn(&Self, &ManagedAddress, &DcdtTokenPayment<Self::Api>),
+    >(
+        &self,
+        send_fn: SendLaunchpadTokensFn,
+    ) {
+        self.require_claim_period();
+        if self.try_cancel_below_soft_cap() {
+            self.claim_cancellation_refund();
+            return;
+        }
+
+        let caller = self.blockchain().get_caller();
+        require!(!self.has_user_claimed(&caller), "Already claimed");
+
+        let ticket_range = self.try_get_ticket_range(&caller);
+        let nr_confirmed_tickets = self.nr_confirmed_tickets(&caller).take();
+        self.ticket_range_for_address(&caller).clear();
+        self.ticket_batch(ticket_range.first_id).clear();
+
+        self.claim_list().add(&caller);
+        self.pro_rata_claimed_tickets()
+            .update(|claimed_tickets| *claimed_tickets += nr_confirmed_tickets);
+
+        let allocation_denominator = self.get_pro_rata_allocation_denominator();
+        self.settle_pro_rata_ticket_payment(&caller, allocation_denominator);
+
+        let launchpad_tokens_amount = self.total_launchpad_tokens_deposited().get()
+            * nr_confirmed_tickets as u32
+            / allocation_denominator as u32;
+        if launchpad_tokens_amount > 0 {
+            let launchpad_token_id = self.launchpad_token_id().get();
+            let payment = DcdtTokenPayment::new(launchpad_token_id, 0, launchpad_tokens_amount);
+            send_fn(self, &caller, &payment);
+        }
+    }
+
+    fn claim_pro_rata_ticket_payment(&self) {
+        self.require_claim_period();
+        if self.try_cancel_below_soft_cap() {
+            return;
+        }
+
+        let owner = self.blockchain().get_owner_address();
+        let _ = self.send_claimable_ticket_payment(&owner);
+
+        // allocations are rounded down, so the dust may be sent before everyone claimed
+        let nr_unclaimed_tickets =
+            self.total_confirmed_tickets().get() - self.pro_rata_claimed_tickets().get();
+        let launchpad_tokens_needed = self.total_launchpad_tokens_deposited().get()
+            * nr_unclaimed_tickets as u32
+            / self.get_pro_rata_allocation_denominator() as u32;
+
+        let launchpad_token_id = self.launchpad_token_id().get();
+        let launchpad_tokens_balance = self.blockchain().get_dcdt_balance(
+            &self.blockchain().get_sc_address(),
+            &launchpad_token_id,
+            0,
+        );
+        let extra_launchpad_tokens = launchpad_tokens_balance - launchpad_tokens_needed;
+        if extra_launchpad_tokens > 0 {
+            self.send()
+                .direct_dcdt(&owner, &launchpad_token_id, 0, &extra_launchpad_tokens);
+        }
+    }
+
+    /// Each payment is kept in the ratio of winning tickets to the denominator, rounded down
+    fn settle_pro_rata_ticket_payment(
+        &self,
+        address: &ManagedAddress,
+        allocation_denominator: usize,
+    ) {
+        let payments = self.user_ticket_payments(address).take();
+        let nr_winning_tickets = self.nr_winning_tickets().get();
+        let has_variable_ticket_payments = self.has_variable_ticket_payments();
+        for payment in &payments {
+            let paid_amount = payment.price_per_ticket * payment.nr_tickets as u32;
+            let kept_amount =
+                &paid_amount * nr_winning_tickets as u32 / allocation_denominator as u32;
+            let refund_amount = paid_amount - &kept_amount;
+            if refund_amount > 0 {
+                self.send()
+                    .direct(address, &payment.token_id, 0, &refund_amount);
+            }
+
+            if has_variable_ticket_payments {
+                self.claimable_ticket_payment_per_token()
+                    .entry(payment.token_id)
+                    .or_default()
+                    .update(|claimable| *claimable += kept_amount);
+            } else {
+                self.claimable_ticket_payment()
+                    .update(|claimable| *claimable += kept_amount);
+            }
+        }
+    }
+
+    /// When undersubscribed, every confirmed ticket receives the full amount per winning ticket
+    fn get_pro_rata_allocation_denominator(&self) -> usize {
+        let total_confirmed_tickets = self.total_confirmed_tickets().get();
+        let nr_winning_tickets = self.nr_winning_tickets().get();
+
+        core::cmp::max(total_confirmed_tickets, nr_winning_tickets)
+    }
+
+    #[view(getProRataClaimedTickets)]
+    #[storage_mapper("proRataClaimedTickets")]
+    fn pro_rata_claimed_tickets(&self) -> SingleValueMapper<usize>;
+}
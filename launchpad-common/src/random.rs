@@ -32,6 +32,10 @@ impl<M: ManagedTypeApi + CryptoApi> Random<M> {
         }
     }
 
+    pub fn from_seed(seed: ManagedBuffer<M>) -> Self {
+        Self { seed, index: 0 }
+    }
+
     pub fn next_usize(&mut self) -> usize {
         if self.index + USIZE_BYTES > HASH_LEN {
             self.hash_seed();
@@ -59,6 +63,19 @@ impl<M: ManagedTypeApi + CryptoApi> Random<M> {
         }
     }
 
+    /// Range is [min, max), built from two consecutive `next_usize` values
+    pub fn next_u64_in_range(&mut self, min: u64, max: u64) -> u64 {
+        let high = self.next_usize() as u32 as u64;
+        let low = self.next_usize() as u32 as u64;
+        let rand = (high << 32) | low;
+
+        if min >= max {
+            min
+        } else {
+            min + rand % (max - min)
+        }
+    }
+
     fn hash_seed(&mut self) {
         let handle = self.seed.get_raw_handle();
         M::crypto_api_impl().sha256_managed(handle.into(), handle.into());
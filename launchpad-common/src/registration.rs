@@ -0,0 +1,130 @@
+dharitri_sc::imports!();
+dharitri_sc::derive_imports!();
+
+use crate::permissions::Role;
+
+#[derive(TypeAbi, TopEncode, TopDecode)]
+pub enum RegistrationRule {
+    Disabled,
+    FlatCap {
+        max_tickets_per_user: usize,
+    },
+    WhitelistWithCap {
+        max_tickets_per_user: usize,
+    },
+}
+
+#[dharitri_sc::module]
+pub trait RegistrationModule:
+    crate::launch_stage::LaunchStageModule
+    + crate::config::ConfigModule
+    + crate::tickets::TicketsModule
+    + crate::blacklist::BlacklistModule
+    + crate::permissions::PermissionsModule
+    + crate::token_send::TokenSendModule
+    + crate::common_events::CommonEventsModule
+    + dharitri_sc_modules::pause::PauseModule
+{
+    #[endpoint(setRegistrationRule)]
+    fn set_registration_rule(&self, rule: RegistrationRule) {
+        self.require_role(Role::TicketManager);
+        self.require_add_tickets_period();
+
+        match rule {
+            RegistrationRule::Disabled => {}
+            RegistrationRule::FlatCap {
+                max_tickets_per_user,
+            }
+            | RegistrationRule::WhitelistWithCap {
+                max_tickets_per_user,
+            } => {
+                require!(max_tickets_per_user > 0, "Invalid max tickets per user");
+            }
+        }
+
+        self.registration_rule().set(rule);
+    }
+
+    #[endpoint(addUsersToRegistrationWhitelist)]
+    fn add_users_to_registration_whitelist(&self, users: MultiValueEncoded<ManagedAddress>) {
+        self.require_role(Role::TicketManager);
+        self.require_add_tickets_period();
+
+        let whitelist_mapper = self.registration_whitelist();
+        for user in users {
+            whitelist_mapper.add(&user);
+        }
+    }
+
+    #[endpoint(removeUsersFromRegistrationWhitelist)]
+    fn remove_users_from_registration_whitelist(&self, users: MultiValueEncoded<ManagedAddress>) {
+        self.require_role(Role::TicketManager);
+        self.require_add_tickets_period();
+
+        let whitelist_mapper = self.registration_whitelist();
+        for user in users {
+            whitelist_mapper.remove(&user);
+        }
+    }
+
+    /// Users register themselves during the add tickets period,
+    /// getting the same ticket range as the ones added through `addTickets`
+    #[endpoint(registerTickets)]
+    fn register_tickets(&self, nr_tickets: usize) {
+        self.require_not_paused();
+        self.require_add_tickets_period();
+        require!(
+            !self.has_ticket_tiers(),
+            "Tickets must be added with a tier"
+        );
+        self.blockchain().check_caller_is_user_account();
+
+        let caller = self.blockchain().get_caller();
+        require!(
+            !self.is_user_blacklisted(&caller),
+            "You have been put into the blacklist and may not register"
+        );
+
+        let allowance = self.get_registration_allowance(caller.clone());
+        require!(
+            nr_tickets > 0 && nr_tickets <= allowance,
+            "Invalid number of tickets"
+        );
+
+        self.try_create_tickets(caller, nr_tickets);
+
+        self.emit_register_tickets_event(nr_tickets);
+    }
+
+    #[view(getRegistrationAllowance)]
+    fn get_registration_allowance(&self, address: ManagedAddress) -> usize {
+        match self.registration_rule().get() {
+            RegistrationRule::Disabled => 0,
+            RegistrationRule::FlatCap {
+                max_tickets_per_user,
+            } => max_tickets_per_user,
+            RegistrationRule::WhitelistWithCap {
+                max_tickets_per_user,
+            } => {
+                if self.registration_whitelist().contains(&address) {
+                    max_tickets_per_user
+                } else {
+                    0
+                }
+            }
+        }
+    }
+
+    #[view(isUserInRegistrationWhitelist)]
+    fn is_user_in_registration_whitelist(&self, address: ManagedAddress) -> bool {
+        self.registration_whitelist().contains(&address)
+    }
+
+    #[view(getRegistrationRule)]
+    fn get_registration_rule(&self) -> RegistrationRule {
+        self.registration_rule().get()
+    }
+
+    #[storage_mapper("registrationWhitelist")]
+    fn registration_whitelist(&self) -> WhitelistMapper<Self::Api, ManagedAddress>;
+}
@@ -1,11 +1,15 @@
 dharitri_sc::imports!();
 
-use crate::config::{TimelineConfig, TokenAmountPair};
+use crate::{
+    config::{DutchAuction, TimelineConfig, TimelineMode, TokenAmountPair},
+    permissions::Role,
+};
 
 #[dharitri_sc::module]
 pub trait SetupModule:
     crate::launch_stage::LaunchStageModule
     + crate::config::ConfigModule
+    + crate::permissions::PermissionsModule
     + crate::common_events::CommonEventsModule
 {
     fn deposit_launchpad_tokens(&self, total_winning_tickets: usize) {
@@ -18,17 +22,42 @@ pub trait SetupModule:
         let launchpad_token_id = self.launchpad_token_id().get();
         require!(payment_token == launchpad_token_id, "Wrong token");
 
-        let amount_per_ticket = self.launchpad_tokens_per_winning_ticket().get();
-        let amount_needed = amount_per_ticket * (total_winning_tickets as u32);
+        let amount_needed = if self.has_ticket_tiers() {
+            self.get_max_tier_launchpad_tokens_needed(total_winning_tickets)
+        } else {
+            let amount_per_ticket = self.launchpad_tokens_per_winning_ticket().get();
+            amount_per_ticket * (total_winning_tickets as u32)
+        };
         require!(payment_amount == amount_needed, "Wrong amount");
 
         self.launchpad_tokens_deposited().set(true);
         self.total_launchpad_tokens_deposited().set(payment_amount);
     }
 
-    #[only_owner]
+    /// Each tier may win at most as many tickets as it has, up to the total winning tickets.
+    /// Any tokens left after the winners are known are returned on `claimTicketPayment`.
+    fn get_max_tier_launchpad_tokens_needed(&self, total_winning_tickets: usize) -> BigUint {
+        let mut amount_needed = BigUint::zero();
+        let mut previous_last_ticket_id = 0;
+        for (tier_index, tier) in self.ticket_tiers().iter().enumerate() {
+            let last_ticket_id = self.tier_last_ticket_id(tier_index + 1).get();
+            if last_ticket_id <= previous_last_ticket_id {
+                continue;
+            }
+
+            let nr_tier_tickets = last_ticket_id - previous_last_ticket_id;
+            let max_winning_tickets = core::cmp::min(nr_tier_tickets, total_winning_tickets);
+            amount_needed += tier.launchpad_tokens_per_ticket * max_winning_tickets as u32;
+
+            previous_last_ticket_id = last_ticket_id;
+        }
+
+        amount_needed
+    }
+
     #[endpoint(setTicketPrice)]
     fn set_ticket_price(&self, token_id: RewaOrDcdtTokenIdentifier, amount: BigUint) {
+        self.require_role(Role::TicketManager);
         self.require_add_tickets_period();
         self.try_set_ticket_price(token_id.clone(), amount.clone());
 
@@ -36,9 +65,46 @@ pub trait SetupModule:
         self.emit_set_ticket_price_event(ticket_price);
     }
 
-    #[only_owner]
+    /// Users may pay for tickets in this token as well, at the given price per ticket
+    #[endpoint(addTicketPaymentToken)]
+    fn add_ticket_payment_token(&self, token_id: RewaOrDcdtTokenIdentifier, amount: BigUint) {
+        self.require_role(Role::TicketManager);
+        self.require_add_tickets_period();
+        require!(token_id.is_valid(), "Invalid token ID");
+        require!(amount > 0, "Ticket price must be higher than 0");
+        require!(
+            !self.has_ticket_tiers(),
+            "Ticket tiers cannot be used with variable ticket payments"
+        );
+        require!(
+            !self.has_dutch_auction(),
+            "Dutch auction cannot be used with variable ticket payments"
+        );
+
+        let ticket_price: TokenAmountPair<Self::Api> = self.ticket_price().get();
+        require!(
+            token_id != ticket_price.token_id,
+            "Token is already the main payment token"
+        );
+
+        let _ = self.additional_ticket_prices().insert(token_id, amount);
+    }
+
+    #[endpoint(removeTicketPaymentToken)]
+    fn remove_ticket_payment_token(&self, token_id: RewaOrDcdtTokenIdentifier) {
+        self.require_role(Role::TicketManager);
+        self.require_add_tickets_period();
+
+        let removed_price = self.additional_ticket_prices().remove(&token_id);
+        require!(
+            removed_price.is_some(),
+            "Token is not an additional payment token"
+        );
+    }
+
     #[endpoint(setLaunchpadTokensPerWinningTicket)]
     fn set_launchpad_tokens_per_winning_ticket(&self, amount: BigUint) {
+        self.require_role(Role::Treasurer);
         self.require_add_tickets_period();
         require!(
             !self.were_launchpad_tokens_deposited(),
@@ -47,41 +113,131 @@ pub trait SetupModule:
         self.try_set_launchpad_tokens_per_winning_ticket(&amount);
     }
 
-    #[only_owner]
+    /// The ticket price is paid in the main ticket payment token.
+    /// Winners pay the clearing price, and the difference is refunded when claiming.
+    fn set_dutch_auction(
+        &self,
+        start_price: BigUint,
+        floor_price: BigUint,
+        start_point: u64,
+        end_point: u64,
+    ) {
+        self.require_add_tickets_period();
+        require!(
+            floor_price > 0 && start_price > floor_price,
+            "Invalid Dutch auction prices"
+        );
+
+        let config = self.configuration().get();
+        require!(
+            start_point >= config.confirmation_period_start_block
+                && start_point < end_point
+                && end_point <= config.winner_selection_start_block,
+            "Dutch auction must take place during the confirmation period"
+        );
+        require!(
+            !self.has_ticket_tiers()
+                && !self.has_variable_ticket_payments()
+                && !self.pro_rata_allocation().get()
+                && self.claim_deadline().is_empty(),
+            "Dutch auction cannot be used with ticket tiers, variable ticket payments, pro-rata allocation or a claim deadline"
+        );
+
+        self.dutch_auction().set(&DutchAuction {
+            start_price,
+            floor_price,
+            start_point,
+            end_point,
+        });
+    }
+
+    /// If fewer tickets are confirmed by the end of the confirmation period,
+    /// `filterTickets` cancels the launchpad instead, so everyone may be refunded
+    #[endpoint(setSoftCap)]
+    fn set_soft_cap(&self, soft_cap: usize) {
+        self.require_role(Role::TicketManager);
+        self.require_add_tickets_period();
+
+        self.soft_cap().set(soft_cap);
+    }
+
     #[endpoint(setConfirmationPeriodStartBlock)]
     fn set_confirmation_period_start_block(&self, new_start_block: u64) {
+        self.require_role(Role::Operator);
+        self.require_timeline_mode(TimelineMode::BlockNonce);
+        self.require_add_tickets_period();
+        self.try_set_confirmation_period_start(new_start_block);
+    }
+
+    #[endpoint(setWinnerSelectionStartBlock)]
+    fn set_winner_selection_start_block(&self, new_start_block: u64) {
+        self.require_role(Role::Operator);
+        self.require_timeline_mode(TimelineMode::BlockNonce);
+        self.require_add_tickets_period();
+        self.try_set_winner_selection_start(new_start_block);
+    }
+
+    #[endpoint(setClaimStartBlock)]
+    fn set_claim_start_block(&self, new_start_block: u64) {
+        self.require_role(Role::Operator);
+        self.require_timeline_mode(TimelineMode::BlockNonce);
+        self.require_add_tickets_period();
+        self.try_set_claim_start(new_start_block);
+    }
+
+    #[endpoint(setConfirmationPeriodStartTimestamp)]
+    fn set_confirmation_period_start_timestamp(&self, new_start_timestamp: u64) {
+        self.require_role(Role::Operator);
+        self.require_timeline_mode(TimelineMode::Timestamp);
+        self.require_add_tickets_period();
+        self.try_set_confirmation_period_start(new_start_timestamp);
+    }
+
+    #[endpoint(setWinnerSelectionStartTimestamp)]
+    fn set_winner_selection_start_timestamp(&self, new_start_timestamp: u64) {
+        self.require_role(Role::Operator);
+        self.require_timeline_mode(TimelineMode::Timestamp);
+        self.require_add_tickets_period();
+        self.try_set_winner_selection_start(new_start_timestamp);
+    }
+
+    #[endpoint(setClaimStartTimestamp)]
+    fn set_claim_start_timestamp(&self, new_start_timestamp: u64) {
+        self.require_role(Role::Operator);
+        self.require_timeline_mode(TimelineMode::Timestamp);
+        self.require_add_tickets_period();
+        self.try_set_claim_start(new_start_timestamp);
+    }
+
+    fn try_set_confirmation_period_start(&self, new_start: u64) {
         self.configuration().update(|config| {
             self.require_valid_config_timeline_change(
                 config.confirmation_period_start_block,
-                new_start_block,
+                new_start,
             );
 
-            config.confirmation_period_start_block = new_start_block;
+            config.confirmation_period_start_block = new_start;
             self.require_valid_time_periods(config);
         });
     }
 
-    #[only_owner]
-    #[endpoint(setWinnerSelectionStartBlock)]
-    fn set_winner_selection_start_block(&self, new_start_block: u64) {
+    fn try_set_winner_selection_start(&self, new_start: u64) {
         self.configuration().update(|config| {
             self.require_valid_config_timeline_change(
                 config.winner_selection_start_block,
-                new_start_block,
+                new_start,
             );
 
-            config.winner_selection_start_block = new_start_block;
+            config.winner_selection_start_block = new_start;
             self.require_valid_time_periods(config);
         });
     }
 
-    #[only_owner]
-    #[endpoint(setClaimStartBlock)]
-    fn set_claim_start_block(&self, new_start_block: u64) {
+    fn try_set_claim_start(&self, new_start: u64) {
         self.configuration().update(|config| {
-            self.require_valid_config_timeline_change(config.claim_start_block, new_start_block);
+            self.require_valid_config_timeline_change(config.claim_start_block, new_start);
 
-            config.claim_start_block = new_start_block;
+            config.claim_start_block = new_start;
             self.require_valid_time_periods(config);
         });
     }
@@ -89,6 +245,10 @@ pub trait SetupModule:
     fn try_set_ticket_price(&self, token_id: RewaOrDcdtTokenIdentifier, amount: BigUint) {
         require!(token_id.is_valid(), "Invalid token ID");
         require!(amount > 0, "Ticket price must be higher than 0");
+        require!(
+            !self.additional_ticket_prices().contains_key(&token_id),
+            "Token is already an additional payment token"
+        );
 
         self.ticket_price()
             .set(&TokenAmountPair { token_id, amount });
@@ -112,14 +272,14 @@ pub trait SetupModule:
         self.nr_winning_tickets().set(nr_winning_tickets);
     }
 
-    fn require_valid_config_timeline_change(&self, old_start_block: u64, new_start_block: u64) {
-        let current_block = self.blockchain().get_block_nonce();
+    fn require_valid_config_timeline_change(&self, old_start: u64, new_start: u64) {
+        let current_point = self.get_current_timeline_point();
         require!(
-            old_start_block > current_block,
+            old_start > current_point,
             "Cannot change start block, it's either in progress or passed already"
         );
         require!(
-            new_start_block > current_block,
+            new_start > current_point,
             "Start block cannot be in the past"
         );
     }
@@ -1,4 +1,4 @@
-use crate::config::TokenAmountPair;
+use crate::config::{TicketTier, TokenAmountPair};
 
 dharitri_sc::imports!();
 dharitri_sc::derive_imports!();
@@ -20,6 +20,16 @@ pub struct TicketBatch<M: ManagedTypeApi> {
     pub nr_tickets: usize,
 }
 
+/// Ticket batches that remain after filtering, in ticket ID order, for the weighted lottery.
+/// The first `nr_drawn` positions of the batch hold its already drawn tickets.
+#[derive(TypeAbi, TopEncode, TopDecode)]
+pub struct WeightedTicketBatch {
+    pub first_ticket_id: usize,
+    pub nr_tickets: usize,
+    pub weight: u64,
+    pub nr_drawn: usize,
+}
+
 #[dharitri_sc::module]
 pub trait TicketsModule:
     crate::launch_stage::LaunchStageModule + crate::config::ConfigModule
@@ -29,6 +39,10 @@ pub trait TicketsModule:
         address_number_pairs: MultiValueEncoded<MultiValue2<ManagedAddress, usize>>,
     ) {
         self.require_add_tickets_period();
+        require!(
+            !self.has_ticket_tiers(),
+            "Tickets must be added with a tier"
+        );
 
         for multi_arg in address_number_pairs {
             let (buyer, nr_tickets) = multi_arg.into_tuple();
@@ -37,20 +51,88 @@ pub trait TicketsModule:
         }
     }
 
-    fn claim_ticket_payment(&self) {
-        self.require_claim_period();
+    /// Tiers must be added before any tickets. Returns the new tier ID.
+    fn add_ticket_tier(
+        &self,
+        ticket_price: BigUint,
+        launchpad_tokens_per_ticket: BigUint,
+    ) -> usize {
+        self.require_add_tickets_period();
+        require!(self.last_ticket_id().get() == 0, "Tickets already added");
+        require!(
+            !self.has_registration_rule(),
+            "Ticket tiers cannot be used with ticket registration"
+        );
+        require!(
+            !self.has_variable_ticket_payments(),
+            "Ticket tiers cannot be used with variable ticket payments"
+        );
+        require!(
+            self.claim_deadline().is_empty(),
+            "Ticket tiers cannot be used with a claim deadline"
+        );
+        require!(
+            !self.pro_rata_allocation().get(),
+            "Ticket tiers cannot be used with pro-rata allocation"
+        );
+        require!(
+            !self.has_dutch_auction(),
+            "Ticket tiers cannot be used with a Dutch auction"
+        );
+        require!(ticket_price > 0, "Ticket price must be higher than 0");
+        require!(
+            launchpad_tokens_per_ticket > 0,
+            "Launchpad tokens per winning ticket cannot be set to zero"
+        );
 
-        let owner = self.blockchain().get_caller();
+        self.ticket_tiers().push(&TicketTier {
+            ticket_price,
+            launchpad_tokens_per_ticket,
+        })
+    }
 
-        let ticket_payment_mapper = self.claimable_ticket_payment();
-        let claimable_ticket_payment = ticket_payment_mapper.get();
-        if claimable_ticket_payment > 0 {
-            ticket_payment_mapper.clear();
+    /// Tickets must be added in ascending tier order
+    fn add_tickets_with_tier(
+        &self,
+        address_number_tier_triples: MultiValueEncoded<MultiValue3<ManagedAddress, usize, usize>>,
+    ) {
+        self.require_add_tickets_period();
+        require!(self.has_ticket_tiers(), "No ticket tiers");
+        require!(
+            !self.were_launchpad_tokens_deposited(),
+            "Tokens already deposited"
+        );
 
-            let ticket_price: TokenAmountPair<Self::Api> = self.ticket_price().get();
-            self.send()
-                .direct(&owner, &ticket_price.token_id, 0, &claimable_ticket_payment);
+        let nr_tiers = self.ticket_tiers().len();
+        for multi_arg in address_number_tier_triples {
+            let (buyer, nr_tickets, tier_id) = multi_arg.into_tuple();
+            require!(tier_id > 0 && tier_id <= nr_tiers, "Invalid ticket tier");
+
+            let last_ticket_id = self.last_ticket_id().get();
+            if last_ticket_id > 0 {
+                require!(
+                    tier_id >= self.get_ticket_tier_id(last_ticket_id),
+                    "Tickets must be added in ascending tier order"
+                );
+            }
+
+            self.try_create_tickets(buyer.clone(), nr_tickets);
+            self.user_ticket_tier(&buyer).set(tier_id);
+            self.tier_last_ticket_id(tier_id)
+                .set(self.last_ticket_id().get());
         }
+    }
+
+    fn claim_ticket_payment(&self) {
+        self.require_claim_period();
+        require!(
+            !self.is_waitlist_draw_pending(),
+            "Waitlist winners not selected yet"
+        );
+
+        let owner = self.blockchain().get_owner_address();
+
+        let _ = self.send_claimable_ticket_payment(&owner);
 
         let launchpad_token_id = self.launchpad_token_id().get();
         let launchpad_tokens_balance = self.blockchain().get_dcdt_balance(
@@ -59,9 +141,14 @@ pub trait TicketsModule:
             0,
         );
 
-        let nr_winning_tickets = self.nr_winning_tickets().get();
-        let amount_per_ticket = self.launchpad_tokens_per_winning_ticket().get();
-        let launchpad_tokens_needed = amount_per_ticket * (nr_winning_tickets as u32);
+        let launchpad_tokens_needed = if self.has_ticket_tiers() {
+            let (_, tier_launchpad_tokens) = self.get_tier_winning_tickets_value();
+            tier_launchpad_tokens
+        } else {
+            let nr_winning_tickets = self.nr_winning_tickets().get();
+            let amount_per_ticket = self.launchpad_tokens_per_winning_ticket().get();
+            amount_per_ticket * (nr_winning_tickets as u32)
+        };
 
         let extra_launchpad_tokens = launchpad_tokens_balance - launchpad_tokens_needed;
         if extra_launchpad_tokens > 0 {
@@ -70,6 +157,27 @@ pub trait TicketsModule:
         }
     }
 
+    /// Returns the claimable ticket payment denominated in the main ticket price token.
+    /// With variable ticket payments, the payment is sent per token as users claim.
+    fn send_claimable_ticket_payment(&self, owner: &ManagedAddress) -> BigUint {
+        let claimable_ticket_payment = self.claimable_ticket_payment().take();
+        if claimable_ticket_payment > 0 && !self.has_variable_ticket_payments() {
+            let ticket_price: TokenAmountPair<Self::Api> = self.ticket_price().get();
+            self.send()
+                .direct(owner, &ticket_price.token_id, 0, &claimable_ticket_payment);
+        }
+
+        let mut claimable_per_token_mapper = self.claimable_ticket_payment_per_token();
+        for (token_id, amount) in claimable_per_token_mapper.iter() {
+            if amount > 0 {
+                self.send().direct(owner, &token_id, 0, &amount);
+            }
+        }
+        claimable_per_token_mapper.clear();
+
+        claimable_ticket_payment
+    }
+
     // range is [min, max], both inclusive
     #[view(getTicketRangeForAddress)]
     fn get_ticket_range_for_address(
@@ -121,6 +229,60 @@ pub trait TicketsModule:
         last_ticket_id_mapper.set(last_ticket_id);
     }
 
+    /// Users without a weight have weight 1
+    fn set_ticket_weights(
+        &self,
+        address_weight_pairs: MultiValueEncoded<MultiValue2<ManagedAddress, u64>>,
+    ) {
+        self.require_add_tickets_period();
+        require!(
+            self.claim_deadline().is_empty(),
+            "Weighted lottery cannot be used with a claim deadline"
+        );
+        require!(
+            !self.pro_rata_allocation().get(),
+            "Weighted lottery cannot be used with pro-rata allocation"
+        );
+
+        for multi_arg in address_weight_pairs {
+            let (address, weight) = multi_arg.into_tuple();
+            require!(weight > 0, "Invalid ticket weight");
+
+            self.ticket_weight(&address).set(weight);
+        }
+
+        self.weighted_lottery().set(true);
+    }
+
+    /// The price stops descending once the winning tickets are all confirmed
+    fn update_clearing_ticket_price(&self, price_per_ticket: &BigUint) {
+        self.clearing_ticket_price().set(price_per_ticket);
+        if self.total_confirmed_tickets().get() >= self.nr_winning_tickets().get() {
+            self.dutch_auction_sold_out().set(true);
+        }
+    }
+
+    /// The price descends again if unconfirmed or refunded tickets leave it short of
+    /// the number of winning tickets
+    fn refresh_dutch_auction_sold_out(&self) {
+        let sold_out_mapper = self.dutch_auction_sold_out();
+        if sold_out_mapper.get()
+            && self.total_confirmed_tickets().get() < self.nr_winning_tickets().get()
+        {
+            sold_out_mapper.clear();
+        }
+    }
+
+    #[view(getTicketWeight)]
+    fn get_ticket_weight(&self, address: &ManagedAddress) -> u64 {
+        let ticket_weight_mapper = self.ticket_weight(address);
+        if ticket_weight_mapper.is_empty() {
+            1
+        } else {
+            ticket_weight_mapper.get()
+        }
+    }
+
     fn try_get_ticket_range(&self, address: &ManagedAddress) -> TicketRange {
         let ticket_range_mapper = self.ticket_range_for_address(address);
         require!(!ticket_range_mapper.is_empty(), "You have no tickets");
@@ -159,6 +321,37 @@ pub trait TicketsModule:
     #[storage_mapper("nrConfirmedTickets")]
     fn nr_confirmed_tickets(&self, address: &ManagedAddress) -> SingleValueMapper<usize>;
 
+    #[view(getTotalConfirmedTickets)]
+    #[storage_mapper("totalConfirmedTickets")]
+    fn total_confirmed_tickets(&self) -> SingleValueMapper<usize>;
+
+    #[storage_mapper("waitlistWinningTicket")]
+    fn waitlist_winning_ticket(&self, ticket_id: usize) -> SingleValueMapper<bool>;
+
+    /// Tickets of the winners blacklisted after the selection, skipped by the redraw
+    #[storage_mapper("invalidatedTicket")]
+    fn invalidated_ticket(&self, ticket_id: usize) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("nextRedrawTicketPosition")]
+    fn next_redraw_ticket_position(&self) -> SingleValueMapper<usize>;
+
+    #[view(isWeightedLottery)]
+    #[storage_mapper("weightedLottery")]
+    fn weighted_lottery(&self) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("ticketWeight")]
+    fn ticket_weight(&self, address: &ManagedAddress) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("weightedTicketBatches")]
+    fn weighted_ticket_batches(&self) -> VecMapper<WeightedTicketBatch>;
+
+    /// Fenwick tree over the remaining weight of the weighted ticket batches
+    #[storage_mapper("ticketWeightTree")]
+    fn ticket_weight_tree(&self, index: usize) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("remainingTicketWeight")]
+    fn remaining_ticket_weight(&self) -> SingleValueMapper<u64>;
+
     // only used during shuffling. Default (0) means ticket pos = ticket ID.
     #[storage_mapper("ticketPosToId")]
     fn ticket_pos_to_id(&self, ticket_pos: usize) -> SingleValueMapper<usize>;
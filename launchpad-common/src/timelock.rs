@@ -0,0 +1,184 @@
+dharitri_sc::imports!();
+dharitri_sc::derive_imports!();
+
+use crate::permissions::Role;
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub enum AdminAction<M: ManagedTypeApi> {
+    SetTicketPrice {
+        token_id: RewaOrDcdtTokenIdentifier<M>,
+        amount: BigUint<M>,
+    },
+    /// Start points are blocks or timestamps, depending on the timeline mode
+    SetConfirmationPeriodStart {
+        start: u64,
+    },
+    SetWinnerSelectionStart {
+        start: u64,
+    },
+    SetClaimStart {
+        start: u64,
+    },
+    /// The same arguments as `setUnlockSchedule`, for the contracts that have one
+    SetUnlockSchedule {
+        args: ManagedVec<M, u64>,
+    },
+    /// The lock parameters, for the contracts that send locked launchpad tokens
+    SetLaunchpadTokensLockPercentage {
+        lock_percentage: u32,
+    },
+    SetLaunchpadTokensUnlockEpoch {
+        unlock_epoch: u64,
+    },
+    SetSimpleLockScAddress {
+        sc_address: ManagedAddress<M>,
+    },
+}
+
+impl<M: ManagedTypeApi> AdminAction<M> {
+    pub fn required_role(&self) -> Role {
+        match self {
+            AdminAction::SetTicketPrice { .. } => Role::TicketManager,
+            AdminAction::SetConfirmationPeriodStart { .. }
+            | AdminAction::SetWinnerSelectionStart { .. }
+            | AdminAction::SetClaimStart { .. } => Role::Operator,
+            AdminAction::SetUnlockSchedule { .. }
+            | AdminAction::SetLaunchpadTokensLockPercentage { .. }
+            | AdminAction::SetLaunchpadTokensUnlockEpoch { .. }
+            | AdminAction::SetSimpleLockScAddress { .. } => Role::Treasurer,
+        }
+    }
+
+    /// Returns true for the actions every launchpad can execute
+    pub fn is_common(&self) -> bool {
+        matches!(
+            self,
+            AdminAction::SetTicketPrice { .. }
+                | AdminAction::SetConfirmationPeriodStart { .. }
+                | AdminAction::SetWinnerSelectionStart { .. }
+                | AdminAction::SetClaimStart { .. }
+        )
+    }
+}
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct PendingAdminAction<M: ManagedTypeApi> {
+    pub action: AdminAction<M>,
+    pub proposer: ManagedAddress<M>,
+    pub executable_from_block: u64,
+}
+
+#[dharitri_sc::module]
+pub trait TimelockModule:
+    crate::launch_stage::LaunchStageModule
+    + crate::config::ConfigModule
+    + crate::setup::SetupModule
+    + crate::permissions::PermissionsModule
+    + crate::common_events::CommonEventsModule
+{
+    /// Once the confirmation period starts, parameters may only be changed
+    /// through proposals, which can be executed after this many blocks
+    #[only_owner]
+    #[endpoint(setAdminActionDelay)]
+    fn set_admin_action_delay(&self, delay_blocks: u64) {
+        self.require_add_tickets_period();
+        require!(delay_blocks > 0, "Invalid admin action delay");
+
+        self.admin_action_delay().set(delay_blocks);
+    }
+
+    /// Contracts only accept proposals for the actions they can execute,
+    /// see `AdminAction::is_common`
+    fn propose_admin_action(&self, action: AdminAction<Self::Api>) -> u64 {
+        self.require_role(action.required_role());
+
+        let delay = self.admin_action_delay().get();
+        require!(delay > 0, "Admin action delay not set");
+
+        let action_id = self.last_admin_action_id().update(|id| {
+            *id += 1;
+            *id
+        });
+        let current_block = self.blockchain().get_block_nonce();
+        let pending_action = PendingAdminAction {
+            action,
+            proposer: self.blockchain().get_caller(),
+            executable_from_block: current_block + delay,
+        };
+        let _ = self
+            .pending_admin_actions()
+            .insert(action_id, pending_action.clone());
+
+        self.emit_propose_admin_action_event(action_id, pending_action);
+
+        action_id
+    }
+
+    #[endpoint(cancelAdminAction)]
+    fn cancel_admin_action(&self, action_id: u64) {
+        let pending_action = self.take_pending_admin_action(action_id);
+
+        self.emit_cancel_admin_action_event(action_id, pending_action);
+    }
+
+    fn take_executable_admin_action(&self, action_id: u64) -> AdminAction<Self::Api> {
+        let pending_action = self.take_pending_admin_action(action_id);
+        let current_block = self.blockchain().get_block_nonce();
+        require!(
+            current_block >= pending_action.executable_from_block,
+            "Action delay has not passed yet"
+        );
+
+        let action = pending_action.action.clone();
+        self.emit_execute_admin_action_event(action_id, pending_action);
+
+        action
+    }
+
+    fn take_pending_admin_action(&self, action_id: u64) -> PendingAdminAction<Self::Api> {
+        let pending_action = self
+            .pending_admin_actions()
+            .remove(&action_id)
+            .unwrap_or_else(|| sc_panic!("Unknown admin action"));
+        self.require_role(pending_action.action.required_role());
+
+        pending_action
+    }
+
+    /// Executes the actions shared by all launchpads,
+    /// the unlock schedule and lock parameters are handled by the contracts that have them
+    fn execute_admin_action(&self, action: AdminAction<Self::Api>) {
+        match action {
+            AdminAction::SetTicketPrice { token_id, amount } => {
+                // confirmed tickets are paid at the current price and in the current token
+                self.require_add_tickets_period();
+                self.try_set_ticket_price(token_id.clone(), amount.clone());
+
+                let ticket_price = RewaOrDcdtTokenPayment::new(token_id, 0, amount);
+                self.emit_set_ticket_price_event(ticket_price);
+            }
+            AdminAction::SetConfirmationPeriodStart { start } => {
+                self.try_set_confirmation_period_start(start)
+            }
+            AdminAction::SetWinnerSelectionStart { start } => {
+                self.try_set_winner_selection_start(start)
+            }
+            AdminAction::SetClaimStart { start } => self.try_set_claim_start(start),
+            AdminAction::SetUnlockSchedule { .. }
+            | AdminAction::SetLaunchpadTokensLockPercentage { .. }
+            | AdminAction::SetLaunchpadTokensUnlockEpoch { .. }
+            | AdminAction::SetSimpleLockScAddress { .. } => sc_panic!("Action not supported"),
+        }
+    }
+
+    #[view(getAdminActionDelay)]
+    #[storage_mapper("adminActionDelay")]
+    fn admin_action_delay(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("lastAdminActionId")]
+    fn last_admin_action_id(&self) -> SingleValueMapper<u64>;
+
+    #[view(getPendingAdminActions)]
+    #[storage_mapper("pendingAdminActions")]
+    fn pending_admin_actions(&self) -> MapMapper<u64, PendingAdminAction<Self::Api>>;
+}
@@ -1,29 +1,191 @@
 dharitri_sc::imports!();
 
-use crate::{common_events, config::TokenAmountPair};
+use crate::{
+    common_events,
+    config::{TicketPayment, TokenAmountPair},
+};
 
 #[dharitri_sc::module]
 pub trait TokenSendModule: crate::config::ConfigModule + common_events::CommonEventsModule {
+    fn record_ticket_payment(
+        &self,
+        address: &ManagedAddress,
+        token_id: RewaOrDcdtTokenIdentifier,
+        price_per_ticket: BigUint,
+        nr_tickets: usize,
+    ) {
+        self.user_ticket_payments(address).update(|payments| {
+            if !payments.is_empty() {
+                let last_index = payments.len() - 1;
+                let mut last_payment = payments.get(last_index);
+                if last_payment.token_id == token_id
+                    && last_payment.price_per_ticket == price_per_ticket
+                {
+                    last_payment.nr_tickets += nr_tickets;
+                    let _ = payments.set(last_index, &last_payment);
+
+                    return;
+                }
+            }
+
+            payments.push(TicketPayment {
+                token_id,
+                price_per_ticket,
+                nr_tickets,
+            });
+        });
+    }
+
+    /// Refunds the most recently confirmed tickets first
     fn refund_ticket_payment(&self, address: &ManagedAddress, nr_tickets_to_refund: usize) {
         if nr_tickets_to_refund == 0 {
             return;
         }
 
-        let ticket_price: TokenAmountPair<Self::Api> = self.ticket_price().get();
-        let ticket_payment_refund_amount = ticket_price.amount * nr_tickets_to_refund as u32;
-        self.send().direct(
-            address,
-            &ticket_price.token_id,
-            0,
-            &ticket_payment_refund_amount,
-        );
+        let payments_mapper = self.user_ticket_payments(address);
+        if payments_mapper.is_empty() {
+            let ticket_price: TokenAmountPair<Self::Api> = self.ticket_price().get();
+            self.send_ticket_payment_refund(
+                address,
+                nr_tickets_to_refund,
+                ticket_price.token_id,
+                ticket_price.amount,
+            );
+
+            return;
+        }
+
+        let mut payments = payments_mapper.get();
+        let mut nr_tickets_left = nr_tickets_to_refund;
+        while nr_tickets_left > 0 && !payments.is_empty() {
+            let last_index = payments.len() - 1;
+            let mut payment = payments.get(last_index);
+            let nr_refunded_tickets = core::cmp::min(nr_tickets_left, payment.nr_tickets);
+
+            self.send_ticket_payment_refund(
+                address,
+                nr_refunded_tickets,
+                payment.token_id.clone(),
+                payment.price_per_ticket.clone(),
+            );
+
+            nr_tickets_left -= nr_refunded_tickets;
+            payment.nr_tickets -= nr_refunded_tickets;
+            if payment.nr_tickets == 0 {
+                payments.remove(last_index);
+            } else {
+                let _ = payments.set(last_index, &payment);
+            }
+        }
+
+        if payments.is_empty() {
+            payments_mapper.clear();
+        } else {
+            payments_mapper.set(&payments);
+        }
+    }
+
+    fn send_ticket_payment_refund(
+        &self,
+        address: &ManagedAddress,
+        nr_tickets_to_refund: usize,
+        token_id: RewaOrDcdtTokenIdentifier,
+        price_per_ticket: BigUint,
+    ) {
+        let ticket_payment_refund_amount = price_per_ticket * nr_tickets_to_refund as u32;
+        self.send()
+            .direct(address, &token_id, 0, &ticket_payment_refund_amount);
 
         self.emit_refund_ticket_payment_event(
             nr_tickets_to_refund,
-            RewaOrDcdtTokenPayment::new(ticket_price.token_id, 0, ticket_payment_refund_amount),
+            RewaOrDcdtTokenPayment::new(token_id, 0, ticket_payment_refund_amount),
         );
     }
 
+    /// Called after the refund, so the remaining payments are for winning tickets.
+    /// With a fixed ticket price, these are already part of the claimable ticket payment.
+    fn settle_ticket_payment(&self, address: &ManagedAddress) {
+        let payments = self.user_ticket_payments(address).take();
+        if self.has_dutch_auction() {
+            self.refund_above_clearing_price(address, &payments);
+            return;
+        }
+        if !self.has_variable_ticket_payments() {
+            return;
+        }
+
+        let mut claimable_mapper = self.claimable_ticket_payment_per_token();
+        for payment in &payments {
+            let amount = payment.price_per_ticket * payment.nr_tickets as u32;
+            claimable_mapper
+                .entry(payment.token_id)
+                .or_default()
+                .update(|claimable| *claimable += amount);
+        }
+    }
+
+    /// Settles all but the last `nr_tickets_to_keep` tickets, which are refunded first.
+    /// Winning tickets already claimed before the waitlist draw are settled here as well.
+    fn settle_winning_ticket_payment(&self, address: &ManagedAddress, nr_tickets_to_keep: usize) {
+        let payments_mapper = self.user_ticket_payments(address);
+        if payments_mapper.is_empty() {
+            return;
+        }
+
+        let mut payments = payments_mapper.get();
+        let nr_paid_tickets = payments
+            .iter()
+            .fold(0, |nr_tickets, payment| nr_tickets + payment.nr_tickets);
+        if nr_paid_tickets <= nr_tickets_to_keep {
+            return;
+        }
+
+        let mut claimable_mapper = self.claimable_ticket_payment_per_token();
+        let mut nr_tickets_left = nr_paid_tickets - nr_tickets_to_keep;
+        while nr_tickets_left > 0 {
+            let mut payment = payments.get(0);
+            let nr_settled_tickets = core::cmp::min(nr_tickets_left, payment.nr_tickets);
+            let amount = &payment.price_per_ticket * nr_settled_tickets as u32;
+            claimable_mapper
+                .entry(payment.token_id.clone())
+                .or_default()
+                .update(|claimable| *claimable += amount);
+
+            nr_tickets_left -= nr_settled_tickets;
+            payment.nr_tickets -= nr_settled_tickets;
+            if payment.nr_tickets == 0 {
+                payments.remove(0);
+            } else {
+                let _ = payments.set(0, &payment);
+            }
+        }
+
+        if payments.is_empty() {
+            payments_mapper.clear();
+        } else {
+            payments_mapper.set(&payments);
+        }
+    }
+
+    fn refund_above_clearing_price(
+        &self,
+        address: &ManagedAddress,
+        payments: &ManagedVec<TicketPayment<Self::Api>>,
+    ) {
+        let clearing_price = self.clearing_ticket_price().get();
+        let mut refund_amount = BigUint::zero();
+        for payment in payments {
+            refund_amount +=
+                (payment.price_per_ticket - &clearing_price) * payment.nr_tickets as u32;
+        }
+
+        if refund_amount > 0 {
+            let ticket_price: TokenAmountPair<Self::Api> = self.ticket_price().get();
+            self.send()
+                .direct(address, &ticket_price.token_id, 0, &refund_amount);
+        }
+    }
+
     fn send_launchpad_tokens<
         SendLaunchpadTokensFn: Fn(&Self, &ManagedAddress, &DcdtTokenPayment<Self::Api>),
     >(
@@ -37,7 +199,7 @@ pub trait TokenSendModule: crate::config::ConfigModule + common_events::CommonEv
         }
 
         let launchpad_token_id = self.launchpad_token_id().get();
-        let tokens_per_winning_ticket = self.launchpad_tokens_per_winning_ticket().get();
+        let tokens_per_winning_ticket = self.get_launchpad_tokens_per_ticket_for_user(address);
         let launchpad_tokens_amount_to_send =
             BigUint::from(nr_claimed_tickets as u32) * tokens_per_winning_ticket;
 
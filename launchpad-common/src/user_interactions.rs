@@ -1,6 +1,9 @@
 dharitri_sc::imports!();
 
-use crate::{config::TokenAmountPair, tickets::WINNING_TICKET};
+use crate::{
+    permissions::Role,
+    tickets::{TicketRange, WINNING_TICKET},
+};
 
 #[dharitri_sc::module]
 pub trait UserInteractionsModule:
@@ -9,7 +12,9 @@ pub trait UserInteractionsModule:
     + crate::blacklist::BlacklistModule
     + crate::tickets::TicketsModule
     + crate::token_send::TokenSendModule
+    + crate::price_oracle::PriceOracleModule
     + crate::permissions::PermissionsModule
+    + crate::address_groups::AddressGroupsModule
     + crate::common_events::CommonEventsModule
     + dharitri_sc_modules::pause::PauseModule
 {
@@ -39,15 +44,40 @@ pub trait UserInteractionsModule:
             "Trying to confirm too many tickets"
         );
 
-        let ticket_price: TokenAmountPair<Self::Api> = self.ticket_price().get();
-        let total_ticket_price = ticket_price.amount * nr_tickets_to_confirm as u32;
-        require!(
-            payment_token == ticket_price.token_id,
-            "Wrong payment token used"
-        );
-        require!(payment_amount == total_ticket_price, "Wrong amount sent");
+        let fixed_price_per_ticket = self.get_ticket_price_for_user(&caller, &payment_token);
+        let opt_oracle_price_per_ticket = self.get_oracle_ticket_price(&payment_token);
+        let is_changing_price = opt_oracle_price_per_ticket.is_some() || self.has_dutch_auction();
+        let price_per_ticket = opt_oracle_price_per_ticket.unwrap_or(fixed_price_per_ticket);
+        let total_ticket_price = &price_per_ticket * nr_tickets_to_confirm as u32;
+        let payment_amount = if is_changing_price {
+            // the price may change until execution, so any excess is returned
+            require!(payment_amount >= total_ticket_price, "Wrong amount sent");
+            let excess_amount = &payment_amount - &total_ticket_price;
+            if excess_amount > 0 {
+                self.send()
+                    .direct(&caller, &payment_token, 0, &excess_amount);
+            }
 
+            total_ticket_price
+        } else {
+            require!(payment_amount == total_ticket_price, "Wrong amount sent");
+
+            payment_amount
+        };
+
+        self.add_group_confirmed_tickets(&caller, nr_tickets_to_confirm);
         self.nr_confirmed_tickets(&caller).set(total_confirmed);
+        self.total_confirmed_tickets()
+            .update(|total| *total += nr_tickets_to_confirm);
+        if self.has_dutch_auction() {
+            self.update_clearing_ticket_price(&price_per_ticket);
+        }
+        self.record_ticket_payment(
+            &caller,
+            payment_token.clone(),
+            price_per_ticket,
+            nr_tickets_to_confirm,
+        );
 
         let token_payment = RewaOrDcdtTokenPayment::new(payment_token, 0, payment_amount);
         self.emit_confirm_tickets_event(
@@ -58,6 +88,31 @@ pub trait UserInteractionsModule:
         );
     }
 
+    /// The most recently confirmed tickets are unconfirmed first
+    #[endpoint(unconfirmTickets)]
+    fn unconfirm_tickets(&self, nr_tickets_to_unconfirm: usize) {
+        self.require_not_paused();
+        self.require_confirmation_period();
+
+        let caller = self.blockchain().get_caller();
+        let confirmed_tickets_mapper = self.nr_confirmed_tickets(&caller);
+        let nr_confirmed = confirmed_tickets_mapper.get();
+        require!(
+            nr_tickets_to_unconfirm > 0 && nr_tickets_to_unconfirm <= nr_confirmed,
+            "Trying to unconfirm too many tickets"
+        );
+
+        let total_confirmed = nr_confirmed - nr_tickets_to_unconfirm;
+        confirmed_tickets_mapper.set(total_confirmed);
+        self.total_confirmed_tickets()
+            .update(|total| *total -= nr_tickets_to_unconfirm);
+        self.refresh_dutch_auction_sold_out();
+        self.remove_group_confirmed_tickets(&caller, nr_tickets_to_unconfirm);
+        self.refund_ticket_payment(&caller, nr_tickets_to_unconfirm);
+
+        self.emit_unconfirm_tickets_event(nr_tickets_to_unconfirm, total_confirmed);
+    }
+
     fn claim_launchpad_tokens<
         SendLaunchpadTokensFn: Fn(&Self, &ManagedAddress, &DcdtTokenPayment<Self::Api>),
     >(
@@ -69,6 +124,15 @@ pub trait UserInteractionsModule:
         let caller = self.blockchain().get_caller();
         require!(!self.has_user_claimed(&caller), "Already claimed");
 
+        let is_waitlist_draw_pending = self.is_waitlist_draw_pending();
+        if is_waitlist_draw_pending {
+            require!(
+                self.get_current_timeline_point() < self.claim_deadline().get(),
+                "Waitlist winners not selected yet"
+            );
+        }
+
+        let is_waitlist_completed = self.waitlist_winners_selected().get();
         let ticket_range = self.try_get_ticket_range(&caller);
         let nr_confirmed_tickets = self.nr_confirmed_tickets(&caller).get();
         let mut nr_redeemable_tickets = 0;
@@ -78,28 +142,99 @@ pub trait UserInteractionsModule:
             if ticket_status == WINNING_TICKET {
                 self.ticket_status(ticket_id).clear();
 
+                // winning tickets not claimed before the claim deadline are forfeited
+                if !is_waitlist_completed {
+                    nr_redeemable_tickets += 1;
+                }
+            } else if is_waitlist_completed && self.waitlist_winning_ticket(ticket_id).take() {
                 nr_redeemable_tickets += 1;
             }
 
-            self.ticket_pos_to_id(ticket_id).clear();
+            // ticket positions are still needed for the waitlist draw
+            if !is_waitlist_draw_pending {
+                self.ticket_pos_to_id(ticket_id).clear();
+            }
         }
 
-        self.nr_confirmed_tickets(&caller).clear();
-        self.ticket_range_for_address(&caller).clear();
-        self.ticket_batch(ticket_range.first_id).clear();
-
         if nr_redeemable_tickets > 0 {
             self.nr_winning_tickets()
                 .update(|nr_winning_tickets| *nr_winning_tickets -= nr_redeemable_tickets);
+
+            let tier_id = self.user_ticket_tier(&caller).get();
+            if tier_id > 0 {
+                self.tier_nr_winning_tickets(tier_id)
+                    .update(|nr_winning_tickets| *nr_winning_tickets -= nr_redeemable_tickets);
+            }
+        }
+
+        // the losing tickets take part in the waitlist draw, so they are refunded afterwards
+        if is_waitlist_draw_pending && nr_redeemable_tickets < nr_confirmed_tickets {
+            require!(
+                nr_redeemable_tickets > 0,
+                "Waitlist winners not selected yet"
+            );
+
+            self.nr_confirmed_tickets(&caller)
+                .set(nr_confirmed_tickets - nr_redeemable_tickets);
+            self.send_launchpad_tokens(&caller, nr_redeemable_tickets, send_fn);
+
+            return;
         }
 
+        self.nr_confirmed_tickets(&caller).clear();
+        self.ticket_range_for_address(&caller).clear();
+        self.ticket_batch(ticket_range.first_id).clear();
+
         self.claim_list().add(&caller);
 
         let nr_tickets_to_refund = nr_confirmed_tickets - nr_redeemable_tickets;
         self.refund_ticket_payment(&caller, nr_tickets_to_refund);
+        self.settle_ticket_payment(&caller);
         self.send_launchpad_tokens(&caller, nr_redeemable_tickets, send_fn);
     }
 
+    /// With variable ticket payments, the payment of winning tickets is settled as users claim.
+    /// Settles it for winners who haven't claimed yet, so it can be sent by `claimTicketPayment`.
+    /// The payment of their losing tickets is kept for the refund when they claim.
+    #[endpoint(settleUnclaimedTicketPayments)]
+    fn settle_unclaimed_ticket_payments(&self, users: MultiValueEncoded<ManagedAddress>) {
+        self.require_role(Role::Treasurer);
+        self.require_claim_period();
+        require!(
+            self.has_variable_ticket_payments(),
+            "Ticket payments are settled at winner selection"
+        );
+        require!(
+            !self.is_waitlist_draw_pending(),
+            "Waitlist winners not selected yet"
+        );
+
+        let is_waitlist_completed = self.waitlist_winners_selected().get();
+        for address in users {
+            let ticket_range_mapper = self.ticket_range_for_address(&address);
+            if ticket_range_mapper.is_empty() {
+                continue;
+            }
+
+            let ticket_range: TicketRange = ticket_range_mapper.get();
+            let mut nr_redeemable_tickets = 0;
+            for ticket_id in ticket_range.first_id..=ticket_range.last_id {
+                let is_redeemable = if is_waitlist_completed {
+                    self.waitlist_winning_ticket(ticket_id).get()
+                } else {
+                    self.ticket_status(ticket_id).get() == WINNING_TICKET
+                };
+                if is_redeemable {
+                    nr_redeemable_tickets += 1;
+                }
+            }
+
+            let nr_confirmed_tickets = self.nr_confirmed_tickets(&address).get();
+            let nr_losing_tickets = nr_confirmed_tickets - nr_redeemable_tickets;
+            self.settle_winning_ticket_payment(&address, nr_losing_tickets);
+        }
+    }
+
     #[view(hasUserClaimedTokens)]
     fn has_user_claimed(&self, address: &ManagedAddress) -> bool {
         self.claim_list().contains(address)
@@ -3,10 +3,15 @@ dharitri_sc::imports!();
 use crate::{
     launch_stage::Flags,
     ongoing_operation::{OngoingOperationType, CONTINUE_OP, STOP_OP},
-    random::Random,
-    tickets::{TicketBatch, TicketRange, FIRST_TICKET_ID, WINNING_TICKET},
+    permissions::Role,
+    random::{Hash, Random},
+    tickets::{TicketBatch, TicketRange, WeightedTicketBatch, FIRST_TICKET_ID, WINNING_TICKET},
 };
 
+static TOTAL_WEIGHT_TOO_HIGH_ERR_MSG: &[u8] = b"Total ticket weight is too high";
+pub static WAITLIST_DRAW_TAG: &[u8] = b"waitlist";
+pub static REDRAW_TAG: &[u8] = b"redraw";
+
 #[dharitri_sc::module]
 pub trait WinnerSelectionModule:
     crate::launch_stage::LaunchStageModule
@@ -16,9 +21,85 @@ pub trait WinnerSelectionModule:
     + crate::blacklist::BlacklistModule
     + crate::token_send::TokenSendModule
     + crate::permissions::PermissionsModule
+    + crate::address_groups::AddressGroupsModule
+    + crate::cancellation::CancellationModule
     + crate::common_events::CommonEventsModule
     + dharitri_sc_modules::pause::PauseModule
 {
+    /// Optional commit-reveal mode for the winner selection seed.
+    /// The owner commits the SHA256 hash of a secret before the confirmation period starts,
+    /// and reveals the secret during winner selection, before calling `selectWinners`.
+    /// Once a seed is committed, `selectWinners` fails until the secret is revealed.
+    /// If the secret is not revealed before `reveal_deadline`, the next `selectWinners` call
+    /// cancels the launchpad instead, so the committer can't choose between two outcomes,
+    /// and users get their ticket payments back through `claimRefund`.
+    #[endpoint(commitSeedHash)]
+    fn commit_seed_hash(&self, seed_hash: Hash<Self::Api>, reveal_deadline: u64) {
+        self.require_role(Role::Operator);
+        self.require_add_tickets_period();
+
+        let config = self.configuration().get();
+        require!(
+            reveal_deadline > config.winner_selection_start_block
+                && reveal_deadline <= config.claim_start_block,
+            "Reveal deadline must be during winner selection"
+        );
+
+        self.seed_commitment().set(&seed_hash);
+        self.seed_reveal_deadline().set(reveal_deadline);
+    }
+
+    /// The selection seed is the SHA256 hash of the revealed secret
+    /// concatenated with the random seed of the current block.
+    #[endpoint(revealSeed)]
+    fn reveal_seed(&self, secret: ManagedBuffer) {
+        self.require_role(Role::Operator);
+        self.require_winner_selection_period();
+
+        let seed_commitment_mapper = self.seed_commitment();
+        require!(!seed_commitment_mapper.is_empty(), "No seed commitment");
+        require!(self.revealed_seed().is_empty(), "Seed already revealed");
+        require!(
+            self.get_current_timeline_point() < self.seed_reveal_deadline().get(),
+            "Reveal deadline has passed"
+        );
+
+        let secret_hash = self.crypto().sha256(&secret);
+        require!(
+            secret_hash == seed_commitment_mapper.get(),
+            "Revealed seed does not match commitment"
+        );
+
+        let block_random_seed = self.blockchain().get_block_random_seed();
+        let mut seed_input = secret.clone();
+        seed_input.append(block_random_seed.as_managed_buffer());
+        let seed = self.crypto().sha256(&seed_input);
+
+        self.revealed_seed().set(&secret);
+        self.winner_selection_seed().set(seed.as_managed_buffer());
+    }
+
+    /// Winning tickets not claimed before the deadline are forfeited, see `select_waitlist_winners`
+    fn set_claim_deadline(&self, claim_deadline: u64) {
+        self.require_add_tickets_period();
+        require!(
+            !self.has_ticket_tiers() && !self.weighted_lottery().get(),
+            "Claim deadline cannot be used with ticket tiers or the weighted lottery"
+        );
+        require!(
+            !self.pro_rata_allocation().get() && !self.has_dutch_auction(),
+            "Claim deadline cannot be used with pro-rata allocation or a Dutch auction"
+        );
+
+        let config = self.configuration().get();
+        require!(
+            claim_deadline > config.claim_start_block,
+            "Claim deadline must be after claim start"
+        );
+
+        self.claim_deadline().set(claim_deadline);
+    }
+
     #[endpoint(filterTickets)]
     fn filter_tickets(&self) -> OperationCompletionStatus {
         self.require_not_paused();
@@ -27,11 +108,22 @@ pub trait WinnerSelectionModule:
         let flags_mapper = self.flags();
         let mut flags: Flags = flags_mapper.get();
         require!(!flags.were_tickets_filtered, "Tickets already filtered");
+        require!(
+            !self.pro_rata_allocation().get(),
+            "No winners are selected with pro-rata allocation"
+        );
 
         let last_ticket_id = self.last_ticket_id().get();
+        let has_ticket_tiers = self.has_ticket_tiers();
+        let is_weighted_lottery = self.weighted_lottery().get();
+        let has_address_groups = self.has_address_groups();
         let (mut first_ticket_id_in_batch, mut nr_removed) = self.load_filter_tickets_operation();
 
         if first_ticket_id_in_batch == FIRST_TICKET_ID {
+            if self.try_cancel_below_soft_cap() {
+                return OperationCompletionStatus::Completed;
+            }
+
             flags.has_winner_selection_process_started = true;
         }
 
@@ -44,8 +136,27 @@ pub trait WinnerSelectionModule:
             let ticket_batch: TicketBatch<Self::Api> = current_ticket_batch_mapper.get();
             let address = &ticket_batch.address;
             let nr_tickets_in_batch = ticket_batch.nr_tickets;
+            let tier_id = if has_ticket_tiers {
+                self.user_ticket_tier(address).get()
+            } else {
+                0
+            };
+            let ticket_weight = if is_weighted_lottery {
+                self.get_ticket_weight(address)
+            } else {
+                0
+            };
 
             let nr_confirmed_tickets = self.nr_confirmed_tickets(address).get();
+            if has_address_groups && nr_confirmed_tickets > 0 {
+                let new_first_id = first_ticket_id_in_batch - nr_removed;
+                self.add_grouped_ticket_range(
+                    address,
+                    new_first_id,
+                    new_first_id + nr_confirmed_tickets - 1,
+                );
+            }
+
             if nr_confirmed_tickets == 0 {
                 self.ticket_range_for_address(address).clear();
                 current_ticket_batch_mapper.clear();
@@ -65,9 +176,23 @@ pub trait WinnerSelectionModule:
                 });
             }
 
+            if is_weighted_lottery && nr_confirmed_tickets > 0 {
+                self.add_weighted_ticket_batch(
+                    first_ticket_id_in_batch - nr_removed,
+                    nr_confirmed_tickets,
+                    ticket_weight,
+                );
+            }
+
             nr_removed += nr_tickets_in_batch - nr_confirmed_tickets;
             first_ticket_id_in_batch += nr_tickets_in_batch;
 
+            // keeps the tier ID ranges contiguous after removing tickets
+            if tier_id > 0 {
+                self.tier_last_ticket_id(tier_id)
+                    .set(first_ticket_id_in_batch - nr_removed - 1);
+            }
+
             CONTINUE_OP
         });
 
@@ -111,18 +236,46 @@ pub trait WinnerSelectionModule:
         require!(flags.were_tickets_filtered, "Must filter tickets first");
         require!(!flags.were_winners_selected, "Winners already selected");
 
+        if self.try_cancel_unrevealed_seed() {
+            return OperationCompletionStatus::Completed;
+        }
+
         let nr_winning_tickets = self.nr_winning_tickets().get();
         let last_ticket_position = self.get_total_tickets();
-
-        let (mut rng, mut ticket_position) = self.load_select_winners_operation();
+        let has_ticket_tiers = self.has_ticket_tiers();
+        // when all the tickets win, the draw order makes no difference
+        let is_weighted_lottery =
+            self.weighted_lottery().get() && nr_winning_tickets < last_ticket_position;
+        let has_address_groups = self.has_address_groups();
+
+        // in weighted mode, ticket_position is the number of tickets drawn so far + 1
+        let (mut rng, mut ticket_position, mut nr_selected) =
+            self.load_select_winners_operation(|| self.init_winner_selection_rng());
         let run_result = self.run_while_it_has_gas(|| {
             if nr_winning_tickets == 0 {
                 return STOP_OP;
             }
 
-            self.shuffle_single_ticket(&mut rng, ticket_position, last_ticket_position);
+            let winning_ticket_id = if is_weighted_lottery {
+                self.draw_weighted_ticket(&mut rng)
+            } else {
+                self.shuffle_single_ticket(&mut rng, ticket_position, last_ticket_position)
+            };
+
+            // the ticket is skipped if its address group reached the winning tickets limit
+            if has_address_groups && !self.try_add_group_winning_ticket(winning_ticket_id) {
+                self.ticket_status(winning_ticket_id).clear();
+            } else {
+                nr_selected += 1;
+
+                if has_ticket_tiers {
+                    let tier_id = self.get_ticket_tier_id(winning_ticket_id);
+                    self.tier_nr_winning_tickets(tier_id)
+                        .update(|nr_winning_tickets| *nr_winning_tickets += 1);
+                }
+            }
 
-            if ticket_position == nr_winning_tickets {
+            if nr_selected == nr_winning_tickets || ticket_position == last_ticket_position {
                 return STOP_OP;
             }
 
@@ -136,13 +289,23 @@ pub trait WinnerSelectionModule:
                 self.save_progress(&OngoingOperationType::SelectWinners {
                     rng,
                     ticket_position,
+                    nr_selected,
                 });
             }
             OperationCompletionStatus::Completed => {
                 flags.were_winners_selected = true;
 
-                let ticket_price = self.ticket_price().get();
-                let claimable_ticket_payment = ticket_price.amount * (nr_winning_tickets as u32);
+                // fewer tickets win when too many are skipped because of the address groups
+                let nr_winning_tickets = core::cmp::min(nr_winning_tickets, nr_selected);
+                self.nr_winning_tickets().set(nr_winning_tickets);
+                self.next_redraw_ticket_position().set(ticket_position + 1);
+
+                let claimable_ticket_payment = if has_ticket_tiers {
+                    let (tier_ticket_payment, _) = self.get_tier_winning_tickets_value();
+                    tier_ticket_payment
+                } else {
+                    self.get_winning_ticket_price() * (nr_winning_tickets as u32)
+                };
                 self.claimable_ticket_payment()
                     .set(&claimable_ticket_payment);
 
@@ -155,14 +318,363 @@ pub trait WinnerSelectionModule:
         run_result
     }
 
+    fn add_weighted_ticket_batch(&self, first_ticket_id: usize, nr_tickets: usize, weight: u64) {
+        let remaining_weight_mapper = self.remaining_ticket_weight();
+        let batch_weight = weight
+            .checked_mul(nr_tickets as u64)
+            .unwrap_or_else(|| sc_panic!(TOTAL_WEIGHT_TOO_HIGH_ERR_MSG));
+        let total_weight = remaining_weight_mapper
+            .get()
+            .checked_add(batch_weight)
+            .unwrap_or_else(|| sc_panic!(TOTAL_WEIGHT_TOO_HIGH_ERR_MSG));
+
+        let batch_index = self.weighted_ticket_batches().push(&WeightedTicketBatch {
+            first_ticket_id,
+            nr_tickets,
+            weight,
+            nr_drawn: 0,
+        });
+
+        // tree node i holds the weight of the batches in range (i - lowest_bit(i), i]
+        let lowest_bit = batch_index & batch_index.wrapping_neg();
+        let tree_node_weight = batch_weight + self.get_ticket_weight_prefix_sum(batch_index - 1)
+            - self.get_ticket_weight_prefix_sum(batch_index - lowest_bit);
+        self.ticket_weight_tree(batch_index).set(tree_node_weight);
+        remaining_weight_mapper.set(total_weight);
+    }
+
+    fn get_ticket_weight_prefix_sum(&self, mut batch_index: usize) -> u64 {
+        let mut prefix_sum = 0;
+        while batch_index > 0 {
+            prefix_sum += self.ticket_weight_tree(batch_index).get();
+            batch_index &= batch_index - 1;
+        }
+
+        prefix_sum
+    }
+
+    /// Draws one of the remaining tickets, with a probability proportional to its weight.
+    /// Drawn tickets are swapped to the start of their batch, so they are never drawn again.
+    fn draw_weighted_ticket(&self, rng: &mut Random<Self::Api>) -> usize {
+        let remaining_weight_mapper = self.remaining_ticket_weight();
+        let remaining_weight = remaining_weight_mapper.get();
+        let rand_weight = rng.next_u64_in_range(0, remaining_weight);
+
+        // find the batch containing the drawn weight, descending the tree
+        let batches_mapper = self.weighted_ticket_batches();
+        let nr_batches = batches_mapper.len();
+        let mut batch_index = 0;
+        let mut weight_before_batch = 0;
+        let mut step = 1 << (usize::BITS - 1 - nr_batches.leading_zeros());
+        while step > 0 {
+            let next_index = batch_index + step;
+            if next_index <= nr_batches {
+                let node_weight = self.ticket_weight_tree(next_index).get();
+                if weight_before_batch + node_weight <= rand_weight {
+                    batch_index = next_index;
+                    weight_before_batch += node_weight;
+                }
+            }
+
+            step /= 2;
+        }
+        batch_index += 1;
+
+        let mut batch: WeightedTicketBatch = batches_mapper.get(batch_index);
+        let ticket_offset = ((rand_weight - weight_before_batch) / batch.weight) as usize;
+        let first_remaining_pos = batch.first_ticket_id + batch.nr_drawn;
+        let drawn_pos = first_remaining_pos + ticket_offset;
+
+        let winning_ticket_id = self.get_ticket_id_from_pos(drawn_pos);
+        self.ticket_status(winning_ticket_id).set(WINNING_TICKET);
+
+        let first_remaining_ticket_id = self.get_ticket_id_from_pos(first_remaining_pos);
+        self.ticket_pos_to_id(drawn_pos)
+            .set(first_remaining_ticket_id);
+
+        batch.nr_drawn += 1;
+        batches_mapper.set(batch_index, &batch);
+
+        let mut tree_index = batch_index;
+        while tree_index <= nr_batches {
+            self.ticket_weight_tree(tree_index)
+                .update(|node_weight| *node_weight -= batch.weight);
+            tree_index += tree_index & tree_index.wrapping_neg();
+        }
+        remaining_weight_mapper.set(remaining_weight - batch.weight);
+
+        winning_ticket_id
+    }
+
+    /// After the claim deadline, the unclaimed winning tickets are forfeited and drawn again
+    /// among the losing tickets, continuing the shuffle of `selectWinners`.
+    /// Forfeited tickets are refunded, same as the losing ones.
+    fn select_waitlist_winners(&self) -> OperationCompletionStatus {
+        self.require_not_paused();
+        self.check_caller_owner_or_user();
+
+        let claim_deadline_mapper = self.claim_deadline();
+        require!(!claim_deadline_mapper.is_empty(), "No claim deadline");
+        require!(
+            self.get_current_timeline_point() >= claim_deadline_mapper.get(),
+            "Claim deadline has not passed yet"
+        );
+        require!(
+            !self.waitlist_winners_selected().get(),
+            "Waitlist winners already selected"
+        );
+
+        // users may not claim until the draw is completed, so this remains unchanged
+        let nr_forfeited_tickets = self.nr_winning_tickets().get();
+        let last_ticket_position = self.get_total_tickets();
+        let has_address_groups = self.has_address_groups();
+
+        // forfeited tickets still count towards the winning tickets limit of their address group
+        let first_ticket_position = self.next_redraw_ticket_position().get();
+        let (mut rng, mut ticket_position, mut nr_waitlist_winning_tickets) = self
+            .load_select_waitlist_winners_operation(first_ticket_position, || {
+                self.derive_winner_selection_rng(WAITLIST_DRAW_TAG, first_ticket_position)
+            });
+        let run_result = self.run_while_it_has_gas(|| {
+            if nr_waitlist_winning_tickets == nr_forfeited_tickets
+                || ticket_position > last_ticket_position
+            {
+                return STOP_OP;
+            }
+
+            let rand_pos = rng.next_usize_in_range(ticket_position, last_ticket_position + 1);
+
+            let winning_ticket_id = self.get_ticket_id_from_pos(rand_pos);
+            if !has_address_groups || self.try_add_group_winning_ticket(winning_ticket_id) {
+                self.waitlist_winning_ticket(winning_ticket_id).set(true);
+                nr_waitlist_winning_tickets += 1;
+            }
+
+            let current_ticket_id = self.get_ticket_id_from_pos(ticket_position);
+            self.ticket_pos_to_id(rand_pos).set(current_ticket_id);
+
+            ticket_position += 1;
+
+            CONTINUE_OP
+        });
+
+        match run_result {
+            OperationCompletionStatus::InterruptedBeforeOutOfGas => {
+                self.save_progress(&OngoingOperationType::SelectWaitlistWinners {
+                    rng,
+                    ticket_position,
+                    nr_selected: nr_waitlist_winning_tickets,
+                });
+            }
+            OperationCompletionStatus::Completed => {
+                self.waitlist_winners_selected().set(true);
+                self.nr_winning_tickets().set(nr_waitlist_winning_tickets);
+
+                let nr_refunded_winning_tickets =
+                    nr_forfeited_tickets - nr_waitlist_winning_tickets;
+                let refunded_ticket_payment =
+                    self.get_winning_ticket_price() * (nr_refunded_winning_tickets as u32);
+                self.claimable_ticket_payment()
+                    .update(|claimable_ticket_payment| {
+                        *claimable_ticket_payment -= refunded_ticket_payment
+                    });
+
+                self.emit_select_waitlist_winners_completed_event(
+                    nr_forfeited_tickets,
+                    nr_waitlist_winning_tickets,
+                );
+            }
+        };
+
+        run_result
+    }
+
+    /// Winners may still be blacklisted after the selection, until the claim period starts.
+    /// All their tickets are refunded, and their winning tickets are freed for `redraw_winners`.
+    /// Only exposed by the contracts without additional selection steps.
+    fn add_winners_to_blacklist(&self, users_list: &ManagedVec<ManagedAddress>, reason: u32) {
+        self.require_role(Role::Compliance);
+        self.require_winner_selection_period();
+
+        let flags: Flags = self.flags().get();
+        require!(
+            flags.were_winners_selected && flags.was_additional_step_completed,
+            "Winners not selected yet"
+        );
+        require!(
+            !self.has_ticket_tiers() && !self.weighted_lottery().get(),
+            "Winners cannot be redrawn with ticket tiers or the weighted lottery"
+        );
+        require!(
+            self.claim_deadline().is_empty(),
+            "Winners cannot be redrawn with a claim deadline"
+        );
+        require!(
+            self.current_ongoing_operation().is_empty(),
+            "Winners redraw in progress"
+        );
+
+        let mut nr_freed_tickets = 0;
+        for address in users_list {
+            let ticket_range_mapper = self.ticket_range_for_address(&address);
+            if !ticket_range_mapper.is_empty() {
+                let ticket_range: TicketRange = ticket_range_mapper.take();
+                let mut nr_user_winning_tickets = 0;
+                for ticket_id in ticket_range.first_id..=ticket_range.last_id {
+                    if self.ticket_status(ticket_id).take() == WINNING_TICKET {
+                        nr_user_winning_tickets += 1;
+                    }
+                    self.invalidated_ticket(ticket_id).set(true);
+                }
+                self.ticket_batch(ticket_range.first_id).clear();
+
+                self.remove_group_winning_tickets(&address, nr_user_winning_tickets);
+                nr_freed_tickets += nr_user_winning_tickets;
+            }
+
+            self.blacklist_user(&address, reason);
+        }
+
+        if nr_freed_tickets > 0 {
+            self.nr_winning_tickets()
+                .update(|nr_winning_tickets| *nr_winning_tickets -= nr_freed_tickets);
+            self.nr_winning_tickets_to_redraw()
+                .update(|nr_tickets_to_redraw| *nr_tickets_to_redraw += nr_freed_tickets);
+
+            let freed_ticket_payment = self.get_winning_ticket_price() * (nr_freed_tickets as u32);
+            self.claimable_ticket_payment()
+                .update(|claimable_ticket_payment| {
+                    *claimable_ticket_payment -= freed_ticket_payment
+                });
+        }
+
+        self.emit_add_users_to_blacklist_event(users_list.clone(), reason);
+    }
+
+    /// Draws the winning tickets freed by blacklisting among the losing tickets,
+    /// continuing the shuffle of `selectWinners`. Tickets of blacklisted users are skipped.
+    /// If not enough losing tickets are left, the remaining winning tickets are dropped.
+    fn redraw_winners(&self) -> OperationCompletionStatus {
+        self.require_not_paused();
+        self.require_winner_selection_period();
+
+        self.check_caller_owner_or_user();
+
+        let nr_tickets_to_redraw = self.nr_winning_tickets_to_redraw().get();
+        require!(nr_tickets_to_redraw > 0, "No winning tickets to redraw");
+
+        let last_ticket_position = self.get_total_tickets();
+        let first_ticket_position = self.next_redraw_ticket_position().get();
+        let (mut rng, mut ticket_position, mut nr_redrawn) = self
+            .load_redraw_winners_operation(first_ticket_position, || {
+                self.derive_winner_selection_rng(REDRAW_TAG, first_ticket_position)
+            });
+        let run_result = self.run_while_it_has_gas(|| {
+            if nr_redrawn == nr_tickets_to_redraw || ticket_position > last_ticket_position {
+                return STOP_OP;
+            }
+
+            let rand_pos = rng.next_usize_in_range(ticket_position, last_ticket_position + 1);
+
+            let drawn_ticket_id = self.get_ticket_id_from_pos(rand_pos);
+            if !self.invalidated_ticket(drawn_ticket_id).get()
+                && self.try_add_group_winning_ticket(drawn_ticket_id)
+            {
+                self.ticket_status(drawn_ticket_id).set(WINNING_TICKET);
+                nr_redrawn += 1;
+            }
+
+            let current_ticket_id = self.get_ticket_id_from_pos(ticket_position);
+            self.ticket_pos_to_id(rand_pos).set(current_ticket_id);
+
+            ticket_position += 1;
+
+            CONTINUE_OP
+        });
+
+        match run_result {
+            OperationCompletionStatus::InterruptedBeforeOutOfGas => {
+                self.save_progress(&OngoingOperationType::RedrawWinners {
+                    rng,
+                    ticket_position,
+                    nr_redrawn,
+                });
+            }
+            OperationCompletionStatus::Completed => {
+                self.next_redraw_ticket_position().set(ticket_position);
+                self.nr_winning_tickets_to_redraw().clear();
+                self.nr_winning_tickets()
+                    .update(|nr_winning_tickets| *nr_winning_tickets += nr_redrawn);
+
+                let redrawn_ticket_payment = self.get_winning_ticket_price() * (nr_redrawn as u32);
+                self.claimable_ticket_payment()
+                    .update(|claimable_ticket_payment| {
+                        *claimable_ticket_payment += redrawn_ticket_payment
+                    });
+
+                self.emit_redraw_winners_completed_event(nr_tickets_to_redraw, nr_redrawn);
+            }
+        };
+
+        run_result
+    }
+
+    fn init_winner_selection_rng(&self) -> Random<Self::Api> {
+        let seed_mapper = self.winner_selection_seed();
+        if !seed_mapper.is_empty() {
+            return Random::from_seed(seed_mapper.get());
+        }
+
+        require!(self.seed_commitment().is_empty(), "Must reveal seed first");
+
+        let rng = Random::default();
+        seed_mapper.set(&rng.seed);
+
+        rng
+    }
+
+    /// The launchpad is cancelled if the committed seed was not revealed before the deadline
+    fn try_cancel_unrevealed_seed(&self) -> bool {
+        if self.seed_commitment().is_empty() || !self.winner_selection_seed().is_empty() {
+            return false;
+        }
+
+        let reveal_deadline = self.seed_reveal_deadline().get();
+        if self.get_current_timeline_point() < reveal_deadline {
+            return false;
+        }
+
+        self.launchpad_cancelled().set(true);
+        self.emit_seed_not_revealed_event(reveal_deadline);
+
+        true
+    }
+
+    /// The draws that follow `selectWinners` derive their seed from the winner selection seed,
+    /// so the caller can't influence them and they can be replayed off-chain.
+    /// The seed is the SHA256 hash of the selection seed, the draw tag
+    /// and the big-endian u32 position the draw starts from.
+    fn derive_winner_selection_rng(
+        &self,
+        draw_tag: &[u8],
+        first_ticket_position: usize,
+    ) -> Random<Self::Api> {
+        let mut seed_input = self.winner_selection_seed().get();
+        seed_input.append_bytes(draw_tag);
+        seed_input.append_bytes(&(first_ticket_position as u32).to_be_bytes());
+
+        Random::from_hash(self.crypto().sha256(&seed_input), 0)
+    }
+
     /// Fisher-Yates algorithm,
-    /// each position i is swapped with a random one in range [i, n]
+    /// each position i is swapped with a random one in range [i, n].
+    /// Returns the ID of the newly selected winning ticket.
     fn shuffle_single_ticket(
         &self,
         rng: &mut Random<Self::Api>,
         current_ticket_position: usize,
         last_ticket_position: usize,
-    ) {
+    ) -> usize {
         let rand_pos = rng.next_usize_in_range(current_ticket_position, last_ticket_position + 1);
 
         let winning_ticket_id = self.get_ticket_id_from_pos(rand_pos);
@@ -170,6 +682,8 @@ pub trait WinnerSelectionModule:
 
         let current_ticket_id = self.get_ticket_id_from_pos(current_ticket_position);
         self.ticket_pos_to_id(rand_pos).set(current_ticket_id);
+
+        winning_ticket_id
     }
 
     #[view(getNumberOfWinningTicketsForAddress)]
@@ -207,4 +721,22 @@ pub trait WinnerSelectionModule:
 
         self.blockchain().check_caller_is_user_account();
     }
+
+    #[view(getSeedCommitment)]
+    #[storage_mapper("seedCommitment")]
+    fn seed_commitment(&self) -> SingleValueMapper<Hash<Self::Api>>;
+
+    #[view(getRevealedSeed)]
+    #[storage_mapper("revealedSeed")]
+    fn revealed_seed(&self) -> SingleValueMapper<ManagedBuffer>;
+
+    #[view(getSeedRevealDeadline)]
+    #[storage_mapper("seedRevealDeadline")]
+    fn seed_reveal_deadline(&self) -> SingleValueMapper<u64>;
+
+    /// The initial seed used by the shuffle in `selectWinners`.
+    /// Together with the ticket list, it allows replaying the whole selection off-chain.
+    #[view(getWinnerSelectionSeed)]
+    #[storage_mapper("winnerSelectionSeed")]
+    fn winner_selection_seed(&self) -> SingleValueMapper<ManagedBuffer>;
 }
@@ -1,7 +1,12 @@
 dharitri_sc::imports!();
 dharitri_sc::derive_imports!();
 
-use crate::token_release::UnlockMilestone;
+use launchpad_common::random::Hash;
+
+use crate::{
+    guaranteed_tickets_init::GuaranteedTicketInfo,
+    token_release::{UnlockMilestone, UnlockSchedule},
+};
 
 #[derive(TypeAbi, TopEncode)]
 pub struct ClaimLaunchpadTokensEvent<M: ManagedTypeApi> {
@@ -11,14 +16,6 @@ pub struct ClaimLaunchpadTokensEvent<M: ManagedTypeApi> {
     token_payment: DcdtTokenPayment<M>,
 }
 
-#[derive(TypeAbi, TopEncode)]
-pub struct AddUsersToBlacklistEvent<M: ManagedTypeApi> {
-    admin: ManagedAddress<M>,
-    block: u64,
-    epoch: u64,
-    users: ManagedVec<M, ManagedAddress<M>>,
-}
-
 #[derive(TypeAbi, TopEncode)]
 pub struct RemoveGuaranteedUsersFromBlacklistEvent<M: ManagedTypeApi> {
     admin: ManagedAddress<M>,
@@ -53,6 +50,58 @@ pub struct DistributeGuaranteedTicketsCompletedEvent<M: ManagedTypeApi> {
     total_additional_winning_tickets: usize,
 }
 
+#[derive(TypeAbi, TopEncode)]
+pub struct SetWhitelistMerkleRootEvent<M: ManagedTypeApi> {
+    admin: ManagedAddress<M>,
+    block: u64,
+    epoch: u64,
+    merkle_root: Hash<M>,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct ClaimTicketAllocationEvent<M: ManagedTypeApi> {
+    user: ManagedAddress<M>,
+    block: u64,
+    epoch: u64,
+    total_tickets_allowance: usize,
+    guaranteed_tickets_info: ManagedVec<M, GuaranteedTicketInfo>,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct SetLinearUnlockScheduleEvent<M: ManagedTypeApi> {
+    admin: ManagedAddress<M>,
+    block: u64,
+    epoch: u64,
+    cliff_epoch: u64,
+    start_epoch: u64,
+    end_epoch: u64,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct SetNamedUnlockScheduleEvent<M: ManagedTypeApi> {
+    admin: ManagedAddress<M>,
+    block: u64,
+    epoch: u64,
+    schedule_name: ManagedBuffer<M>,
+    unlock_schedule: UnlockSchedule<M>,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct CreateVestingPositionEvent<M: ManagedTypeApi> {
+    user: ManagedAddress<M>,
+    block: u64,
+    epoch: u64,
+    vesting_position: DcdtTokenPayment<M>,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct RedeemVestingPositionEvent<M: ManagedTypeApi> {
+    user: ManagedAddress<M>,
+    block: u64,
+    epoch: u64,
+    token_payment: DcdtTokenPayment<M>,
+}
+
 #[dharitri_sc::module]
 pub trait EventsModule {
     fn emit_claim_launchpad_tokens_event(&self, token_payment: DcdtTokenPayment) {
@@ -72,23 +121,6 @@ pub trait EventsModule {
         )
     }
 
-    fn emit_add_users_to_blacklist_event(&self, users: ManagedVec<ManagedAddress>) {
-        let admin = self.blockchain().get_caller();
-        let block = self.blockchain().get_block_nonce();
-        let epoch = self.blockchain().get_block_epoch();
-        self.add_users_to_blacklist_event(
-            admin.clone(),
-            block,
-            epoch,
-            AddUsersToBlacklistEvent {
-                admin,
-                block,
-                epoch,
-                users,
-            },
-        )
-    }
-
     fn emit_remove_guaranteed_users_from_blacklist_event(&self, users: ManagedVec<ManagedAddress>) {
         let admin = self.blockchain().get_caller();
         let block = self.blockchain().get_block_nonce();
@@ -167,6 +199,125 @@ pub trait EventsModule {
         )
     }
 
+    fn emit_set_whitelist_merkle_root_event(&self, merkle_root: Hash<Self::Api>) {
+        let admin = self.blockchain().get_caller();
+        let block = self.blockchain().get_block_nonce();
+        let epoch = self.blockchain().get_block_epoch();
+        self.set_whitelist_merkle_root_event(
+            admin.clone(),
+            block,
+            epoch,
+            SetWhitelistMerkleRootEvent {
+                admin,
+                block,
+                epoch,
+                merkle_root,
+            },
+        )
+    }
+
+    fn emit_claim_ticket_allocation_event(
+        &self,
+        total_tickets_allowance: usize,
+        guaranteed_tickets_info: ManagedVec<GuaranteedTicketInfo>,
+    ) {
+        let user = self.blockchain().get_caller();
+        let block = self.blockchain().get_block_nonce();
+        let epoch = self.blockchain().get_block_epoch();
+        self.claim_ticket_allocation_event(
+            user.clone(),
+            block,
+            epoch,
+            ClaimTicketAllocationEvent {
+                user,
+                block,
+                epoch,
+                total_tickets_allowance,
+                guaranteed_tickets_info,
+            },
+        )
+    }
+
+    fn emit_set_linear_unlock_schedule_event(
+        &self,
+        cliff_epoch: u64,
+        start_epoch: u64,
+        end_epoch: u64,
+    ) {
+        let admin = self.blockchain().get_caller();
+        let block = self.blockchain().get_block_nonce();
+        let epoch = self.blockchain().get_block_epoch();
+        self.set_linear_unlock_schedule_event(
+            admin.clone(),
+            block,
+            epoch,
+            SetLinearUnlockScheduleEvent {
+                admin,
+                block,
+                epoch,
+                cliff_epoch,
+                start_epoch,
+                end_epoch,
+            },
+        )
+    }
+
+    fn emit_set_named_unlock_schedule_event(
+        &self,
+        schedule_name: ManagedBuffer,
+        unlock_schedule: UnlockSchedule<Self::Api>,
+    ) {
+        let admin = self.blockchain().get_caller();
+        let block = self.blockchain().get_block_nonce();
+        let epoch = self.blockchain().get_block_epoch();
+        self.set_named_unlock_schedule_event(
+            admin.clone(),
+            block,
+            epoch,
+            SetNamedUnlockScheduleEvent {
+                admin,
+                block,
+                epoch,
+                schedule_name,
+                unlock_schedule,
+            },
+        )
+    }
+
+    fn emit_create_vesting_position_event(&self, vesting_position: DcdtTokenPayment) {
+        let user = self.blockchain().get_caller();
+        let block = self.blockchain().get_block_nonce();
+        let epoch = self.blockchain().get_block_epoch();
+        self.create_vesting_position_event(
+            user.clone(),
+            block,
+            epoch,
+            CreateVestingPositionEvent {
+                user,
+                block,
+                epoch,
+                vesting_position,
+            },
+        )
+    }
+
+    fn emit_redeem_vesting_position_event(&self, token_payment: DcdtTokenPayment) {
+        let user = self.blockchain().get_caller();
+        let block = self.blockchain().get_block_nonce();
+        let epoch = self.blockchain().get_block_epoch();
+        self.redeem_vesting_position_event(
+            user.clone(),
+            block,
+            epoch,
+            RedeemVestingPositionEvent {
+                user,
+                block,
+                epoch,
+                token_payment,
+            },
+        )
+    }
+
     #[event("claimLaunchpadTokens")]
     fn claim_launchpad_tokens_event(
         &self,
@@ -176,15 +327,6 @@ pub trait EventsModule {
         claim_launchpad_tokens_event: ClaimLaunchpadTokensEvent<Self::Api>,
     );
 
-    #[event("addUsersToBlacklist")]
-    fn add_users_to_blacklist_event(
-        &self,
-        #[indexed] admin: ManagedAddress,
-        #[indexed] block: u64,
-        #[indexed] epoch: u64,
-        add_users_to_blacklist_event: AddUsersToBlacklistEvent<Self::Api>,
-    );
-
     #[event("removeGuaranteedUsersFromBlacklist")]
     fn remove_guaranteed_users_from_blacklist_event(
         &self,
@@ -224,4 +366,58 @@ pub trait EventsModule {
             Self::Api,
         >,
     );
+
+    #[event("setWhitelistMerkleRoot")]
+    fn set_whitelist_merkle_root_event(
+        &self,
+        #[indexed] admin: ManagedAddress,
+        #[indexed] block: u64,
+        #[indexed] epoch: u64,
+        set_whitelist_merkle_root_event: SetWhitelistMerkleRootEvent<Self::Api>,
+    );
+
+    #[event("claimTicketAllocation")]
+    fn claim_ticket_allocation_event(
+        &self,
+        #[indexed] user: ManagedAddress,
+        #[indexed] block: u64,
+        #[indexed] epoch: u64,
+        claim_ticket_allocation_event: ClaimTicketAllocationEvent<Self::Api>,
+    );
+
+    #[event("setLinearUnlockSchedule")]
+    fn set_linear_unlock_schedule_event(
+        &self,
+        #[indexed] admin: ManagedAddress,
+        #[indexed] block: u64,
+        #[indexed] epoch: u64,
+        set_linear_unlock_schedule_event: SetLinearUnlockScheduleEvent<Self::Api>,
+    );
+
+    #[event("setNamedUnlockSchedule")]
+    fn set_named_unlock_schedule_event(
+        &self,
+        #[indexed] admin: ManagedAddress,
+        #[indexed] block: u64,
+        #[indexed] epoch: u64,
+        set_named_unlock_schedule_event: SetNamedUnlockScheduleEvent<Self::Api>,
+    );
+
+    #[event("createVestingPosition")]
+    fn create_vesting_position_event(
+        &self,
+        #[indexed] user: ManagedAddress,
+        #[indexed] block: u64,
+        #[indexed] epoch: u64,
+        create_vesting_position_event: CreateVestingPositionEvent<Self::Api>,
+    );
+
+    #[event("redeemVestingPosition")]
+    fn redeem_vesting_position_event(
+        &self,
+        #[indexed] user: ManagedAddress,
+        #[indexed] block: u64,
+        #[indexed] epoch: u64,
+        redeem_vesting_position_event: RedeemVestingPositionEvent<Self::Api>,
+    );
 }
@@ -37,12 +37,15 @@ pub trait GuaranteedTicketsInitModule:
     + launchpad_common::config::ConfigModule
     + launchpad_common::ongoing_operation::OngoingOperationModule
     + launchpad_common::tickets::TicketsModule
+    + crate::token_release::TokenReleaseModule
+    + crate::events::EventsModule
 {
     fn add_tickets_with_guaranteed_winners(
         &self,
         address_number_pairs: MultiValueEncoded<
             MultiValue3<ManagedAddress, usize, MultiValueEncodedCounted<MultiValue2<usize, usize>>>,
         >,
+        opt_unlock_schedule_name: Option<ManagedBuffer>,
     ) -> AddTicketsResult {
         self.require_add_tickets_period();
 
@@ -60,56 +63,31 @@ pub trait GuaranteedTicketsInitModule:
                 continue;
             }
 
-            require!(
-                !self.blockchain().is_smart_contract(&buyer),
-                "Only user accounts can participate"
-            );
-            require!(
-                total_tickets_allowance <= MAX_TICKETS_ALLOWANCE,
-                "Total number of tickets exceeds maximum allowed"
-            );
-            require!(
-                guaranteed_ticket_raw.len() <= MAX_GUARANTEED_TICKETS_ENTRIES,
-                "Number of guaranteed tickets entries exceeds maximum allowed"
-            );
-
-            self.try_create_tickets(buyer.clone(), total_tickets_allowance);
-
-            let mut user_ticket_status = UserTicketsStatus::new(total_tickets_allowance);
-
-            let mut user_guaranteed_tickets = 0;
-
             let mut guaranteed_ticket_infos = ManagedVec::new();
             for info in guaranteed_ticket_raw.into_iter() {
                 let (guaranteed_tickets, min_confirmed_tickets) = info.into_tuple();
-                require!(
-                    guaranteed_tickets <= min_confirmed_tickets,
-                    "Invalid guaranteed ticket min confirmed tickets"
-                );
-                user_guaranteed_tickets += guaranteed_tickets;
-
-                let guaranteed_ticket_info = GuaranteedTicketInfo {
+                guaranteed_ticket_infos.push(GuaranteedTicketInfo {
                     guaranteed_tickets,
                     min_confirmed_tickets,
-                };
-                guaranteed_ticket_infos.push(guaranteed_ticket_info);
+                });
             }
 
-            if user_guaranteed_tickets > 0 {
-                require!(
-                    total_winning_tickets >= user_guaranteed_tickets,
-                    "Not enough winning tickets for guaranteed allocation"
-                );
-                let _ = guaranteed_ticket_whitelist.insert(buyer.clone());
-                total_winning_tickets -= user_guaranteed_tickets;
-                total_guaranteed_tickets += user_guaranteed_tickets;
-                user_ticket_status.guaranteed_tickets_info = guaranteed_ticket_infos;
-                total_guaranteed_tickets_added += user_guaranteed_tickets;
+            if let Some(unlock_schedule_name) = &opt_unlock_schedule_name {
+                self.assign_user_unlock_schedule(&buyer, unlock_schedule_name);
             }
-            total_tickets_added += total_tickets_allowance;
 
+            let user_guaranteed_tickets = self.try_add_user_tickets(
+                buyer,
+                total_tickets_allowance,
+                guaranteed_ticket_infos,
+                &mut guaranteed_ticket_whitelist,
+                &mut total_winning_tickets,
+                &mut total_guaranteed_tickets,
+            );
+
+            total_guaranteed_tickets_added += user_guaranteed_tickets;
+            total_tickets_added += total_tickets_allowance;
             total_users_count += 1;
-            self.user_ticket_status(&buyer).set(user_ticket_status);
         }
 
         self.total_guaranteed_tickets()
@@ -123,6 +101,59 @@ pub trait GuaranteedTicketsInitModule:
         }
     }
 
+    /// Creates the user's tickets and reserves their guaranteed tickets.
+    /// Returns the number of guaranteed tickets reserved for the user.
+    fn try_add_user_tickets(
+        &self,
+        buyer: ManagedAddress,
+        total_tickets_allowance: usize,
+        guaranteed_ticket_infos: ManagedVec<GuaranteedTicketInfo>,
+        guaranteed_ticket_whitelist: &mut UnorderedSetMapper<ManagedAddress>,
+        total_winning_tickets: &mut usize,
+        total_guaranteed_tickets: &mut usize,
+    ) -> usize {
+        require!(
+            !self.blockchain().is_smart_contract(&buyer),
+            "Only user accounts can participate"
+        );
+        require!(
+            total_tickets_allowance <= MAX_TICKETS_ALLOWANCE,
+            "Total number of tickets exceeds maximum allowed"
+        );
+        require!(
+            guaranteed_ticket_infos.len() <= MAX_GUARANTEED_TICKETS_ENTRIES,
+            "Number of guaranteed tickets entries exceeds maximum allowed"
+        );
+
+        self.try_create_tickets(buyer.clone(), total_tickets_allowance);
+
+        let mut user_ticket_status = UserTicketsStatus::new(total_tickets_allowance);
+
+        let mut user_guaranteed_tickets = 0;
+        for info in guaranteed_ticket_infos.iter() {
+            require!(
+                info.guaranteed_tickets <= info.min_confirmed_tickets,
+                "Invalid guaranteed ticket min confirmed tickets"
+            );
+            user_guaranteed_tickets += info.guaranteed_tickets;
+        }
+
+        if user_guaranteed_tickets > 0 {
+            require!(
+                *total_winning_tickets >= user_guaranteed_tickets,
+                "Not enough winning tickets for guaranteed allocation"
+            );
+            let _ = guaranteed_ticket_whitelist.insert(buyer.clone());
+            *total_winning_tickets -= user_guaranteed_tickets;
+            *total_guaranteed_tickets += user_guaranteed_tickets;
+            user_ticket_status.guaranteed_tickets_info = guaranteed_ticket_infos;
+        }
+
+        self.user_ticket_status(&buyer).set(user_ticket_status);
+
+        user_guaranteed_tickets
+    }
+
     fn clear_users_with_guaranteed_ticket_after_blacklist(
         &self,
         users: &ManagedVec<ManagedAddress>,
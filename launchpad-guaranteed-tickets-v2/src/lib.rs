@@ -3,14 +3,22 @@
 dharitri_sc::imports!();
 dharitri_sc::derive_imports!();
 
-use launchpad_common::{config::TokenAmountPair, launch_stage::Flags, tickets::WINNING_TICKET};
+use launchpad_common::{
+    config::{TimelineMode, TokenAmountPair},
+    launch_stage::Flags,
+    permissions::Role,
+    tickets::WINNING_TICKET,
+    timelock::AdminAction,
+};
 
 use crate::guaranteed_ticket_winners::GuaranteedTicketsSelectionOperation;
 
 pub mod events;
 pub mod guaranteed_ticket_winners;
 pub mod guaranteed_tickets_init;
+pub mod merkle_whitelist;
 pub mod token_release;
+pub mod vesting_position;
 
 use crate::guaranteed_tickets_init::GuaranteedTicketInfo;
 
@@ -26,14 +34,21 @@ pub trait LaunchpadGuaranteedTickets:
     + launchpad_common::winner_selection::WinnerSelectionModule
     + launchpad_common::ongoing_operation::OngoingOperationModule
     + launchpad_common::permissions::PermissionsModule
+    + launchpad_common::address_groups::AddressGroupsModule
+    + launchpad_common::timelock::TimelockModule
     + launchpad_common::blacklist::BlacklistModule
     + launchpad_common::token_send::TokenSendModule
+    + launchpad_common::price_oracle::PriceOracleModule
     + launchpad_common::user_interactions::UserInteractionsModule
+    + launchpad_common::cancellation::CancellationModule
     + guaranteed_tickets_init::GuaranteedTicketsInitModule
     + guaranteed_ticket_winners::GuaranteedTicketWinnersModule
+    + merkle_whitelist::MerkleWhitelistModule
     + token_release::TokenReleaseModule
+    + vesting_position::VestingPositionModule
     + events::EventsModule
     + launchpad_common::common_events::CommonEventsModule
+    + dharitri_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
     + dharitri_sc_modules::pause::PauseModule
 {
     #[allow(clippy::too_many_arguments)]
@@ -48,6 +63,7 @@ pub trait LaunchpadGuaranteedTickets:
         confirmation_period_start_block: u64,
         winner_selection_start_block: u64,
         claim_start_block: u64,
+        opt_timeline_mode: OptionalValue<TimelineMode>,
     ) {
         self.init_base(
             launchpad_token_id,
@@ -59,15 +75,19 @@ pub trait LaunchpadGuaranteedTickets:
             winner_selection_start_block,
             claim_start_block,
             Flags::default(),
+            opt_timeline_mode.into_option().unwrap_or_default(),
         );
     }
 
+    /// `blacklisted_users` lists the users blacklisted by the previous version
     #[upgrade]
-    fn upgrade(&self) {}
+    fn upgrade(&self, blacklisted_users: MultiValueEncoded<ManagedAddress>) {
+        self.migrate_support_address();
+        self.migrate_blacklisted_users(blacklisted_users);
+    }
 
     /// The parameter `address_number_pairs` is a list of tuples, where each tuple contains:
     /// the address of the user, total number of tickets to be added, and a list of guaranteed tickets
-    #[only_owner]
     #[endpoint(addTickets)]
     fn add_tickets_endpoint(
         &self,
@@ -75,7 +95,32 @@ pub trait LaunchpadGuaranteedTickets:
             MultiValue3<ManagedAddress, usize, MultiValueEncodedCounted<MultiValue2<usize, usize>>>,
         >,
     ) {
-        let add_tickets_result = self.add_tickets_with_guaranteed_winners(address_number_pairs);
+        self.require_role(Role::TicketManager);
+
+        let add_tickets_result =
+            self.add_tickets_with_guaranteed_winners(address_number_pairs, None);
+
+        self.emit_add_tickets_event(
+            add_tickets_result.total_users_count,
+            add_tickets_result.total_tickets_added,
+            add_tickets_result.total_guaranteed_tickets_added,
+        );
+    }
+
+    /// Same as `addTickets`, but the added users release their launchpad tokens
+    /// following the given named unlock schedule, instead of the global one
+    #[endpoint(addTicketsWithUnlockSchedule)]
+    fn add_tickets_with_unlock_schedule_endpoint(
+        &self,
+        unlock_schedule_name: ManagedBuffer,
+        address_number_pairs: MultiValueEncoded<
+            MultiValue3<ManagedAddress, usize, MultiValueEncodedCounted<MultiValue2<usize, usize>>>,
+        >,
+    ) {
+        self.require_role(Role::TicketManager);
+
+        let add_tickets_result = self
+            .add_tickets_with_guaranteed_winners(address_number_pairs, Some(unlock_schedule_name));
 
         self.emit_add_tickets_event(
             add_tickets_result.total_users_count,
@@ -84,10 +129,11 @@ pub trait LaunchpadGuaranteedTickets:
         );
     }
 
-    #[only_owner]
     #[payable("*")]
     #[endpoint(depositLaunchpadTokens)]
     fn deposit_launchpad_tokens_endpoint(&self) {
+        self.require_role(Role::Treasurer);
+
         let base_selection_winning_tickets = self.nr_winning_tickets().get();
         let reserved_tickets = self.total_guaranteed_tickets().get();
         let total_tickets = base_selection_winning_tickets + reserved_tickets;
@@ -96,12 +142,14 @@ pub trait LaunchpadGuaranteedTickets:
     }
 
     #[endpoint(addUsersToBlacklist)]
-    fn add_users_to_blacklist_endpoint(&self, users_list: MultiValueEncoded<ManagedAddress>) {
+    fn add_users_to_blacklist_endpoint(
+        &self,
+        reason: u32,
+        users_list: MultiValueEncoded<ManagedAddress>,
+    ) {
         let users_vec = users_list.to_vec();
-        self.add_users_to_blacklist(&users_vec);
+        self.add_users_to_blacklist(&users_vec, reason);
         self.clear_users_with_guaranteed_ticket_after_blacklist(&users_vec);
-
-        self.emit_add_users_to_blacklist_event(users_vec);
     }
 
     #[endpoint(removeGuaranteedUsersFromBlacklist)]
@@ -194,6 +242,8 @@ pub trait LaunchpadGuaranteedTickets:
                 claimable_tokens,
             ));
         }
+
+        self.try_create_vesting_position(&caller);
     }
 
     fn compute_launchpad_results(&self, caller: &ManagedAddress) {
@@ -227,6 +277,7 @@ pub trait LaunchpadGuaranteedTickets:
 
         let nr_tickets_to_refund = nr_confirmed_tickets - nr_redeemable_tickets;
         self.refund_ticket_payment(caller, nr_tickets_to_refund);
+        self.settle_ticket_payment(caller);
 
         if nr_redeemable_tickets > 0 {
             let tokens_per_winning_ticket = self.launchpad_tokens_per_winning_ticket().get();
@@ -238,22 +289,20 @@ pub trait LaunchpadGuaranteedTickets:
         }
     }
 
-    #[only_owner]
+    #[endpoint(claimRefund)]
+    fn claim_refund_endpoint(&self) {
+        self.claim_cancellation_refund();
+    }
+
     #[endpoint(claimTicketPayment)]
     fn claim_ticket_payment_endpoint(&self) {
+        self.require_role(Role::Treasurer);
         self.require_claim_period();
 
-        let owner = self.blockchain().get_caller();
+        let owner = self.blockchain().get_owner_address();
 
         let ticket_price: TokenAmountPair<Self::Api> = self.ticket_price().get();
-        let ticket_payment_mapper = self.claimable_ticket_payment();
-        let claimable_ticket_payment = ticket_payment_mapper.get();
-        if claimable_ticket_payment > 0 {
-            ticket_payment_mapper.clear();
-
-            self.send()
-                .direct(&owner, &ticket_price.token_id, 0, &claimable_ticket_payment);
-        }
+        let claimable_ticket_payment = self.send_claimable_ticket_payment(&owner);
 
         let deposited_tokens_mapper = self.total_launchpad_tokens_deposited();
         let total_launchpad_tokens_deposited = deposited_tokens_mapper.take();
@@ -289,4 +338,32 @@ pub trait LaunchpadGuaranteedTickets:
         )
             .into()
     }
+
+    /// Guaranteed tickets the user qualifies for, given the currently confirmed tickets
+    #[view(getUserGuaranteedTickets)]
+    fn user_guaranteed_tickets(&self, address: ManagedAddress) -> usize {
+        let user_ticket_status_mapper = self.user_ticket_status(&address);
+        require!(!user_ticket_status_mapper.is_empty(), "User not found");
+        let user_confirmed_tickets = self.nr_confirmed_tickets(&address).get();
+
+        self.calculate_guaranteed_tickets(&user_ticket_status_mapper.get(), user_confirmed_tickets)
+            .guaranteed_tickets
+    }
+
+    #[endpoint(proposeAdminAction)]
+    fn propose_admin_action_endpoint(&self, action: AdminAction<Self::Api>) -> u64 {
+        require!(
+            action.is_common() || matches!(action, AdminAction::SetUnlockSchedule { .. }),
+            "Action not supported"
+        );
+        self.propose_admin_action(action)
+    }
+
+    #[endpoint(executeAdminAction)]
+    fn execute_admin_action_endpoint(&self, action_id: u64) {
+        match self.take_executable_admin_action(action_id) {
+            AdminAction::SetUnlockSchedule { args } => self.set_unlock_schedule_from_args(args),
+            action => self.execute_admin_action(action),
+        }
+    }
 }
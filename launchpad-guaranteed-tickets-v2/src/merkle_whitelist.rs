@@ -0,0 +1,123 @@
+dharitri_sc::imports!();
+dharitri_sc::derive_imports!();
+
+use launchpad_common::{permissions::Role, random::Hash};
+
+use crate::guaranteed_tickets_init::GuaranteedTicketInfo;
+
+/// The leaf hash is sha256 over the top encoding of this struct
+#[derive(TopEncode)]
+pub struct WhitelistLeaf<M: ManagedTypeApi> {
+    pub address: ManagedAddress<M>,
+    pub total_tickets_allowance: usize,
+    pub guaranteed_tickets_info: ManagedVec<M, GuaranteedTicketInfo>,
+}
+
+#[dharitri_sc::module]
+pub trait MerkleWhitelistModule:
+    crate::guaranteed_tickets_init::GuaranteedTicketsInitModule
+    + launchpad_common::launch_stage::LaunchStageModule
+    + launchpad_common::config::ConfigModule
+    + launchpad_common::ongoing_operation::OngoingOperationModule
+    + launchpad_common::tickets::TicketsModule
+    + launchpad_common::blacklist::BlacklistModule
+    + launchpad_common::permissions::PermissionsModule
+    + crate::events::EventsModule
+    + dharitri_sc_modules::pause::PauseModule
+{
+    #[endpoint(setWhitelistMerkleRoot)]
+    fn set_whitelist_merkle_root(&self, merkle_root: Hash<Self::Api>) {
+        self.require_role(Role::TicketManager);
+        self.require_add_tickets_period();
+
+        self.whitelist_merkle_root().set(&merkle_root);
+
+        self.emit_set_whitelist_merkle_root_event(merkle_root);
+    }
+
+    /// Proof nodes are given from the leaf up to the root.
+    /// Each pair of nodes is sorted before hashing.
+    #[endpoint(claimTicketAllocation)]
+    fn claim_ticket_allocation(
+        &self,
+        total_tickets_allowance: usize,
+        guaranteed_tickets_info: ManagedVec<GuaranteedTicketInfo>,
+        proof: MultiValueEncoded<Hash<Self::Api>>,
+    ) {
+        self.require_not_paused();
+        self.require_confirmation_period();
+
+        let caller = self.blockchain().get_caller();
+        require!(
+            !self.is_user_blacklisted(&caller),
+            "You have been put into the blacklist and may not claim tickets"
+        );
+        require!(total_tickets_allowance > 0, "No tickets to claim");
+
+        let merkle_root_mapper = self.whitelist_merkle_root();
+        require!(!merkle_root_mapper.is_empty(), "Merkle root not set");
+
+        let leaf_hash = self.compute_whitelist_leaf_hash(WhitelistLeaf {
+            address: caller.clone(),
+            total_tickets_allowance,
+            guaranteed_tickets_info: guaranteed_tickets_info.clone(),
+        });
+        let computed_root = self.compute_merkle_root(leaf_hash, proof);
+        require!(
+            computed_root == merkle_root_mapper.get(),
+            "Invalid Merkle proof"
+        );
+
+        let mut guaranteed_ticket_whitelist = self.users_with_guaranteed_ticket();
+        let mut total_winning_tickets = self.nr_winning_tickets().get();
+        let mut total_guaranteed_tickets = self.total_guaranteed_tickets().get();
+
+        let _ = self.try_add_user_tickets(
+            caller,
+            total_tickets_allowance,
+            guaranteed_tickets_info.clone(),
+            &mut guaranteed_ticket_whitelist,
+            &mut total_winning_tickets,
+            &mut total_guaranteed_tickets,
+        );
+
+        self.total_guaranteed_tickets()
+            .set(total_guaranteed_tickets);
+        self.nr_winning_tickets().set(total_winning_tickets);
+
+        self.emit_claim_ticket_allocation_event(total_tickets_allowance, guaranteed_tickets_info);
+    }
+
+    fn compute_whitelist_leaf_hash(&self, leaf: WhitelistLeaf<Self::Api>) -> Hash<Self::Api> {
+        let mut encoded_leaf = ManagedBuffer::new();
+        let _ = leaf.top_encode(&mut encoded_leaf);
+
+        self.crypto().sha256(&encoded_leaf)
+    }
+
+    fn compute_merkle_root(
+        &self,
+        leaf_hash: Hash<Self::Api>,
+        proof: MultiValueEncoded<Hash<Self::Api>>,
+    ) -> Hash<Self::Api> {
+        let mut computed_hash = leaf_hash;
+        for proof_node in proof {
+            let mut pair = ManagedBuffer::new();
+            if computed_hash.to_byte_array() <= proof_node.to_byte_array() {
+                pair.append(computed_hash.as_managed_buffer());
+                pair.append(proof_node.as_managed_buffer());
+            } else {
+                pair.append(proof_node.as_managed_buffer());
+                pair.append(computed_hash.as_managed_buffer());
+            }
+
+            computed_hash = self.crypto().sha256(&pair);
+        }
+
+        computed_hash
+    }
+
+    #[view(getWhitelistMerkleRoot)]
+    #[storage_mapper("whitelistMerkleRoot")]
+    fn whitelist_merkle_root(&self) -> SingleValueMapper<Hash<Self::Api>>;
+}
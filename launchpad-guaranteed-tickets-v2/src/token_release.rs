@@ -1,7 +1,7 @@
 dharitri_sc::imports!();
 dharitri_sc::derive_imports!();
 
-use launchpad_common::{config, launch_stage};
+use launchpad_common::{config, launch_stage, permissions, permissions::Role};
 
 pub const MAX_PERCENTAGE: u64 = 10_000;
 pub const MAX_UNLOCK_MILESTONES_ENTRIES: usize = 60;
@@ -14,13 +14,35 @@ pub struct UnlockMilestone {
 }
 
 #[derive(TopEncode, TopDecode, TypeAbi, NestedEncode, NestedDecode)]
-pub struct UnlockSchedule<M: ManagedTypeApi> {
-    milestones: ManagedVec<M, UnlockMilestone>,
+pub enum UnlockSchedule<M: ManagedTypeApi> {
+    Milestones {
+        milestones: ManagedVec<M, UnlockMilestone>,
+    },
+    /// Nothing is unlocked before the cliff epoch. Afterwards, tokens are unlocked
+    /// linearly between the start epoch and the end epoch.
+    Linear {
+        cliff_epoch: u64,
+        start_epoch: u64,
+        end_epoch: u64,
+    },
+}
+
+/// The layout of the global `unlockSchedule` storage, unchanged since before linear schedules were added
+#[derive(TopEncode, TopDecode, TypeAbi, NestedEncode, NestedDecode)]
+pub struct MilestonesUnlockSchedule<M: ManagedTypeApi> {
+    pub milestones: ManagedVec<M, UnlockMilestone>,
+}
+
+#[derive(TopEncode, TopDecode, TypeAbi, NestedEncode, NestedDecode)]
+pub struct LinearUnlockSchedule {
+    pub cliff_epoch: u64,
+    pub start_epoch: u64,
+    pub end_epoch: u64,
 }
 
 impl<M: ManagedTypeApi> Default for UnlockSchedule<M> {
     fn default() -> Self {
-        Self {
+        UnlockSchedule::Milestones {
             milestones: ManagedVec::from_single_item(UnlockMilestone {
                 release_epoch: 0,
                 percentage: MAX_PERCENTAGE,
@@ -31,18 +53,48 @@ impl<M: ManagedTypeApi> Default for UnlockSchedule<M> {
 
 impl<M: ManagedTypeApi> UnlockSchedule<M> {
     pub fn new(milestones: ManagedVec<M, UnlockMilestone>) -> Self {
-        UnlockSchedule { milestones }
+        UnlockSchedule::Milestones { milestones }
+    }
+
+    pub fn new_linear(cliff_epoch: u64, start_epoch: u64, end_epoch: u64) -> Self {
+        UnlockSchedule::Linear {
+            cliff_epoch,
+            start_epoch,
+            end_epoch,
+        }
     }
 
     fn validate(&self, current_epoch: u64) -> bool {
-        if self.milestones.is_empty() {
+        match self {
+            UnlockSchedule::Milestones { milestones } => {
+                Self::validate_milestones(milestones, current_epoch)
+            }
+            UnlockSchedule::Linear {
+                cliff_epoch,
+                start_epoch,
+                end_epoch,
+            } => {
+                *start_epoch >= current_epoch
+                    && start_epoch < end_epoch
+                    && start_epoch <= cliff_epoch
+                    && cliff_epoch <= end_epoch
+                    && *end_epoch <= current_epoch + MAX_RELEASE_EPOCH_DIFF
+            }
+        }
+    }
+
+    fn validate_milestones(
+        milestones: &ManagedVec<M, UnlockMilestone>,
+        current_epoch: u64,
+    ) -> bool {
+        if milestones.is_empty() {
             return false;
         }
 
         let mut total_percentage = 0u64;
         let mut last_epoch = 0u64;
 
-        for milestone in self.milestones.iter() {
+        for milestone in milestones.iter() {
             if milestone.percentage > MAX_PERCENTAGE
                 || milestone.release_epoch < current_epoch
                 || milestone.release_epoch < last_epoch
@@ -57,16 +109,159 @@ impl<M: ManagedTypeApi> UnlockSchedule<M> {
 
         total_percentage == MAX_PERCENTAGE
     }
+
+    pub fn compute_unlocked_amount(
+        &self,
+        total_amount: &BigUint<M>,
+        current_epoch: u64,
+    ) -> BigUint<M> {
+        match self {
+            UnlockSchedule::Milestones { milestones } => {
+                let mut unlocked_percentage = 0u64;
+                for milestone in milestones.iter() {
+                    if milestone.release_epoch <= current_epoch {
+                        unlocked_percentage += milestone.percentage;
+                    } else {
+                        break;
+                    }
+                }
+
+                total_amount * unlocked_percentage / MAX_PERCENTAGE
+            }
+            UnlockSchedule::Linear {
+                cliff_epoch,
+                start_epoch,
+                end_epoch,
+            } => {
+                if current_epoch < *cliff_epoch {
+                    return BigUint::zero();
+                }
+                if current_epoch >= *end_epoch {
+                    return total_amount.clone();
+                }
+
+                total_amount * (current_epoch - start_epoch) / (end_epoch - start_epoch)
+            }
+        }
+    }
 }
 
 #[dharitri_sc::module]
 pub trait TokenReleaseModule:
-    config::ConfigModule + launch_stage::LaunchStageModule + crate::events::EventsModule
+    config::ConfigModule
+    + launch_stage::LaunchStageModule
+    + permissions::PermissionsModule
+    + crate::events::EventsModule
 {
-    #[only_owner]
     #[endpoint(setUnlockSchedule)]
     fn set_unlock_schedule(&self, unlock_milestones: MultiValueEncoded<MultiValue2<u64, u64>>) {
+        self.require_role(Role::Treasurer);
         self.require_add_tickets_period();
+
+        let milestones = self.collect_unlock_milestones(unlock_milestones);
+        self.try_set_unlock_schedule(milestones);
+    }
+
+    #[endpoint(setLinearUnlockSchedule)]
+    fn set_linear_unlock_schedule(&self, cliff_epoch: u64, start_epoch: u64, end_epoch: u64) {
+        self.require_role(Role::Treasurer);
+        self.require_add_tickets_period();
+
+        let unlock_schedule = UnlockSchedule::new_linear(cliff_epoch, start_epoch, end_epoch);
+        self.require_valid_unlock_schedule(&unlock_schedule);
+
+        self.unlock_schedule().clear();
+        self.linear_unlock_schedule().set(LinearUnlockSchedule {
+            cliff_epoch,
+            start_epoch,
+            end_epoch,
+        });
+
+        self.emit_set_linear_unlock_schedule_event(cliff_epoch, start_epoch, end_epoch);
+    }
+
+    /// Named schedules can be assigned to users through `addTicketsWithUnlockSchedule`.
+    /// Users without a named schedule follow the global unlock schedule.
+    #[endpoint(setNamedUnlockSchedule)]
+    fn set_named_unlock_schedule(
+        &self,
+        schedule_name: ManagedBuffer,
+        unlock_milestones: MultiValueEncoded<MultiValue2<u64, u64>>,
+    ) {
+        self.require_role(Role::Treasurer);
+        self.require_add_tickets_period();
+
+        let milestones = self.collect_unlock_milestones(unlock_milestones);
+        self.try_set_named_unlock_schedule(schedule_name, UnlockSchedule::new(milestones));
+    }
+
+    #[endpoint(setNamedLinearUnlockSchedule)]
+    fn set_named_linear_unlock_schedule(
+        &self,
+        schedule_name: ManagedBuffer,
+        cliff_epoch: u64,
+        start_epoch: u64,
+        end_epoch: u64,
+    ) {
+        self.require_role(Role::Treasurer);
+        self.require_add_tickets_period();
+
+        self.try_set_named_unlock_schedule(
+            schedule_name,
+            UnlockSchedule::new_linear(cliff_epoch, start_epoch, end_epoch),
+        );
+    }
+
+    /// Queued changes may still be applied after the confirmation period starts,
+    /// as long as the claim period has not started yet
+    fn set_unlock_schedule_from_args(&self, args: ManagedVec<u64>) {
+        self.require_before_claim_period();
+        require!(
+            args.len() % 2 == 0 && args.len() / 2 <= MAX_UNLOCK_MILESTONES_ENTRIES,
+            "Invalid unlock schedule arguments"
+        );
+
+        let mut milestones = ManagedVec::new();
+        for i in (0..args.len()).step_by(2) {
+            milestones.push(UnlockMilestone {
+                release_epoch: args.get(i),
+                percentage: args.get(i + 1),
+            });
+        }
+
+        self.try_set_unlock_schedule(milestones);
+    }
+
+    fn try_set_unlock_schedule(&self, milestones: ManagedVec<UnlockMilestone>) {
+        let unlock_schedule = UnlockSchedule::new(milestones.clone());
+        self.require_valid_unlock_schedule(&unlock_schedule);
+
+        self.linear_unlock_schedule().clear();
+        self.unlock_schedule().set(MilestonesUnlockSchedule {
+            milestones: milestones.clone(),
+        });
+
+        self.emit_set_unlock_schedule_event(milestones);
+    }
+
+    fn try_set_named_unlock_schedule(
+        &self,
+        schedule_name: ManagedBuffer,
+        unlock_schedule: UnlockSchedule<Self::Api>,
+    ) {
+        require!(!schedule_name.is_empty(), "Invalid unlock schedule name");
+        self.require_valid_unlock_schedule(&unlock_schedule);
+
+        self.named_unlock_schedule(&schedule_name)
+            .set(&unlock_schedule);
+
+        self.emit_set_named_unlock_schedule_event(schedule_name, unlock_schedule);
+    }
+
+    fn collect_unlock_milestones(
+        &self,
+        unlock_milestones: MultiValueEncoded<MultiValue2<u64, u64>>,
+    ) -> ManagedVec<UnlockMilestone> {
         require!(
             unlock_milestones.len() <= MAX_UNLOCK_MILESTONES_ENTRIES,
             "Maximum unlock milestones entries exceeded"
@@ -81,16 +276,54 @@ pub trait TokenReleaseModule:
             });
         }
 
+        milestones
+    }
+
+    fn require_valid_unlock_schedule(&self, unlock_schedule: &UnlockSchedule<Self::Api>) {
         let current_epoch = self.blockchain().get_block_epoch();
-        let unlock_schedule = UnlockSchedule::new(milestones.clone());
         require!(
             unlock_schedule.validate(current_epoch),
             "Invalid unlock schedule"
         );
+    }
 
-        self.unlock_schedule().set(unlock_schedule);
+    fn assign_user_unlock_schedule(&self, user: &ManagedAddress, schedule_name: &ManagedBuffer) {
+        require!(
+            !self.named_unlock_schedule(schedule_name).is_empty(),
+            "Unknown unlock schedule"
+        );
 
-        self.emit_set_unlock_schedule_event(milestones);
+        self.user_unlock_schedule_name(user).set(schedule_name);
+    }
+
+    fn get_user_unlock_schedule(&self, address: &ManagedAddress) -> UnlockSchedule<Self::Api> {
+        let schedule_name_mapper = self.user_unlock_schedule_name(address);
+        if !schedule_name_mapper.is_empty() {
+            return self
+                .named_unlock_schedule(&schedule_name_mapper.get())
+                .get();
+        }
+
+        self.get_global_unlock_schedule()
+    }
+
+    fn get_global_unlock_schedule(&self) -> UnlockSchedule<Self::Api> {
+        let linear_unlock_schedule_mapper = self.linear_unlock_schedule();
+        if !linear_unlock_schedule_mapper.is_empty() {
+            let linear_unlock_schedule = linear_unlock_schedule_mapper.get();
+            return UnlockSchedule::new_linear(
+                linear_unlock_schedule.cliff_epoch,
+                linear_unlock_schedule.start_epoch,
+                linear_unlock_schedule.end_epoch,
+            );
+        }
+
+        let unlock_schedule_mapper = self.unlock_schedule();
+        if unlock_schedule_mapper.is_empty() {
+            UnlockSchedule::default()
+        } else {
+            UnlockSchedule::new(unlock_schedule_mapper.get().milestones)
+        }
     }
 
     #[view(getClaimableTokens)]
@@ -106,26 +339,10 @@ pub trait TokenReleaseModule:
             "Already claimed all tokens"
         );
 
-        let unlock_schedule_mapper = self.unlock_schedule();
-        let unlock_schedule = if unlock_schedule_mapper.is_empty() {
-            UnlockSchedule::default()
-        } else {
-            unlock_schedule_mapper.get()
-        };
-
+        let unlock_schedule = self.get_user_unlock_schedule(address);
         let current_epoch = self.blockchain().get_block_epoch();
-
-        let mut claimable_percentage = 0u64;
-        for milestone in unlock_schedule.milestones.iter() {
-            if milestone.release_epoch <= current_epoch {
-                claimable_percentage += milestone.percentage;
-            } else {
-                break;
-            }
-        }
-
         let current_claimable_tokens =
-            &user_total_claimable_balance * claimable_percentage / MAX_PERCENTAGE;
+            unlock_schedule.compute_unlocked_amount(&user_total_claimable_balance, current_epoch);
 
         current_claimable_tokens - user_claimed_balance
     }
@@ -140,5 +357,23 @@ pub trait TokenReleaseModule:
 
     #[view(getUnlockSchedule)]
     #[storage_mapper("unlockSchedule")]
-    fn unlock_schedule(&self) -> SingleValueMapper<UnlockSchedule<Self::Api>>;
+    fn unlock_schedule(&self) -> SingleValueMapper<MilestonesUnlockSchedule<Self::Api>>;
+
+    #[view(getLinearUnlockSchedule)]
+    #[storage_mapper("linearUnlockSchedule")]
+    fn linear_unlock_schedule(&self) -> SingleValueMapper<LinearUnlockSchedule>;
+
+    #[view(getNamedUnlockSchedule)]
+    #[storage_mapper("namedUnlockSchedule")]
+    fn named_unlock_schedule(
+        &self,
+        schedule_name: &ManagedBuffer,
+    ) -> SingleValueMapper<UnlockSchedule<Self::Api>>;
+
+    #[view(getUserUnlockScheduleName)]
+    #[storage_mapper("userUnlockScheduleName")]
+    fn user_unlock_schedule_name(
+        &self,
+        address: &ManagedAddress,
+    ) -> SingleValueMapper<ManagedBuffer>;
 }
@@ -0,0 +1,152 @@
+dharitri_sc::imports!();
+dharitri_sc::derive_imports!();
+
+use launchpad_common::permissions::Role;
+
+use crate::token_release::UnlockSchedule;
+
+/// A position of amount `a` holds `a / (total_amount - unlocked_amount)`
+/// of the tokens that were still locked when the position was created
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct VestingPositionAttributes<M: ManagedTypeApi> {
+    pub unlock_schedule: UnlockSchedule<M>,
+    pub total_amount: BigUint<M>,
+    pub unlocked_amount: BigUint<M>,
+}
+
+#[dharitri_sc::module]
+pub trait VestingPositionModule:
+    launchpad_common::config::ConfigModule
+    + launchpad_common::launch_stage::LaunchStageModule
+    + launchpad_common::permissions::PermissionsModule
+    + crate::token_release::TokenReleaseModule
+    + crate::events::EventsModule
+    + dharitri_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    + dharitri_sc_modules::pause::PauseModule
+{
+    /// The number of decimals should match the launchpad token
+    #[payable("REWA")]
+    #[endpoint(issueVestingPositionToken)]
+    fn issue_vesting_position_token(
+        &self,
+        token_display_name: ManagedBuffer,
+        token_ticker: ManagedBuffer,
+        num_decimals: usize,
+    ) {
+        self.require_role(Role::Treasurer);
+
+        let issue_cost = self.call_value().rewa_value().clone_value();
+        self.vesting_position_token().issue_and_set_all_roles(
+            DcdtTokenType::Meta,
+            issue_cost,
+            token_display_name,
+            token_ticker,
+            num_decimals,
+            None,
+        );
+    }
+
+    /// When enabled, the tokens that are still locked at the first claim
+    /// are sent as a transferable vesting position instead
+    #[endpoint(setVestingPositionsEnabled)]
+    fn set_vesting_positions_enabled(&self, enabled: bool) {
+        self.require_role(Role::Treasurer);
+        self.require_add_tickets_period();
+        require!(
+            !enabled || !self.vesting_position_token().is_empty(),
+            "Vesting position token not issued"
+        );
+
+        self.vesting_positions_enabled().set(enabled);
+    }
+
+    fn try_create_vesting_position(&self, user: &ManagedAddress) {
+        if !self.vesting_positions_enabled().get() {
+            return;
+        }
+
+        let total_amount = self.user_total_claimable_balance(user).get();
+        let claimed_amount = self.user_claimed_balance(user).get();
+        if claimed_amount >= total_amount {
+            return;
+        }
+
+        let locked_amount = &total_amount - &claimed_amount;
+        let attributes = VestingPositionAttributes {
+            unlock_schedule: self.get_user_unlock_schedule(user),
+            total_amount: total_amount.clone(),
+            unlocked_amount: claimed_amount,
+        };
+        self.user_claimed_balance(user).set(total_amount);
+
+        let vesting_position =
+            self.vesting_position_token()
+                .nft_create_and_send(user, locked_amount, &attributes);
+
+        self.emit_create_vesting_position_event(vesting_position);
+    }
+
+    /// Any holder can redeem the portion of the position unlocked so far.
+    /// The still locked remainder is returned as a new position.
+    #[payable("*")]
+    #[endpoint(redeemVestingPosition)]
+    fn redeem_vesting_position(&self) {
+        self.require_not_paused();
+
+        let payment = self.call_value().single_dcdt();
+        let vesting_position_mapper = self.vesting_position_token();
+        vesting_position_mapper.require_same_token(&payment.token_identifier);
+
+        let attributes: VestingPositionAttributes<Self::Api> =
+            vesting_position_mapper.get_token_attributes(payment.token_nonce);
+        let current_epoch = self.blockchain().get_block_epoch();
+        let unlocked_amount = attributes
+            .unlock_schedule
+            .compute_unlocked_amount(&attributes.total_amount, current_epoch);
+        require!(
+            unlocked_amount > attributes.unlocked_amount,
+            "Nothing to redeem yet"
+        );
+
+        let locked_amount_at_creation = &attributes.total_amount - &attributes.unlocked_amount;
+        let redeemable_amount = &payment.amount * &(&unlocked_amount - &attributes.unlocked_amount)
+            / locked_amount_at_creation;
+
+        vesting_position_mapper.nft_burn(payment.token_nonce, &payment.amount);
+
+        let caller = self.blockchain().get_caller();
+        let remaining_amount = &payment.amount - &redeemable_amount;
+        if remaining_amount > 0 {
+            let new_attributes = VestingPositionAttributes {
+                unlock_schedule: attributes.unlock_schedule,
+                total_amount: attributes.total_amount,
+                unlocked_amount,
+            };
+            let _ = vesting_position_mapper.nft_create_and_send(
+                &caller,
+                remaining_amount,
+                &new_attributes,
+            );
+        }
+
+        if redeemable_amount > 0 {
+            let launchpad_token_id = self.launchpad_token_id().get();
+            self.send()
+                .direct_dcdt(&caller, &launchpad_token_id, 0, &redeemable_amount);
+
+            self.emit_redeem_vesting_position_event(DcdtTokenPayment::new(
+                launchpad_token_id,
+                0,
+                redeemable_amount,
+            ));
+        }
+    }
+
+    #[view(getVestingPositionToken)]
+    #[storage_mapper("vestingPositionToken")]
+    fn vesting_position_token(&self) -> NonFungibleTokenMapper<Self::Api>;
+
+    #[view(areVestingPositionsEnabled)]
+    #[storage_mapper("vestingPositionsEnabled")]
+    fn vesting_positions_enabled(&self) -> SingleValueMapper<bool>;
+}
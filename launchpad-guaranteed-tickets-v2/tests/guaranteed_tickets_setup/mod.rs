@@ -1,6 +1,6 @@
 use dharitri_sc::types::{
-    Address, RewaOrDcdtTokenIdentifier, MultiValueEncoded, MultiValueEncodedCounted,
-    OperationCompletionStatus,
+    Address, DcdtLocalRole, RewaOrDcdtTokenIdentifier, MultiValueEncoded,
+    MultiValueEncodedCounted, OperationCompletionStatus, OptionalValue,
 };
 
 use launchpad_common::{
@@ -11,6 +11,7 @@ use launchpad_common::{
     winner_selection::WinnerSelectionModule,
 };
 use launchpad_guaranteed_tickets_v2::token_release::TokenReleaseModule;
+use launchpad_guaranteed_tickets_v2::vesting_position::VestingPositionModule;
 use launchpad_guaranteed_tickets_v2::{
     guaranteed_tickets_init::GuaranteedTicketsInitModule, LaunchpadGuaranteedTickets,
 };
@@ -22,6 +23,7 @@ use dharitri_sc_scenario::{
 };
 
 pub static LAUNCHPAD_TOKEN_ID: &[u8] = b"LAUNCH-123456";
+pub static VESTING_POSITION_TOKEN_ID: &[u8] = b"VLAUNCH-123456";
 pub const LAUNCHPAD_TOKENS_PER_TICKET: u64 = 100;
 pub const CONFIRM_START_BLOCK: u64 = 5;
 pub const WINNER_SELECTION_START_BLOCK: u64 = 10;
@@ -87,6 +89,7 @@ where
                     CONFIRM_START_BLOCK,
                     WINNER_SELECTION_START_BLOCK,
                     CLAIM_START_BLOCK,
+                    OptionalValue::None,
                 );
             })
             .assert_ok();
@@ -260,6 +263,56 @@ where
         );
     }
 
+    pub fn set_linear_unlock_schedule(
+        &mut self,
+        cliff_epoch: u64,
+        start_epoch: u64,
+        end_epoch: u64,
+    ) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.owner_address,
+            &self.lp_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_linear_unlock_schedule(cliff_epoch, start_epoch, end_epoch);
+            },
+        )
+    }
+
+    pub fn enable_vesting_positions(&mut self) {
+        self.b_mock.set_dcdt_local_roles(
+            self.lp_wrapper.address_ref(),
+            VESTING_POSITION_TOKEN_ID,
+            &[DcdtLocalRole::NftCreate, DcdtLocalRole::NftBurn],
+        );
+
+        self.b_mock
+            .execute_tx(
+                &self.owner_address,
+                &self.lp_wrapper,
+                &rust_biguint!(0),
+                |sc| {
+                    sc.vesting_position_token()
+                        .set_token_id(managed_token_id!(VESTING_POSITION_TOKEN_ID));
+                    sc.set_vesting_positions_enabled(true);
+                },
+            )
+            .assert_ok();
+    }
+
+    pub fn redeem_vesting_position(&mut self, user: &Address, nonce: u64, amount: u64) -> TxResult {
+        self.b_mock.execute_dcdt_transfer(
+            user,
+            &self.lp_wrapper,
+            VESTING_POSITION_TOKEN_ID,
+            nonce,
+            &rust_biguint!(amount),
+            |sc| {
+                sc.redeem_vesting_position();
+            },
+        )
+    }
+
     pub fn pause_contract(&mut self) {
         let _ = self.b_mock.execute_tx(
             &self.owner_address,
@@ -4,29 +4,43 @@ mod guaranteed_tickets_setup;
 
 use guaranteed_tickets_setup::{
     LaunchpadSetup, CLAIM_START_BLOCK, CONFIRM_START_BLOCK, LAUNCHPAD_TOKENS_PER_TICKET,
-    LAUNCHPAD_TOKEN_ID, MAX_TIER_TICKETS, TICKET_COST, WINNER_SELECTION_START_BLOCK,
+    LAUNCHPAD_TOKEN_ID, MAX_TIER_TICKETS, TICKET_COST, VESTING_POSITION_TOKEN_ID,
+    WINNER_SELECTION_START_BLOCK,
 };
 use launchpad_common::{
     config::ConfigModule,
+    launch_stage::LaunchStageModule,
+    random::Hash,
     setup::SetupModule,
     tickets::{TicketsModule, WINNING_TICKET},
+    user_interactions::UserInteractionsModule,
     winner_selection::WinnerSelectionModule,
 };
 use launchpad_guaranteed_tickets_v2::{
     guaranteed_ticket_winners::{
         GuaranteedTicketWinnersModule, GuaranteedTicketsSelectionOperation,
     },
-    guaranteed_tickets_init::GuaranteedTicketsInitModule,
+    guaranteed_tickets_init::{GuaranteedTicketInfo, GuaranteedTicketsInitModule},
+    merkle_whitelist::{MerkleWhitelistModule, WhitelistLeaf},
     token_release::TokenReleaseModule,
     LaunchpadGuaranteedTickets,
 };
-use dharitri_sc::types::{
-    RewaOrDcdtTokenIdentifier, MultiValueEncoded, MultiValueEncodedCounted,
+use dharitri_sc::{
+    codec::Empty,
+    contract_base::ContractBase,
+    types::{
+        Address, ManagedBuffer, ManagedVec, MultiValueEncoded, MultiValueEncodedCounted,
+        RewaOrDcdtTokenIdentifier,
+    },
+};
+use dharitri_sc_scenario::{
+    managed_address, managed_biguint, managed_buffer, rust_biguint, DebugApi,
 };
-use dharitri_sc_scenario::{managed_address, managed_biguint, rust_biguint};
 
 use crate::guaranteed_tickets_setup::NR_WINNING_TICKETS;
 
+const SEED_REVEAL_DEADLINE_BLOCK: u64 = WINNER_SELECTION_START_BLOCK + 2;
+
 #[test]
 fn init_test() {
     let _ = LaunchpadSetup::new(
@@ -996,7 +1010,7 @@ fn blacklist_scenario_test() {
             |sc| {
                 let mut blacklist = MultiValueEncoded::new();
                 blacklist.push(managed_address!(&participants[4]));
-                sc.add_users_to_blacklist_endpoint(blacklist);
+                sc.add_users_to_blacklist_endpoint(0, blacklist);
             },
         )
         .assert_error(4, "Permission denied");
@@ -1033,7 +1047,7 @@ fn blacklist_scenario_test() {
             |sc| {
                 let mut blacklist = MultiValueEncoded::new();
                 blacklist.push(managed_address!(&second_new_participant));
-                sc.add_users_to_blacklist_endpoint(blacklist);
+                sc.add_users_to_blacklist_endpoint(0, blacklist);
             },
         )
         .assert_ok();
@@ -1121,7 +1135,7 @@ fn blacklist_scenario_test() {
             |sc| {
                 let mut blacklist = MultiValueEncoded::new();
                 blacklist.push(managed_address!(&second_new_participant));
-                sc.add_users_to_blacklist_endpoint(blacklist);
+                sc.add_users_to_blacklist_endpoint(0, blacklist);
             },
         )
         .assert_error(4, "May only modify blacklist before winner selection");
@@ -1234,7 +1248,7 @@ fn remove_from_blacklist_underflow_and_empty_storage_test() {
                 let mut blacklist = MultiValueEncoded::new();
                 blacklist.push(managed_address!(&participants[0]));
                 blacklist.push(managed_address!(&new_participant2));
-                sc.add_users_to_blacklist_endpoint(blacklist);
+                sc.add_users_to_blacklist_endpoint(0, blacklist);
             },
         )
         .assert_ok();
@@ -1639,6 +1653,294 @@ fn vesting_with_four_milestones_test() {
     );
 }
 
+#[test]
+fn linear_vesting_with_cliff_test() {
+    let nr_winning_tickets = 1;
+    let mut lp_setup = LaunchpadSetup::new(
+        nr_winning_tickets,
+        launchpad_guaranteed_tickets_v2::contract_obj,
+    );
+
+    // Cliff before the start epoch
+    lp_setup
+        .set_linear_unlock_schedule(0, 1, 4)
+        .assert_user_error("Invalid unlock schedule");
+
+    // Start epoch after the end epoch
+    lp_setup
+        .set_linear_unlock_schedule(4, 4, 2)
+        .assert_user_error("Invalid unlock schedule");
+
+    lp_setup.set_linear_unlock_schedule(2, 0, 4).assert_ok();
+    let participant = &lp_setup.participants[0].clone();
+
+    lp_setup.b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+    lp_setup.confirm(participant, 1).assert_ok();
+
+    lp_setup
+        .b_mock
+        .set_block_nonce(WINNER_SELECTION_START_BLOCK);
+    lp_setup.filter_tickets().assert_ok();
+    lp_setup.select_base_winners_mock(1).assert_ok();
+    lp_setup.distribute_tickets().assert_ok();
+
+    lp_setup.b_mock.set_block_nonce(CLAIM_START_BLOCK);
+
+    // Nothing is unlocked before the cliff
+    lp_setup.b_mock.set_block_epoch(1);
+    lp_setup.claim_user(participant).assert_ok();
+    lp_setup
+        .b_mock
+        .check_dcdt_balance(participant, LAUNCHPAD_TOKEN_ID, &rust_biguint!(0));
+
+    // At the cliff, the tokens vested since the start epoch are unlocked (50%)
+    lp_setup.b_mock.set_block_epoch(2);
+    lp_setup.claim_user(participant).assert_ok();
+    lp_setup.b_mock.check_dcdt_balance(
+        participant,
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(LAUNCHPAD_TOKENS_PER_TICKET / 2),
+    );
+
+    // 75% total
+    lp_setup.b_mock.set_block_epoch(3);
+    lp_setup.claim_user(participant).assert_ok();
+    lp_setup.b_mock.check_dcdt_balance(
+        participant,
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(LAUNCHPAD_TOKENS_PER_TICKET * 3 / 4),
+    );
+
+    // Everything is unlocked after the end epoch
+    lp_setup.b_mock.set_block_epoch(5);
+    lp_setup.claim_user(participant).assert_ok();
+    lp_setup.b_mock.check_dcdt_balance(
+        participant,
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(LAUNCHPAD_TOKENS_PER_TICKET),
+    );
+
+    lp_setup
+        .claim_user(participant)
+        .assert_error(4, "Already claimed all tokens");
+
+    lp_setup.b_mock.check_dcdt_balance(
+        lp_setup.lp_wrapper.address_ref(),
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(0),
+    );
+}
+
+#[test]
+fn named_unlock_schedule_per_user_test() {
+    let mut lp_setup = LaunchpadSetup::new(
+        NR_WINNING_TICKETS,
+        launchpad_guaranteed_tickets_v2::contract_obj,
+    );
+    lp_setup.set_unlock_schedule(vec![(0, 5000), (1, 5000)]);
+
+    let partner = lp_setup
+        .b_mock
+        .create_user_account(&rust_biguint!(TICKET_COST));
+
+    // The schedule must exist before it can be assigned
+    lp_setup
+        .b_mock
+        .execute_tx(
+            &lp_setup.owner_address,
+            &lp_setup.lp_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut args = MultiValueEncoded::new();
+                args.push(
+                    (
+                        managed_address!(&partner),
+                        1,
+                        MultiValueEncodedCounted::new(),
+                    )
+                        .into(),
+                );
+                sc.add_tickets_with_unlock_schedule_endpoint(managed_buffer!(b"partner"), args);
+            },
+        )
+        .assert_user_error("Unknown unlock schedule");
+
+    lp_setup
+        .b_mock
+        .execute_tx(
+            &lp_setup.owner_address,
+            &lp_setup.lp_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_named_linear_unlock_schedule(managed_buffer!(b"partner"), 2, 0, 4);
+
+                let mut args = MultiValueEncoded::new();
+                args.push(
+                    (
+                        managed_address!(&partner),
+                        1,
+                        MultiValueEncodedCounted::new(),
+                    )
+                        .into(),
+                );
+                sc.add_tickets_with_unlock_schedule_endpoint(managed_buffer!(b"partner"), args);
+
+                assert_eq!(
+                    sc.user_unlock_schedule_name(&managed_address!(&partner))
+                        .get(),
+                    managed_buffer!(b"partner")
+                );
+            },
+        )
+        .assert_ok();
+
+    let participant = lp_setup.participants[0].clone();
+
+    lp_setup.b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+    lp_setup.confirm(&participant, 1).assert_ok();
+    lp_setup.confirm(&partner, 1).assert_ok();
+
+    lp_setup
+        .b_mock
+        .set_block_nonce(WINNER_SELECTION_START_BLOCK);
+    lp_setup.filter_tickets().assert_ok();
+    lp_setup.select_base_winners_mock(1).assert_ok();
+    lp_setup.distribute_tickets().assert_ok();
+
+    lp_setup.b_mock.set_block_nonce(CLAIM_START_BLOCK);
+
+    // The participant follows the global schedule, the partner is still before the cliff
+    lp_setup.claim_user(&participant).assert_ok();
+    lp_setup.claim_user(&partner).assert_ok();
+    lp_setup.b_mock.check_dcdt_balance(
+        &participant,
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(LAUNCHPAD_TOKENS_PER_TICKET / 2),
+    );
+    lp_setup
+        .b_mock
+        .check_dcdt_balance(&partner, LAUNCHPAD_TOKEN_ID, &rust_biguint!(0));
+
+    lp_setup.b_mock.set_block_epoch(2);
+    lp_setup.claim_user(&participant).assert_ok();
+    lp_setup.claim_user(&partner).assert_ok();
+    lp_setup.b_mock.check_dcdt_balance(
+        &participant,
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(LAUNCHPAD_TOKENS_PER_TICKET),
+    );
+    lp_setup.b_mock.check_dcdt_balance(
+        &partner,
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(LAUNCHPAD_TOKENS_PER_TICKET / 2),
+    );
+
+    lp_setup.b_mock.set_block_epoch(4);
+    lp_setup.claim_user(&partner).assert_ok();
+    lp_setup.b_mock.check_dcdt_balance(
+        &partner,
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(LAUNCHPAD_TOKENS_PER_TICKET),
+    );
+}
+
+#[test]
+fn vesting_position_redeem_test() {
+    let nr_winning_tickets = 1;
+    let mut lp_setup = LaunchpadSetup::new(
+        nr_winning_tickets,
+        launchpad_guaranteed_tickets_v2::contract_obj,
+    );
+    lp_setup.set_linear_unlock_schedule(0, 0, 4).assert_ok();
+    lp_setup.enable_vesting_positions();
+    let participant = &lp_setup.participants[0].clone();
+
+    lp_setup.b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+    lp_setup.confirm(participant, 1).assert_ok();
+
+    lp_setup
+        .b_mock
+        .set_block_nonce(WINNER_SELECTION_START_BLOCK);
+    lp_setup.filter_tickets().assert_ok();
+    lp_setup.select_base_winners_mock(1).assert_ok();
+    lp_setup.distribute_tickets().assert_ok();
+
+    lp_setup.b_mock.set_block_nonce(CLAIM_START_BLOCK);
+
+    // Nothing is unlocked yet, so the whole allocation is sent as a vesting position
+    lp_setup.claim_user(participant).assert_ok();
+    lp_setup
+        .b_mock
+        .check_dcdt_balance(participant, LAUNCHPAD_TOKEN_ID, &rust_biguint!(0));
+    lp_setup.b_mock.check_nft_balance::<Empty>(
+        participant,
+        VESTING_POSITION_TOKEN_ID,
+        1,
+        &rust_biguint!(LAUNCHPAD_TOKENS_PER_TICKET),
+        None,
+    );
+
+    lp_setup
+        .claim_user(participant)
+        .assert_error(4, "Already claimed all tokens");
+    lp_setup
+        .redeem_vesting_position(participant, 1, LAUNCHPAD_TOKENS_PER_TICKET)
+        .assert_user_error("Nothing to redeem yet");
+
+    // Half of the position is unlocked, the rest is returned as a new position
+    lp_setup.b_mock.set_block_epoch(2);
+    lp_setup
+        .redeem_vesting_position(participant, 1, LAUNCHPAD_TOKENS_PER_TICKET)
+        .assert_ok();
+    lp_setup.b_mock.check_dcdt_balance(
+        participant,
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(LAUNCHPAD_TOKENS_PER_TICKET / 2),
+    );
+    lp_setup.b_mock.check_nft_balance::<Empty>(
+        participant,
+        VESTING_POSITION_TOKEN_ID,
+        1,
+        &rust_biguint!(0),
+        None,
+    );
+    lp_setup.b_mock.check_nft_balance::<Empty>(
+        participant,
+        VESTING_POSITION_TOKEN_ID,
+        2,
+        &rust_biguint!(LAUNCHPAD_TOKENS_PER_TICKET / 2),
+        None,
+    );
+
+    lp_setup
+        .redeem_vesting_position(participant, 2, LAUNCHPAD_TOKENS_PER_TICKET / 2)
+        .assert_user_error("Nothing to redeem yet");
+
+    // Everything is unlocked
+    lp_setup.b_mock.set_block_epoch(4);
+    lp_setup
+        .redeem_vesting_position(participant, 2, LAUNCHPAD_TOKENS_PER_TICKET / 2)
+        .assert_ok();
+    lp_setup.b_mock.check_dcdt_balance(
+        participant,
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(LAUNCHPAD_TOKENS_PER_TICKET),
+    );
+    lp_setup.b_mock.check_nft_balance::<Empty>(
+        participant,
+        VESTING_POSITION_TOKEN_ID,
+        2,
+        &rust_biguint!(0),
+        None,
+    );
+
+    lp_setup.b_mock.check_dcdt_balance(
+        lp_setup.lp_wrapper.address_ref(),
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(0),
+    );
+}
+
 #[test]
 fn unlock_milestones_wrong_step_and_order_test() {
     let mut lp_setup = LaunchpadSetup::new(
@@ -2267,3 +2569,476 @@ fn no_participants_test() {
         &rust_biguint!(0),
     );
 }
+
+#[test]
+fn commit_reveal_seed_test() {
+    let mut lp_setup = LaunchpadSetup::new(
+        NR_WINNING_TICKETS,
+        launchpad_guaranteed_tickets_v2::contract_obj,
+    );
+    let participants = lp_setup.participants.clone();
+
+    // The reveal deadline must leave time to reveal during winner selection
+    lp_setup
+        .b_mock
+        .execute_tx(
+            &lp_setup.owner_address,
+            &lp_setup.lp_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let secret_hash = sc.crypto().sha256(&managed_buffer!(b"secret"));
+                sc.commit_seed_hash(secret_hash, WINNER_SELECTION_START_BLOCK);
+            },
+        )
+        .assert_error(4, "Reveal deadline must be during winner selection");
+
+    // Commit the hash of the secret before the confirmation period
+    lp_setup
+        .b_mock
+        .execute_tx(
+            &lp_setup.owner_address,
+            &lp_setup.lp_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let secret_hash = sc.crypto().sha256(&managed_buffer!(b"secret"));
+                sc.commit_seed_hash(secret_hash, SEED_REVEAL_DEADLINE_BLOCK);
+            },
+        )
+        .assert_ok();
+
+    lp_setup.b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+
+    // Commitment may not be changed after the add tickets period
+    lp_setup
+        .b_mock
+        .execute_tx(
+            &lp_setup.owner_address,
+            &lp_setup.lp_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let secret_hash = sc.crypto().sha256(&managed_buffer!(b"other secret"));
+                sc.commit_seed_hash(secret_hash, SEED_REVEAL_DEADLINE_BLOCK);
+            },
+        )
+        .assert_error(4, "Add tickets period has passed");
+
+    for (i, p) in participants.iter().enumerate() {
+        lp_setup.confirm(p, i + 1).assert_ok();
+    }
+
+    lp_setup
+        .b_mock
+        .set_block_nonce(WINNER_SELECTION_START_BLOCK);
+    lp_setup.filter_tickets().assert_ok();
+
+    // Selection may not start before the seed is revealed
+    lp_setup
+        .select_winners()
+        .assert_error(4, "Must reveal seed first");
+
+    lp_setup
+        .b_mock
+        .execute_tx(
+            &lp_setup.owner_address,
+            &lp_setup.lp_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.reveal_seed(managed_buffer!(b"wrong secret"));
+            },
+        )
+        .assert_error(4, "Revealed seed does not match commitment");
+
+    let block_random_seed = [7u8; 48];
+    lp_setup.b_mock.set_block_random_seed(&block_random_seed);
+    lp_setup
+        .b_mock
+        .execute_tx(
+            &lp_setup.owner_address,
+            &lp_setup.lp_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.reveal_seed(managed_buffer!(b"secret"));
+            },
+        )
+        .assert_ok();
+
+    lp_setup.select_winners().assert_ok();
+
+    lp_setup
+        .b_mock
+        .execute_query(&lp_setup.lp_wrapper, |sc| {
+            let mut seed_input = managed_buffer!(b"secret");
+            seed_input.append_bytes(&block_random_seed);
+            let expected_seed = sc.crypto().sha256(&seed_input);
+
+            assert_eq!(sc.revealed_seed().get(), managed_buffer!(b"secret"));
+            assert_eq!(
+                &sc.winner_selection_seed().get(),
+                expected_seed.as_managed_buffer()
+            );
+            assert!(sc.flags().get().were_winners_selected);
+        })
+        .assert_ok();
+}
+
+#[test]
+fn commit_reveal_seed_deadline_cancel_test() {
+    let mut lp_setup = LaunchpadSetup::new(
+        NR_WINNING_TICKETS,
+        launchpad_guaranteed_tickets_v2::contract_obj,
+    );
+    let participants = lp_setup.participants.clone();
+
+    lp_setup
+        .b_mock
+        .execute_tx(
+            &lp_setup.owner_address,
+            &lp_setup.lp_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let secret_hash = sc.crypto().sha256(&managed_buffer!(b"secret"));
+                sc.commit_seed_hash(secret_hash, SEED_REVEAL_DEADLINE_BLOCK);
+            },
+        )
+        .assert_ok();
+
+    lp_setup.b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+    for (i, p) in participants.iter().enumerate() {
+        lp_setup.confirm(p, i + 1).assert_ok();
+    }
+
+    lp_setup
+        .b_mock
+        .set_block_nonce(WINNER_SELECTION_START_BLOCK);
+    lp_setup.filter_tickets().assert_ok();
+
+    // The secret is never revealed
+    lp_setup
+        .select_winners()
+        .assert_error(4, "Must reveal seed first");
+
+    lp_setup.b_mock.set_block_nonce(SEED_REVEAL_DEADLINE_BLOCK);
+    lp_setup
+        .b_mock
+        .execute_tx(
+            &lp_setup.owner_address,
+            &lp_setup.lp_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.reveal_seed(managed_buffer!(b"secret"));
+            },
+        )
+        .assert_error(4, "Reveal deadline has passed");
+
+    // Past the deadline, selecting winners cancels the launchpad instead of drawing
+    lp_setup.select_winners().assert_ok();
+
+    lp_setup
+        .b_mock
+        .execute_query(&lp_setup.lp_wrapper, |sc| {
+            assert!(sc.launchpad_cancelled().get());
+            assert!(sc.winner_selection_seed().is_empty());
+            assert!(!sc.flags().get().were_winners_selected);
+        })
+        .assert_ok();
+
+    lp_setup
+        .select_winners()
+        .assert_error(4, "Not in winner selection period");
+
+    // Users get their ticket payments back
+    lp_setup
+        .b_mock
+        .execute_tx(
+            &participants[0],
+            &lp_setup.lp_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.claim_refund_endpoint();
+            },
+        )
+        .assert_ok();
+    lp_setup.b_mock.check_rewa_balance(
+        &participants[0],
+        &rust_biguint!(TICKET_COST * MAX_TIER_TICKETS as u64),
+    );
+}
+
+fn whitelist_leaf_hash(
+    sc: &launchpad_guaranteed_tickets_v2::ContractObj<DebugApi>,
+    address: &Address,
+    total_tickets_allowance: usize,
+    guaranteed_tickets: &[(usize, usize)],
+) -> Hash<DebugApi> {
+    let mut guaranteed_tickets_info = ManagedVec::new();
+    for &(guaranteed_tickets, min_confirmed_tickets) in guaranteed_tickets {
+        guaranteed_tickets_info.push(GuaranteedTicketInfo {
+            guaranteed_tickets,
+            min_confirmed_tickets,
+        });
+    }
+
+    sc.compute_whitelist_leaf_hash(WhitelistLeaf {
+        address: managed_address!(address),
+        total_tickets_allowance,
+        guaranteed_tickets_info,
+    })
+}
+
+#[test]
+fn merkle_whitelist_claim_test() {
+    let mut lp_setup = LaunchpadSetup::new(
+        NR_WINNING_TICKETS,
+        launchpad_guaranteed_tickets_v2::contract_obj,
+    );
+    let first_user = lp_setup
+        .b_mock
+        .create_user_account(&rust_biguint!(TICKET_COST * 2));
+    let second_user = lp_setup.b_mock.create_user_account(&rust_biguint!(0));
+
+    // Two leaves: first user gets 2 tickets with 1 guaranteed, second user gets 1 ticket
+    lp_setup
+        .b_mock
+        .execute_tx(
+            &lp_setup.owner_address,
+            &lp_setup.lp_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let first_leaf = whitelist_leaf_hash(&sc, &first_user, 2, &[(1, 2)]);
+                let second_leaf = whitelist_leaf_hash(&sc, &second_user, 1, &[]);
+
+                let mut pair = ManagedBuffer::new();
+                if first_leaf.to_byte_array() <= second_leaf.to_byte_array() {
+                    pair.append(first_leaf.as_managed_buffer());
+                    pair.append(second_leaf.as_managed_buffer());
+                } else {
+                    pair.append(second_leaf.as_managed_buffer());
+                    pair.append(first_leaf.as_managed_buffer());
+                }
+
+                sc.set_whitelist_merkle_root(sc.crypto().sha256(&pair));
+            },
+        )
+        .assert_ok();
+
+    // Allocations may only be claimed during the confirmation period
+    lp_setup
+        .b_mock
+        .execute_tx(&first_user, &lp_setup.lp_wrapper, &rust_biguint!(0), |sc| {
+            let mut proof = MultiValueEncoded::new();
+            proof.push(whitelist_leaf_hash(&sc, &second_user, 1, &[]));
+            sc.claim_ticket_allocation(
+                2,
+                ManagedVec::from_single_item(GuaranteedTicketInfo {
+                    guaranteed_tickets: 1,
+                    min_confirmed_tickets: 2,
+                }),
+                proof,
+            );
+        })
+        .assert_user_error("Not in confirmation period");
+
+    lp_setup.b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+
+    // Claiming a larger allowance than the one in the tree fails
+    lp_setup
+        .b_mock
+        .execute_tx(&first_user, &lp_setup.lp_wrapper, &rust_biguint!(0), |sc| {
+            let mut proof = MultiValueEncoded::new();
+            proof.push(whitelist_leaf_hash(&sc, &second_user, 1, &[]));
+            sc.claim_ticket_allocation(
+                3,
+                ManagedVec::from_single_item(GuaranteedTicketInfo {
+                    guaranteed_tickets: 1,
+                    min_confirmed_tickets: 2,
+                }),
+                proof,
+            );
+        })
+        .assert_user_error("Invalid Merkle proof");
+
+    lp_setup
+        .b_mock
+        .execute_tx(&first_user, &lp_setup.lp_wrapper, &rust_biguint!(0), |sc| {
+            let mut proof = MultiValueEncoded::new();
+            proof.push(whitelist_leaf_hash(&sc, &second_user, 1, &[]));
+            sc.claim_ticket_allocation(
+                2,
+                ManagedVec::from_single_item(GuaranteedTicketInfo {
+                    guaranteed_tickets: 1,
+                    min_confirmed_tickets: 2,
+                }),
+                proof,
+            );
+
+            let user_ticket_status = sc.user_ticket_status(&managed_address!(&first_user)).get();
+            assert_eq!(user_ticket_status.total_tickets_allowance, 2);
+            assert_eq!(user_ticket_status.guaranteed_tickets_info.len(), 1);
+            assert_eq!(
+                sc.get_total_number_of_tickets_for_address(&managed_address!(&first_user)),
+                2
+            );
+            assert!(sc
+                .users_with_guaranteed_ticket()
+                .contains(&managed_address!(&first_user)));
+            assert_eq!(sc.nr_winning_tickets().get(), NR_WINNING_TICKETS - 2);
+            assert_eq!(sc.total_guaranteed_tickets().get(), 2);
+        })
+        .assert_ok();
+
+    // The same allocation may not be claimed twice
+    lp_setup
+        .b_mock
+        .execute_tx(&first_user, &lp_setup.lp_wrapper, &rust_biguint!(0), |sc| {
+            let mut proof = MultiValueEncoded::new();
+            proof.push(whitelist_leaf_hash(&sc, &second_user, 1, &[]));
+            sc.claim_ticket_allocation(
+                2,
+                ManagedVec::from_single_item(GuaranteedTicketInfo {
+                    guaranteed_tickets: 1,
+                    min_confirmed_tickets: 2,
+                }),
+                proof,
+            );
+        })
+        .assert_user_error("Duplicate entry for user");
+
+    lp_setup.confirm(&first_user, 2).assert_ok();
+}
+
+#[test]
+fn unconfirm_tickets_test() {
+    let mut lp_setup = LaunchpadSetup::new(
+        NR_WINNING_TICKETS,
+        launchpad_guaranteed_tickets_v2::contract_obj,
+    );
+    lp_setup.b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+    let participants = lp_setup.participants.clone();
+    let base_user_balance = rust_biguint!(TICKET_COST * MAX_TIER_TICKETS as u64);
+
+    lp_setup.confirm(&participants[2], 3).assert_ok();
+    lp_setup
+        .b_mock
+        .execute_query(&lp_setup.lp_wrapper, |sc| {
+            assert_eq!(
+                sc.user_guaranteed_tickets(managed_address!(&participants[2])),
+                1
+            );
+        })
+        .assert_ok();
+
+    lp_setup
+        .b_mock
+        .execute_tx(
+            &participants[2],
+            &lp_setup.lp_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.unconfirm_tickets(1);
+            },
+        )
+        .assert_ok();
+    lp_setup
+        .b_mock
+        .check_rewa_balance(&participants[2], &(&base_user_balance - TICKET_COST * 2));
+
+    // no longer meets the minimum confirmed tickets for the guaranteed ticket
+    lp_setup
+        .b_mock
+        .execute_query(&lp_setup.lp_wrapper, |sc| {
+            assert_eq!(
+                sc.nr_confirmed_tickets(&managed_address!(&participants[2]))
+                    .get(),
+                2
+            );
+            assert_eq!(
+                sc.user_guaranteed_tickets(managed_address!(&participants[2])),
+                0
+            );
+        })
+        .assert_ok();
+
+    lp_setup
+        .b_mock
+        .execute_tx(
+            &participants[2],
+            &lp_setup.lp_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.unconfirm_tickets(3);
+            },
+        )
+        .assert_user_error("Trying to unconfirm too many tickets");
+
+    lp_setup
+        .b_mock
+        .set_block_nonce(WINNER_SELECTION_START_BLOCK);
+    lp_setup
+        .b_mock
+        .execute_tx(
+            &participants[2],
+            &lp_setup.lp_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.unconfirm_tickets(1);
+            },
+        )
+        .assert_user_error("Not in confirmation period");
+}
+
+#[test]
+fn unlock_schedule_storage_layout_test() {
+    let mut lp_setup = LaunchpadSetup::new(
+        NR_WINNING_TICKETS,
+        launchpad_guaranteed_tickets_v2::contract_obj,
+    );
+
+    // contracts deployed before linear schedules store the number of milestones,
+    // followed by the (release_epoch, percentage) pairs
+    let mut legacy_layout = Vec::new();
+    legacy_layout.extend_from_slice(&2u32.to_be_bytes());
+    for (release_epoch, percentage) in [(1u64, 4_000u64), (2, 6_000)] {
+        legacy_layout.extend_from_slice(&release_epoch.to_be_bytes());
+        legacy_layout.extend_from_slice(&percentage.to_be_bytes());
+    }
+
+    lp_setup.set_unlock_schedule(vec![(1, 4_000), (2, 6_000)]);
+    lp_setup
+        .b_mock
+        .execute_query(&lp_setup.lp_wrapper, |sc| {
+            let stored_schedule: ManagedBuffer<DebugApi> = sc.storage_raw().read("unlockSchedule");
+            assert_eq!(stored_schedule, managed_buffer!(legacy_layout.as_slice()));
+        })
+        .assert_ok();
+
+    // a schedule stored by the previous version is still decoded after upgrade
+    lp_setup.set_linear_unlock_schedule(0, 0, 4).assert_ok();
+    lp_setup
+        .b_mock
+        .execute_tx(
+            &lp_setup.owner_address,
+            &lp_setup.lp_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.linear_unlock_schedule().clear();
+                sc.storage_raw()
+                    .write("unlockSchedule", &managed_buffer!(legacy_layout.as_slice()));
+
+                sc.upgrade(MultiValueEncoded::new());
+
+                assert_eq!(sc.unlock_schedule().get().milestones.len(), 2);
+
+                let unlock_schedule = sc.get_global_unlock_schedule();
+                let total_amount = managed_biguint!(LAUNCHPAD_TOKENS_PER_TICKET);
+                assert_eq!(
+                    unlock_schedule.compute_unlocked_amount(&total_amount, 1),
+                    managed_biguint!(LAUNCHPAD_TOKENS_PER_TICKET * 4 / 10)
+                );
+                assert_eq!(
+                    unlock_schedule.compute_unlocked_amount(&total_amount, 2),
+                    total_amount
+                );
+            },
+        )
+        .assert_ok();
+}
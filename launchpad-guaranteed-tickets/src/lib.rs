@@ -3,7 +3,13 @@
 dharitri_sc::imports!();
 dharitri_sc::derive_imports!();
 
-use launchpad_common::{config::TokenAmountPair, launch_stage::Flags, tickets::WINNING_TICKET};
+use launchpad_common::{
+    config::{TimelineMode, TokenAmountPair},
+    launch_stage::Flags,
+    permissions::Role,
+    tickets::WINNING_TICKET,
+    timelock::AdminAction,
+};
 
 use crate::guaranteed_ticket_winners::GuaranteedTicketsSelectionOperation;
 
@@ -23,9 +29,13 @@ pub trait LaunchpadGuaranteedTickets:
     + launchpad_common::winner_selection::WinnerSelectionModule
     + launchpad_common::ongoing_operation::OngoingOperationModule
     + launchpad_common::permissions::PermissionsModule
+    + launchpad_common::address_groups::AddressGroupsModule
+    + launchpad_common::timelock::TimelockModule
     + launchpad_common::blacklist::BlacklistModule
     + launchpad_common::token_send::TokenSendModule
+    + launchpad_common::price_oracle::PriceOracleModule
     + launchpad_common::user_interactions::UserInteractionsModule
+    + launchpad_common::cancellation::CancellationModule
     + launchpad_common::common_events::CommonEventsModule
     + guaranteed_tickets_init::GuaranteedTicketsInitModule
     + guaranteed_ticket_winners::GuaranteedTicketWinnersModule
@@ -45,6 +55,7 @@ pub trait LaunchpadGuaranteedTickets:
         winner_selection_start_block: u64,
         claim_start_block: u64,
         min_confirmed_for_guaranteed_ticket: usize,
+        opt_timeline_mode: OptionalValue<TimelineMode>,
     ) {
         self.init_base(
             launchpad_token_id,
@@ -56,6 +67,7 @@ pub trait LaunchpadGuaranteedTickets:
             winner_selection_start_block,
             claim_start_block,
             Flags::default(),
+            opt_timeline_mode.into_option().unwrap_or_default(),
         );
 
         require!(
@@ -66,22 +78,27 @@ pub trait LaunchpadGuaranteedTickets:
             .set(min_confirmed_for_guaranteed_ticket);
     }
 
+    /// `blacklisted_users` lists the users blacklisted by the previous version
     #[upgrade]
-    fn upgrade(&self) {}
+    fn upgrade(&self, blacklisted_users: MultiValueEncoded<ManagedAddress>) {
+        self.migrate_support_address();
+        self.migrate_blacklisted_users(blacklisted_users);
+    }
 
-    #[only_owner]
     #[endpoint(addTickets)]
     fn add_tickets_endpoint(
         &self,
         address_number_pairs: MultiValueEncoded<MultiValue4<ManagedAddress, usize, usize, bool>>,
     ) {
+        self.require_role(Role::TicketManager);
         self.add_tickets_with_guaranteed_winners(address_number_pairs);
     }
 
-    #[only_owner]
     #[payable("*")]
     #[endpoint(depositLaunchpadTokens)]
     fn deposit_launchpad_tokens_endpoint(&self) {
+        self.require_role(Role::Treasurer);
+
         let base_selection_winning_tickets = self.nr_winning_tickets().get();
         let reserved_tickets = self.total_guaranteed_tickets().get();
         let total_tickets = base_selection_winning_tickets + reserved_tickets;
@@ -90,9 +107,13 @@ pub trait LaunchpadGuaranteedTickets:
     }
 
     #[endpoint(addUsersToBlacklist)]
-    fn add_users_to_blacklist_endpoint(&self, users_list: MultiValueEncoded<ManagedAddress>) {
+    fn add_users_to_blacklist_endpoint(
+        &self,
+        reason: u32,
+        users_list: MultiValueEncoded<ManagedAddress>,
+    ) {
         let users_vec = users_list.to_vec();
-        self.add_users_to_blacklist(&users_vec);
+        self.add_users_to_blacklist(&users_vec, reason);
         self.clear_users_with_guaranteed_ticket_after_blacklist(&users_vec);
     }
 
@@ -203,6 +224,7 @@ pub trait LaunchpadGuaranteedTickets:
 
         let nr_tickets_to_refund = nr_confirmed_tickets - nr_redeemable_tickets;
         self.refund_ticket_payment(caller, nr_tickets_to_refund);
+        self.settle_ticket_payment(caller);
 
         if nr_redeemable_tickets > 0 {
             let tokens_per_winning_ticket = self.launchpad_tokens_per_winning_ticket().get();
@@ -214,22 +236,20 @@ pub trait LaunchpadGuaranteedTickets:
         }
     }
 
-    #[only_owner]
+    #[endpoint(claimRefund)]
+    fn claim_refund_endpoint(&self) {
+        self.claim_cancellation_refund();
+    }
+
     #[endpoint(claimTicketPayment)]
     fn claim_ticket_payment_endpoint(&self) {
+        self.require_role(Role::Treasurer);
         self.require_claim_period();
 
-        let owner = self.blockchain().get_caller();
+        let owner = self.blockchain().get_owner_address();
 
         let ticket_price: TokenAmountPair<Self::Api> = self.ticket_price().get();
-        let ticket_payment_mapper = self.claimable_ticket_payment();
-        let claimable_ticket_payment = ticket_payment_mapper.get();
-        if claimable_ticket_payment > 0 {
-            ticket_payment_mapper.clear();
-
-            self.send()
-                .direct(&owner, &ticket_price.token_id, 0, &claimable_ticket_payment);
-        }
+        let claimable_ticket_payment = self.send_claimable_ticket_payment(&owner);
 
         let deposited_tokens_mapper = self.total_launchpad_tokens_deposited();
         let total_launchpad_tokens_deposited = deposited_tokens_mapper.take();
@@ -269,4 +289,21 @@ pub trait LaunchpadGuaranteedTickets:
         )
             .into()
     }
+
+    #[endpoint(proposeAdminAction)]
+    fn propose_admin_action_endpoint(&self, action: AdminAction<Self::Api>) -> u64 {
+        require!(
+            action.is_common() || matches!(action, AdminAction::SetUnlockSchedule { .. }),
+            "Action not supported"
+        );
+        self.propose_admin_action(action)
+    }
+
+    #[endpoint(executeAdminAction)]
+    fn execute_admin_action_endpoint(&self, action_id: u64) {
+        match self.take_executable_admin_action(action_id) {
+            AdminAction::SetUnlockSchedule { args } => self.set_unlock_schedule_from_args(args),
+            action => self.execute_admin_action(action),
+        }
+    }
 }
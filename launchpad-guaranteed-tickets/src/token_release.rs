@@ -1,7 +1,7 @@
 dharitri_sc::imports!();
 dharitri_sc::derive_imports!();
 
-use launchpad_common::config;
+use launchpad_common::{config, permissions, permissions::Role};
 
 pub const MAX_PERCENTAGE: u64 = 10_000;
 
@@ -33,8 +33,7 @@ impl UnlockSchedule {
 }
 
 #[dharitri_sc::module]
-pub trait TokenReleaseModule: config::ConfigModule {
-    #[only_owner]
+pub trait TokenReleaseModule: config::ConfigModule + permissions::PermissionsModule {
     #[endpoint(setUnlockSchedule)]
     fn set_unlock_schedule(
         &self,
@@ -44,6 +43,8 @@ pub trait TokenReleaseModule: config::ConfigModule {
         vesting_release_percentage: u64,
         vesting_release_period: u64,
     ) {
+        self.require_role(Role::Treasurer);
+
         let configuration = self.configuration();
         require!(
             !configuration.is_empty(),
@@ -51,12 +52,50 @@ pub trait TokenReleaseModule: config::ConfigModule {
         );
         let confirmation_period_start_block = configuration.get().confirmation_period_start_block;
 
-        let current_block = self.blockchain().get_block_nonce();
-        let current_round = self.blockchain().get_block_round();
+        let current_point = self.get_current_timeline_point();
         require!(
-            current_block < confirmation_period_start_block || self.unlock_schedule().is_empty(),
+            current_point < confirmation_period_start_block || self.unlock_schedule().is_empty(),
             "Can't change the unlock schedule"
         );
+
+        self.try_set_unlock_schedule(
+            claim_start_round,
+            initial_release_percentage,
+            vesting_release_times,
+            vesting_release_percentage,
+            vesting_release_period,
+        );
+    }
+
+    /// Queued changes may still be applied after the confirmation period starts,
+    /// as long as the claim period has not started yet
+    fn set_unlock_schedule_from_args(&self, args: ManagedVec<u64>) {
+        require!(args.len() == 5, "Invalid unlock schedule arguments");
+
+        let claim_start_block = self.configuration().get().claim_start_block;
+        require!(
+            self.get_current_timeline_point() < claim_start_block,
+            "Claim period has started"
+        );
+
+        self.try_set_unlock_schedule(
+            args.get(0),
+            args.get(1),
+            args.get(2),
+            args.get(3),
+            args.get(4),
+        );
+    }
+
+    fn try_set_unlock_schedule(
+        &self,
+        claim_start_round: u64,
+        initial_release_percentage: u64,
+        vesting_release_times: u64,
+        vesting_release_percentage: u64,
+        vesting_release_period: u64,
+    ) {
+        let current_round = self.blockchain().get_block_round();
         require!(
             claim_start_round >= current_round,
             "Wrong claim start round"
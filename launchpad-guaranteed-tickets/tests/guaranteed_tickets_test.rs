@@ -7,7 +7,9 @@ use guaranteed_tickets_setup::{
     LAUNCHPAD_TOKEN_ID, MAX_TIER_TICKETS, TICKET_COST, WINNER_SELECTION_START_BLOCK,
 };
 use launchpad_common::{
+    blacklist::{BlacklistModule, LEGACY_BLACKLIST_REASON},
     config::ConfigModule,
+    permissions::{PermissionsModule, Role},
     setup::SetupModule,
     tickets::{TicketsModule, WINNING_TICKET},
     winner_selection::WinnerSelectionModule,
@@ -912,7 +914,7 @@ fn blacklist_scenario_test() {
             |sc| {
                 let mut blacklist = MultiValueEncoded::new();
                 blacklist.push(managed_address!(&participants[4]));
-                sc.add_users_to_blacklist_endpoint(blacklist);
+                sc.add_users_to_blacklist_endpoint(0, blacklist);
             },
         )
         .assert_error(4, "Permission denied");
@@ -949,7 +951,7 @@ fn blacklist_scenario_test() {
             |sc| {
                 let mut blacklist = MultiValueEncoded::new();
                 blacklist.push(managed_address!(&second_new_participant));
-                sc.add_users_to_blacklist_endpoint(blacklist);
+                sc.add_users_to_blacklist_endpoint(0, blacklist);
             },
         )
         .assert_ok();
@@ -1037,7 +1039,7 @@ fn blacklist_scenario_test() {
             |sc| {
                 let mut blacklist = MultiValueEncoded::new();
                 blacklist.push(managed_address!(&second_new_participant));
-                sc.add_users_to_blacklist_endpoint(blacklist);
+                sc.add_users_to_blacklist_endpoint(0, blacklist);
             },
         )
         .assert_error(4, "May only modify blacklist before winner selection");
@@ -1228,3 +1230,97 @@ fn confirm_less_tickets_than_total_available_with_vesting_scenario_test() {
         &rust_biguint!(0),
     );
 }
+
+#[test]
+fn upgrade_support_address_migration_test() {
+    let mut lp_setup = LaunchpadSetup::new(
+        NR_WINNING_TICKETS,
+        launchpad_guaranteed_tickets::contract_obj,
+    );
+    let support_address = lp_setup.participants[0].clone();
+
+    lp_setup
+        .b_mock
+        .execute_tx(
+            &lp_setup.owner_address,
+            &lp_setup.lp_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.legacy_support_address()
+                    .set(managed_address!(&support_address));
+
+                sc.upgrade(MultiValueEncoded::new());
+
+                assert!(sc.legacy_support_address().is_empty());
+                assert!(sc
+                    .role_members(Role::Compliance)
+                    .contains(&managed_address!(&support_address)));
+                assert!(!sc.has_role(Role::Treasurer, managed_address!(&support_address)));
+            },
+        )
+        .assert_ok();
+}
+
+#[test]
+fn upgrade_blacklist_migration_test() {
+    let mut lp_setup = LaunchpadSetup::new(
+        NR_WINNING_TICKETS,
+        launchpad_guaranteed_tickets::contract_obj,
+    );
+    let owner = lp_setup.owner_address.clone();
+    let blacklisted_user = lp_setup.participants[0].clone();
+    let other_user = lp_setup.participants[1].clone();
+
+    // users blacklisted by the previous version only have the legacy set entry
+    lp_setup
+        .b_mock
+        .execute_tx(
+            &lp_setup.owner_address,
+            &lp_setup.lp_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.blacklist().add(&managed_address!(&blacklisted_user));
+            },
+        )
+        .assert_ok();
+
+    lp_setup
+        .b_mock
+        .execute_tx(
+            &lp_setup.owner_address,
+            &lp_setup.lp_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut blacklisted_users = MultiValueEncoded::new();
+                blacklisted_users.push(managed_address!(&other_user));
+                sc.upgrade(blacklisted_users);
+            },
+        )
+        .assert_user_error("User is not blacklisted");
+
+    lp_setup
+        .b_mock
+        .execute_tx(
+            &lp_setup.owner_address,
+            &lp_setup.lp_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                // upgrading again with the same users doesn't duplicate them
+                for _ in 0..2 {
+                    let mut blacklisted_users = MultiValueEncoded::new();
+                    blacklisted_users.push(managed_address!(&blacklisted_user));
+                    sc.upgrade(blacklisted_users);
+                }
+
+                assert_eq!(sc.get_nr_blacklisted_users(), 1);
+
+                let entry = sc
+                    .blacklist_entry(&managed_address!(&blacklisted_user))
+                    .get();
+                assert_eq!(entry.reason, LEGACY_BLACKLIST_REASON);
+                assert_eq!(entry.blacklisted_by, managed_address!(&owner));
+                assert!(sc.is_user_blacklisted(&managed_address!(&blacklisted_user)));
+            },
+        )
+        .assert_ok();
+}
@@ -6,9 +6,9 @@
 
 // Init:                                 1
 // Upgrade:                              1
-// Endpoints:                           41
+// Endpoints:                           99
 // Async Callback (empty):               1
-// Total number of exported functions:  44
+// Total number of exported functions: 102
 
 #![no_std]
 
@@ -26,33 +26,91 @@ dharitri_sc_wasm_adapter::endpoints! {
         removeGuaranteedUsersFromBlacklist => remove_guaranteed_users_from_blacklist_endpoint
         distributeGuaranteedTickets => distribute_guaranteed_tickets_endpoint
         claimLaunchpadTokens => claim_launchpad_tokens_endpoint
+        claimRefund => claim_refund_endpoint
         claimTicketPayment => claim_ticket_payment_endpoint
         getUserTicketsStatus => user_tickets_status
+        proposeAdminAction => propose_admin_action_endpoint
+        executeAdminAction => execute_admin_action_endpoint
         getLaunchStageFlags => flags
+        isLaunchpadCancelled => launchpad_cancelled
+        getCurrentTicketPrice => get_current_ticket_price
         getConfiguration => configuration
+        getTimelineMode => timeline_mode
+        getClaimDeadline => claim_deadline
+        wereWaitlistWinnersSelected => waitlist_winners_selected
+        getNrWinningTicketsToRedraw => nr_winning_tickets_to_redraw
+        getDutchAuction => dutch_auction
+        getClearingTicketPrice => clearing_ticket_price
         getLaunchpadTokenId => launchpad_token_id
         getLaunchpadTokensPerWinningTicket => launchpad_tokens_per_winning_ticket
         getTicketPrice => ticket_price
+        getAdditionalTicketPrices => additional_ticket_prices
+        getReferenceTicketPrice => reference_ticket_price
+        getTicketTiers => ticket_tiers
+        getUserTicketTier => user_ticket_tier
+        getTierNumberOfWinningTickets => tier_nr_winning_tickets
         getNumberOfWinningTickets => nr_winning_tickets
+        isProRataAllocation => pro_rata_allocation
+        getSoftCap => soft_cap
         getTotalLaunchpadTokensDeposited => total_launchpad_tokens_deposited
+        getUserTicketPayments => user_ticket_payments
         setTicketPrice => set_ticket_price
+        addTicketPaymentToken => add_ticket_payment_token
+        removeTicketPaymentToken => remove_ticket_payment_token
         setLaunchpadTokensPerWinningTicket => set_launchpad_tokens_per_winning_ticket
+        setSoftCap => set_soft_cap
         setConfirmationPeriodStartBlock => set_confirmation_period_start_block
         setWinnerSelectionStartBlock => set_winner_selection_start_block
         setClaimStartBlock => set_claim_start_block
+        setConfirmationPeriodStartTimestamp => set_confirmation_period_start_timestamp
+        setWinnerSelectionStartTimestamp => set_winner_selection_start_timestamp
+        setClaimStartTimestamp => set_claim_start_timestamp
         getTicketRangeForAddress => get_ticket_range_for_address
         getTotalNumberOfTicketsForAddress => get_total_number_of_tickets_for_address
+        getTicketWeight => get_ticket_weight
         getTotalNumberOfTickets => last_ticket_id
         getNumberOfConfirmedTicketsForAddress => nr_confirmed_tickets
+        getTotalConfirmedTickets => total_confirmed_tickets
+        isWeightedLottery => weighted_lottery
+        commitSeedHash => commit_seed_hash
+        revealSeed => reveal_seed
         filterTickets => filter_tickets
         selectWinners => select_winners
         getNumberOfWinningTicketsForAddress => get_number_of_winning_tickets_for_address
         getWinningTicketIdsForAddress => get_winning_ticket_ids_for_address
-        setSupportAddress => add_support_address
-        getSupportAddress => support_address
+        getSeedCommitment => seed_commitment
+        getRevealedSeed => revealed_seed
+        getSeedRevealDeadline => seed_reveal_deadline
+        getWinnerSelectionSeed => winner_selection_seed
+        grantRole => grant_role
+        revokeRole => revoke_role
+        hasRole => has_role
+        getRoleMembers => role_members
+        getAddressGroups => address_groups
+        getAddressGroupMembers => address_group_members
+        getUserAddressGroup => user_address_group
+        getGroupNumberOfConfirmedTickets => group_nr_confirmed_tickets
+        getGroupNumberOfWinningTickets => group_nr_winning_tickets
+        setAdminActionDelay => set_admin_action_delay
+        cancelAdminAction => cancel_admin_action
+        getAdminActionDelay => admin_action_delay
+        getPendingAdminActions => pending_admin_actions
         isUserBlacklisted => is_user_blacklisted
+        getBlacklistedUsers => get_blacklisted_users
+        getNrBlacklistedUsers => get_nr_blacklisted_users
+        getBlacklistEntry => blacklist_entry
+        setReferenceTicketPrice => set_reference_ticket_price
+        setOraclePaymentToken => set_oracle_payment_token
+        removeOraclePaymentToken => remove_oracle_payment_token
+        getPriceFeedAddress => price_feed_address
+        getMaxOraclePriceAge => max_oracle_price_age
+        getOraclePaymentToken => oracle_payment_token
         confirmTickets => confirm_tickets
+        unconfirmTickets => unconfirm_tickets
+        settleUnclaimedTicketPayments => settle_unclaimed_ticket_payments
         hasUserClaimedTokens => has_user_claimed
+        cancelLaunchpad => cancel_launchpad
+        withdrawLaunchpadTokens => withdraw_launchpad_tokens
         setUnlockSchedule => set_unlock_schedule
         getClaimableTokens => compute_claimable_tokens
         getUserTotalClaimableBalance => user_total_claimable_balance
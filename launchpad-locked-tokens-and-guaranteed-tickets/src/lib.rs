@@ -3,7 +3,9 @@
 dharitri_sc::imports!();
 dharitri_sc::derive_imports!();
 
-use launchpad_common::launch_stage::Flags;
+use launchpad_common::{
+    config::TimelineMode, launch_stage::Flags, permissions::Role, timelock::AdminAction,
+};
 use launchpad_guaranteed_tickets::guaranteed_ticket_winners::GuaranteedTicketsSelectionOperation;
 
 #[dharitri_sc::contract]
@@ -16,9 +18,13 @@ pub trait LaunchpadLockedTokensAndGuaranteedTickets:
     + launchpad_common::winner_selection::WinnerSelectionModule
     + launchpad_common::ongoing_operation::OngoingOperationModule
     + launchpad_common::permissions::PermissionsModule
+    + launchpad_common::address_groups::AddressGroupsModule
+    + launchpad_common::timelock::TimelockModule
     + launchpad_common::blacklist::BlacklistModule
     + launchpad_common::token_send::TokenSendModule
+    + launchpad_common::price_oracle::PriceOracleModule
     + launchpad_common::user_interactions::UserInteractionsModule
+    + launchpad_common::cancellation::CancellationModule
     + launchpad_common::common_events::CommonEventsModule
     + launchpad_guaranteed_tickets::guaranteed_tickets_init::GuaranteedTicketsInitModule
     + launchpad_guaranteed_tickets::guaranteed_ticket_winners::GuaranteedTicketWinnersModule
@@ -41,6 +47,7 @@ pub trait LaunchpadLockedTokensAndGuaranteedTickets:
         launchpad_tokens_lock_percentage: u32,
         launchpad_tokens_unlock_epoch: u64,
         simple_lock_sc_address: ManagedAddress,
+        opt_timeline_mode: OptionalValue<TimelineMode>,
     ) {
         self.init_base(
             launchpad_token_id,
@@ -52,6 +59,7 @@ pub trait LaunchpadLockedTokensAndGuaranteedTickets:
             winner_selection_start_block,
             claim_start_block,
             Flags::default(),
+            opt_timeline_mode.into_option().unwrap_or_default(),
         );
 
         require!(
@@ -66,19 +74,54 @@ pub trait LaunchpadLockedTokensAndGuaranteedTickets:
         self.try_set_simple_lock_sc_address(simple_lock_sc_address);
     }
 
-    #[only_owner]
     #[endpoint(addTickets)]
     fn add_tickets_endpoint(
         &self,
         address_number_pairs: MultiValueEncoded<MultiValue4<ManagedAddress, usize, usize, bool>>,
     ) {
+        self.require_role(Role::TicketManager);
         self.add_tickets_with_guaranteed_winners(address_number_pairs);
     }
 
-    #[only_owner]
+    /// Each tranche is a pair of lock percentage and unlock epoch
+    #[endpoint(setLaunchpadTokensLockTranches)]
+    fn set_launchpad_tokens_lock_tranches_endpoint(
+        &self,
+        tranches: MultiValueEncoded<MultiValue2<u32, u64>>,
+    ) {
+        self.require_role(Role::Treasurer);
+        self.require_add_tickets_period();
+        self.try_set_launchpad_tokens_lock_tranches(tranches);
+    }
+
+    /// Fails once lock tranches are set
+    #[endpoint(setLaunchpadTokensLockPercentage)]
+    fn set_launchpad_tokens_lock_percentage_endpoint(&self, lock_percentage: u32) {
+        self.require_role(Role::Treasurer);
+        self.require_add_tickets_period();
+        self.try_set_launchpad_tokens_lock_percentage(lock_percentage);
+    }
+
+    /// Fails once lock tranches are set
+    #[endpoint(setLaunchpadTokensUnlockEpoch)]
+    fn set_launchpad_tokens_unlock_epoch_endpoint(&self, unlock_epoch: u64) {
+        self.require_role(Role::Treasurer);
+        self.require_add_tickets_period();
+        self.try_set_launchpad_tokens_unlock_epoch(unlock_epoch);
+    }
+
+    #[endpoint(setSimpleLockScAddress)]
+    fn set_simple_lock_sc_address_endpoint(&self, sc_address: ManagedAddress) {
+        self.require_role(Role::Treasurer);
+        self.require_add_tickets_period();
+        self.try_set_simple_lock_sc_address(sc_address);
+    }
+
     #[payable("*")]
     #[endpoint(depositLaunchpadTokens)]
     fn deposit_launchpad_tokens_endpoint(&self) {
+        self.require_role(Role::Treasurer);
+
         let base_selection_winning_tickets = self.nr_winning_tickets().get();
         let reserved_tickets = self.users_with_guaranteed_ticket().len();
         let total_tickets = base_selection_winning_tickets + reserved_tickets;
@@ -87,12 +130,26 @@ pub trait LaunchpadLockedTokensAndGuaranteedTickets:
     }
 
     #[endpoint(addUsersToBlacklist)]
-    fn add_users_to_blacklist_endpoint(&self, users_list: MultiValueEncoded<ManagedAddress>) {
+    fn add_users_to_blacklist_endpoint(
+        &self,
+        reason: u32,
+        users_list: MultiValueEncoded<ManagedAddress>,
+    ) {
         let users_vec = users_list.to_vec();
-        self.add_users_to_blacklist(&users_vec);
+        self.add_users_to_blacklist(&users_vec, reason);
         self.clear_users_with_guaranteed_ticket_after_blacklist(&users_vec);
     }
 
+    #[endpoint(removeGuaranteedUsersFromBlacklist)]
+    fn remove_guaranteed_users_from_blacklist_endpoint(
+        &self,
+        users_list: MultiValueEncoded<ManagedAddress>,
+    ) {
+        let users_vec = users_list.to_vec();
+        self.remove_users_from_blacklist(users_list);
+        self.remove_guaranteed_tickets_from_blacklist(&users_vec);
+    }
+
     #[endpoint(distributeGuaranteedTickets)]
     fn distribute_guaranteed_tickets_endpoint(&self) -> OperationCompletionStatus {
         self.require_winner_selection_period();
@@ -146,9 +203,50 @@ pub trait LaunchpadLockedTokensAndGuaranteedTickets:
         self.claim_launchpad_tokens(Self::send_locked_launchpad_tokens);
     }
 
-    #[only_owner]
+    #[endpoint(claimRefund)]
+    fn claim_refund_endpoint(&self) {
+        self.claim_cancellation_refund();
+    }
+
     #[endpoint(claimTicketPayment)]
     fn claim_ticket_payment_endpoint(&self) {
+        self.require_role(Role::Treasurer);
         self.claim_ticket_payment();
     }
+
+    #[endpoint(proposeAdminAction)]
+    fn propose_admin_action_endpoint(&self, action: AdminAction<Self::Api>) -> u64 {
+        require!(
+            action.is_common()
+                || matches!(
+                    action,
+                    AdminAction::SetLaunchpadTokensLockPercentage { .. }
+                        | AdminAction::SetLaunchpadTokensUnlockEpoch { .. }
+                        | AdminAction::SetSimpleLockScAddress { .. }
+                ),
+            "Action not supported"
+        );
+        self.propose_admin_action(action)
+    }
+
+    /// Queued lock parameter changes may still be applied after the confirmation period starts,
+    /// as long as the claim period has not started yet
+    #[endpoint(executeAdminAction)]
+    fn execute_admin_action_endpoint(&self, action_id: u64) {
+        match self.take_executable_admin_action(action_id) {
+            AdminAction::SetLaunchpadTokensLockPercentage { lock_percentage } => {
+                self.require_before_claim_period();
+                self.try_set_launchpad_tokens_lock_percentage(lock_percentage);
+            }
+            AdminAction::SetLaunchpadTokensUnlockEpoch { unlock_epoch } => {
+                self.require_before_claim_period();
+                self.try_set_launchpad_tokens_unlock_epoch(unlock_epoch);
+            }
+            AdminAction::SetSimpleLockScAddress { sc_address } => {
+                self.require_before_claim_period();
+                self.try_set_simple_lock_sc_address(sc_address);
+            }
+            action => self.execute_admin_action(action),
+        }
+    }
 }
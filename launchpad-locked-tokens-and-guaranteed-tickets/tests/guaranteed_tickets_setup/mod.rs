@@ -9,6 +9,7 @@ use launchpad_guaranteed_tickets::guaranteed_tickets_init::GuaranteedTicketsInit
 use launchpad_locked_tokens_and_guaranteed_tickets::LaunchpadLockedTokensAndGuaranteedTickets;
 use dharitri_sc::types::{
     Address, RewaOrDcdtTokenIdentifier, DcdtLocalRole, MultiValueEncoded, OperationCompletionStatus,
+    OptionalValue,
 };
 use dharitri_sc_scenario::{
     managed_address, managed_biguint, managed_token_id, rust_biguint,
@@ -106,6 +107,7 @@ where
                     LOCK_PERCENTAGE,
                     UNLOCK_EPOCH,
                     managed_address!(simple_lock_sc.address_ref()),
+                    OptionalValue::None,
                 );
             })
             .assert_ok();
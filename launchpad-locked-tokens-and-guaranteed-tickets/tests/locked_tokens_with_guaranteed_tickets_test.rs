@@ -5,7 +5,7 @@ mod guaranteed_tickets_setup;
 use guaranteed_tickets_setup::{
     simple_lock_mock::LockedTokenAttributes, LaunchpadSetup, CLAIM_START_BLOCK,
     CONFIRM_START_BLOCK, LAUNCHPAD_TOKENS_PER_TICKET, LAUNCHPAD_TOKEN_ID, LOCKED_TOKEN_ID,
-    MAX_TIER_TICKETS, TICKET_COST, UNLOCK_EPOCH, WINNER_SELECTION_START_BLOCK,
+    LOCK_PERCENTAGE, MAX_TIER_TICKETS, TICKET_COST, UNLOCK_EPOCH, WINNER_SELECTION_START_BLOCK,
 };
 use launchpad_common::{
     config::ConfigModule,
@@ -18,6 +18,7 @@ use launchpad_guaranteed_tickets::{
         GuaranteedTicketWinnersModule, GuaranteedTicketsSelectionOperation,
     },
 };
+use launchpad_locked_tokens::locked_launchpad_token_send::LockedLaunchpadTokenSend;
 use launchpad_locked_tokens_and_guaranteed_tickets::LaunchpadLockedTokensAndGuaranteedTickets;
 use dharitri_sc::types::MultiValueEncoded;
 use dharitri_sc_scenario::{managed_address, managed_token_id_wrapped, rust_biguint, DebugApi};
@@ -354,3 +355,39 @@ fn combined_scenario_test() {
         )
         .assert_ok();
 }
+
+#[test]
+fn lock_tranches_test() {
+    let mut lp_setup =
+        LaunchpadSetup::new(launchpad_locked_tokens_and_guaranteed_tickets::contract_obj);
+
+    lp_setup.b_mock.set_block_nonce(CONFIRM_START_BLOCK - 1);
+    lp_setup
+        .b_mock
+        .execute_tx(
+            &lp_setup.owner_address,
+            &lp_setup.lp_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut tranches = MultiValueEncoded::new();
+                tranches.push((2_500u32, UNLOCK_EPOCH).into());
+                tranches.push((2_500u32, UNLOCK_EPOCH * 2).into());
+                sc.set_launchpad_tokens_lock_tranches_endpoint(tranches);
+
+                assert_eq!(sc.get_launchpad_tokens_lock_tranches().len(), 2);
+            },
+        )
+        .assert_ok();
+
+    lp_setup
+        .b_mock
+        .execute_tx(
+            &lp_setup.owner_address,
+            &lp_setup.lp_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_launchpad_tokens_lock_percentage_endpoint(LOCK_PERCENTAGE);
+            },
+        )
+        .assert_user_error("Lock tranches are set");
+}
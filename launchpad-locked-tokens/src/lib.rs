@@ -3,7 +3,9 @@
 dharitri_sc::imports!();
 dharitri_sc::derive_imports!();
 
-use launchpad_common::{launch_stage::Flags, *};
+use launchpad_common::{
+    config::TimelineMode, launch_stage::Flags, permissions::Role, timelock::AdminAction, *,
+};
 
 pub mod locked_launchpad_token_send;
 
@@ -17,9 +19,15 @@ pub trait LaunchpadLockedTokens:
     + winner_selection::WinnerSelectionModule
     + ongoing_operation::OngoingOperationModule
     + permissions::PermissionsModule
+    + address_groups::AddressGroupsModule
+    + timelock::TimelockModule
     + blacklist::BlacklistModule
     + token_send::TokenSendModule
+    + price_oracle::PriceOracleModule
     + user_interactions::UserInteractionsModule
+    + cancellation::CancellationModule
+    + pro_rata::ProRataModule
+    + registration::RegistrationModule
     + locked_launchpad_token_send::LockedLaunchpadTokenSend
     + common_events::CommonEventsModule
     + dharitri_sc_modules::pause::PauseModule
@@ -39,6 +47,7 @@ pub trait LaunchpadLockedTokens:
         launchpad_tokens_lock_percentage: u32,
         launchpad_tokens_unlock_epoch: u64,
         simple_lock_sc_address: ManagedAddress,
+        opt_timeline_mode: OptionalValue<TimelineMode>,
     ) {
         let flags = Flags {
             has_winner_selection_process_started: false,
@@ -56,6 +65,7 @@ pub trait LaunchpadLockedTokens:
             winner_selection_start_block,
             claim_start_block,
             flags,
+            opt_timeline_mode.into_option().unwrap_or_default(),
         );
 
         self.try_set_launchpad_tokens_lock_percentage(launchpad_tokens_lock_percentage);
@@ -63,36 +73,207 @@ pub trait LaunchpadLockedTokens:
         self.try_set_simple_lock_sc_address(simple_lock_sc_address);
     }
 
-    #[only_owner]
     #[endpoint(addTickets)]
     fn add_tickets_endpoint(
         &self,
         address_number_pairs: MultiValueEncoded<MultiValue2<ManagedAddress, usize>>,
     ) {
+        self.require_role(Role::TicketManager);
         self.add_tickets(address_number_pairs);
     }
 
-    #[only_owner]
+    #[endpoint(addTicketTier)]
+    fn add_ticket_tier_endpoint(
+        &self,
+        ticket_price: BigUint,
+        launchpad_tokens_per_ticket: BigUint,
+    ) -> usize {
+        self.require_role(Role::TicketManager);
+        self.add_ticket_tier(ticket_price, launchpad_tokens_per_ticket)
+    }
+
+    #[endpoint(addTicketsWithTier)]
+    fn add_tickets_with_tier_endpoint(
+        &self,
+        address_number_tier_triples: MultiValueEncoded<MultiValue3<ManagedAddress, usize, usize>>,
+    ) {
+        self.require_role(Role::TicketManager);
+        self.add_tickets_with_tier(address_number_tier_triples);
+    }
+
+    #[endpoint(setDutchAuction)]
+    fn set_dutch_auction_endpoint(
+        &self,
+        start_price: BigUint,
+        floor_price: BigUint,
+        start_point: u64,
+        end_point: u64,
+    ) {
+        self.require_role(Role::TicketManager);
+        self.set_dutch_auction(start_price, floor_price, start_point, end_point);
+    }
+
+    #[endpoint(setTicketWeights)]
+    fn set_ticket_weights_endpoint(
+        &self,
+        address_weight_pairs: MultiValueEncoded<MultiValue2<ManagedAddress, u64>>,
+    ) {
+        self.require_role(Role::TicketManager);
+        self.set_ticket_weights(address_weight_pairs);
+    }
+
+    #[endpoint(addAddressGroup)]
+    fn add_address_group_endpoint(
+        &self,
+        max_confirmed_tickets: usize,
+        max_winning_tickets: usize,
+        addresses: MultiValueEncoded<ManagedAddress>,
+    ) -> usize {
+        self.add_address_group(max_confirmed_tickets, max_winning_tickets, addresses)
+    }
+
+    #[endpoint(setClaimDeadline)]
+    fn set_claim_deadline_endpoint(&self, claim_deadline: u64) {
+        self.require_role(Role::Operator);
+        self.set_claim_deadline(claim_deadline);
+    }
+
+    #[endpoint(selectWaitlistWinners)]
+    fn select_waitlist_winners_endpoint(&self) -> OperationCompletionStatus {
+        self.select_waitlist_winners()
+    }
+
+    /// Each tranche is a pair of lock percentage and unlock epoch
+    #[endpoint(setLaunchpadTokensLockTranches)]
+    fn set_launchpad_tokens_lock_tranches_endpoint(
+        &self,
+        tranches: MultiValueEncoded<MultiValue2<u32, u64>>,
+    ) {
+        self.require_role(Role::Treasurer);
+        self.require_add_tickets_period();
+        self.try_set_launchpad_tokens_lock_tranches(tranches);
+    }
+
+    /// Fails once lock tranches are set
+    #[endpoint(setLaunchpadTokensLockPercentage)]
+    fn set_launchpad_tokens_lock_percentage_endpoint(&self, lock_percentage: u32) {
+        self.require_role(Role::Treasurer);
+        self.require_add_tickets_period();
+        self.try_set_launchpad_tokens_lock_percentage(lock_percentage);
+    }
+
+    /// Fails once lock tranches are set
+    #[endpoint(setLaunchpadTokensUnlockEpoch)]
+    fn set_launchpad_tokens_unlock_epoch_endpoint(&self, unlock_epoch: u64) {
+        self.require_role(Role::Treasurer);
+        self.require_add_tickets_period();
+        self.try_set_launchpad_tokens_unlock_epoch(unlock_epoch);
+    }
+
+    #[endpoint(setSimpleLockScAddress)]
+    fn set_simple_lock_sc_address_endpoint(&self, sc_address: ManagedAddress) {
+        self.require_role(Role::Treasurer);
+        self.require_add_tickets_period();
+        self.try_set_simple_lock_sc_address(sc_address);
+    }
+
     #[payable("*")]
     #[endpoint(depositLaunchpadTokens)]
     fn deposit_launchpad_tokens_endpoint(&self) {
+        self.require_role(Role::Treasurer);
+
         let nr_winning_tickets = self.nr_winning_tickets().get();
         self.deposit_launchpad_tokens(nr_winning_tickets);
     }
 
     #[endpoint(claimLaunchpadTokens)]
     fn claim_launchpad_tokens_endpoint(&self) {
-        self.claim_launchpad_tokens(Self::send_locked_launchpad_tokens);
+        if self.pro_rata_allocation().get() {
+            self.claim_pro_rata_launchpad_tokens(Self::send_locked_launchpad_tokens);
+        } else {
+            self.claim_launchpad_tokens(Self::send_locked_launchpad_tokens);
+        }
+    }
+
+    #[endpoint(claimRefund)]
+    fn claim_refund_endpoint(&self) {
+        self.claim_cancellation_refund();
     }
 
-    #[only_owner]
     #[endpoint(claimTicketPayment)]
     fn claim_ticket_payment_endpoint(&self) {
-        self.claim_ticket_payment();
+        self.require_role(Role::Treasurer);
+
+        if self.pro_rata_allocation().get() {
+            self.claim_pro_rata_ticket_payment();
+        } else {
+            self.claim_ticket_payment();
+        }
     }
 
     #[endpoint(addUsersToBlacklist)]
-    fn add_users_to_blacklist_endpoint(&self, users_list: MultiValueEncoded<ManagedAddress>) {
-        self.add_users_to_blacklist(&users_list.to_vec());
+    fn add_users_to_blacklist_endpoint(
+        &self,
+        reason: u32,
+        users_list: MultiValueEncoded<ManagedAddress>,
+    ) {
+        self.add_users_to_blacklist(&users_list.to_vec(), reason);
+    }
+
+    #[endpoint(removeUsersFromBlacklist)]
+    fn remove_users_from_blacklist_endpoint(&self, users_list: MultiValueEncoded<ManagedAddress>) {
+        self.remove_users_from_blacklist(users_list);
+    }
+
+    /// Only available in the base launchpad and the launchpad with locked tokens,
+    /// the other variants select part of their winners in additional steps
+    #[endpoint(addWinnersToBlacklist)]
+    fn add_winners_to_blacklist_endpoint(
+        &self,
+        reason: u32,
+        users_list: MultiValueEncoded<ManagedAddress>,
+    ) {
+        self.add_winners_to_blacklist(&users_list.to_vec(), reason);
+    }
+
+    #[endpoint(redrawWinners)]
+    fn redraw_winners_endpoint(&self) -> OperationCompletionStatus {
+        self.redraw_winners()
+    }
+
+    #[endpoint(proposeAdminAction)]
+    fn propose_admin_action_endpoint(&self, action: AdminAction<Self::Api>) -> u64 {
+        require!(
+            action.is_common()
+                || matches!(
+                    action,
+                    AdminAction::SetLaunchpadTokensLockPercentage { .. }
+                        | AdminAction::SetLaunchpadTokensUnlockEpoch { .. }
+                        | AdminAction::SetSimpleLockScAddress { .. }
+                ),
+            "Action not supported"
+        );
+        self.propose_admin_action(action)
+    }
+
+    /// Queued lock parameter changes may still be applied after the confirmation period starts,
+    /// as long as the claim period has not started yet
+    #[endpoint(executeAdminAction)]
+    fn execute_admin_action_endpoint(&self, action_id: u64) {
+        match self.take_executable_admin_action(action_id) {
+            AdminAction::SetLaunchpadTokensLockPercentage { lock_percentage } => {
+                self.require_before_claim_period();
+                self.try_set_launchpad_tokens_lock_percentage(lock_percentage);
+            }
+            AdminAction::SetLaunchpadTokensUnlockEpoch { unlock_epoch } => {
+                self.require_before_claim_period();
+                self.try_set_launchpad_tokens_unlock_epoch(unlock_epoch);
+            }
+            AdminAction::SetSimpleLockScAddress { sc_address } => {
+                self.require_before_claim_period();
+                self.try_set_simple_lock_sc_address(sc_address);
+            }
+            action => self.execute_admin_action(action),
+        }
     }
 }
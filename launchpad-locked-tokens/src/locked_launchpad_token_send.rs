@@ -1,6 +1,14 @@
 dharitri_sc::imports!();
+dharitri_sc::derive_imports!();
 
 const MAX_PERCENTAGE: u32 = 10_000; // 100%
+const MAX_LOCK_TRANCHES: usize = 10;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, ManagedVecItem)]
+pub struct LockTranche {
+    pub percentage: u32,
+    pub unlock_epoch: u64,
+}
 
 pub mod simple_lock_proxy {
     dharitri_sc::imports!();
@@ -16,6 +24,10 @@ pub mod simple_lock_proxy {
 #[dharitri_sc::module]
 pub trait LockedLaunchpadTokenSend {
     fn try_set_launchpad_tokens_lock_percentage(&self, lock_percentage: u32) {
+        require!(
+            self.launchpad_tokens_lock_tranches().is_empty(),
+            "Lock tranches are set"
+        );
         require!(
             lock_percentage > 0 && lock_percentage <= MAX_PERCENTAGE,
             "Invalid lock percentage"
@@ -25,12 +37,63 @@ pub trait LockedLaunchpadTokenSend {
     }
 
     fn try_set_launchpad_tokens_unlock_epoch(&self, unlock_epoch: u64) {
+        require!(
+            self.launchpad_tokens_lock_tranches().is_empty(),
+            "Lock tranches are set"
+        );
+
         let current_epoch = self.blockchain().get_block_epoch();
         require!(unlock_epoch > current_epoch, "Invalid unlock epoch");
 
         self.launchpad_tokens_unlock_epoch().set(unlock_epoch);
     }
 
+    /// Overrides the single lock percentage and unlock epoch set at init.
+    /// Tranches must be ordered by unlock epoch.
+    fn try_set_launchpad_tokens_lock_tranches(
+        &self,
+        tranches: MultiValueEncoded<MultiValue2<u32, u64>>,
+    ) {
+        require!(
+            !tranches.is_empty() && tranches.len() <= MAX_LOCK_TRANCHES,
+            "Invalid number of lock tranches"
+        );
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        let mut total_percentage = 0u32;
+        let mut last_unlock_epoch = current_epoch;
+        let mut lock_tranches = ManagedVec::new();
+        for tranche in tranches {
+            let (percentage, unlock_epoch) = tranche.into_tuple();
+            require!(
+                percentage > 0 && percentage <= MAX_PERCENTAGE - total_percentage,
+                "Invalid lock percentage"
+            );
+            require!(unlock_epoch > last_unlock_epoch, "Invalid unlock epoch");
+
+            total_percentage += percentage;
+            last_unlock_epoch = unlock_epoch;
+            lock_tranches.push(LockTranche {
+                percentage,
+                unlock_epoch,
+            });
+        }
+
+        self.launchpad_tokens_lock_tranches().set(&lock_tranches);
+    }
+
+    fn get_launchpad_tokens_lock_tranches(&self) -> ManagedVec<LockTranche> {
+        let lock_tranches_mapper = self.launchpad_tokens_lock_tranches();
+        if !lock_tranches_mapper.is_empty() {
+            return lock_tranches_mapper.get();
+        }
+
+        ManagedVec::from_single_item(LockTranche {
+            percentage: self.launchpad_tokens_lock_percentage().get(),
+            unlock_epoch: self.launchpad_tokens_unlock_epoch().get(),
+        })
+    }
+
     fn try_set_simple_lock_sc_address(&self, sc_address: ManagedAddress) {
         require!(
             !sc_address.is_zero() && self.blockchain().is_smart_contract(&sc_address),
@@ -47,25 +110,29 @@ pub trait LockedLaunchpadTokenSend {
     ) {
         let mut unlocked_amount = launchpad_tokens.amount.clone();
 
-        let unlock_epoch = self.launchpad_tokens_unlock_epoch().get();
         let current_epoch = self.blockchain().get_block_epoch();
-        if current_epoch < unlock_epoch {
-            let lock_percentage = self.launchpad_tokens_lock_percentage().get();
-            let lock_amount = &launchpad_tokens.amount * lock_percentage / MAX_PERCENTAGE;
-            if lock_amount > 0 {
-                unlocked_amount -= &lock_amount;
-
-                let sc_address = self.simple_lock_sc_address().get();
-                let _: IgnoreValue = self
-                    .simple_lock_proxy_builder(sc_address)
-                    .lock_tokens(unlock_epoch, dest_address.clone())
-                    .with_dcdt_transfer((
-                        launchpad_tokens.token_identifier.clone(),
-                        launchpad_tokens.token_nonce,
-                        lock_amount,
-                    ))
-                    .execute_on_dest_context();
+        for tranche in self.get_launchpad_tokens_lock_tranches().iter() {
+            if current_epoch >= tranche.unlock_epoch {
+                continue;
+            }
+
+            let lock_amount = &launchpad_tokens.amount * tranche.percentage / MAX_PERCENTAGE;
+            if lock_amount == 0 {
+                continue;
             }
+
+            unlocked_amount -= &lock_amount;
+
+            let sc_address = self.simple_lock_sc_address().get();
+            let _: IgnoreValue = self
+                .simple_lock_proxy_builder(sc_address)
+                .lock_tokens(tranche.unlock_epoch, dest_address.clone())
+                .with_dcdt_transfer((
+                    launchpad_tokens.token_identifier.clone(),
+                    launchpad_tokens.token_nonce,
+                    lock_amount,
+                ))
+                .execute_on_dest_context();
         }
 
         if unlocked_amount > 0 {
@@ -86,6 +153,11 @@ pub trait LockedLaunchpadTokenSend {
     #[storage_mapper("launchpadTokensUnlockEpoch")]
     fn launchpad_tokens_unlock_epoch(&self) -> SingleValueMapper<u64>;
 
+    #[view(getLaunchpadTokensLockTranches)]
+    #[storage_mapper("launchpadTokensLockTranches")]
+    fn launchpad_tokens_lock_tranches(&self) -> SingleValueMapper<ManagedVec<LockTranche>>;
+
+    #[view(getSimpleLockScAddress)]
     #[storage_mapper("simpleLockScAddress")]
     fn simple_lock_sc_address(&self) -> SingleValueMapper<ManagedAddress>;
 
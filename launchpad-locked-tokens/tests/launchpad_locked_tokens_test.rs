@@ -1,17 +1,22 @@
 dharitri_sc::derive_imports!();
 
 use launchpad_common::{
-    config::ConfigModule, user_interactions::UserInteractionsModule,
+    config::ConfigModule,
+    tickets::TicketsModule,
+    timelock::{AdminAction, TimelockModule},
+    user_interactions::UserInteractionsModule,
     winner_selection::WinnerSelectionModule,
 };
-use launchpad_locked_tokens::LaunchpadLockedTokens;
+use launchpad_locked_tokens::{
+    locked_launchpad_token_send::LockedLaunchpadTokenSend, LaunchpadLockedTokens,
+};
 use dharitri_sc::{
     api::ManagedTypeApi,
     codec::{TopDecode, TopEncode},
     contract_base::{CallableContract, ContractBase},
     types::{
         RewaOrDcdtTokenIdentifier, DcdtLocalRole, DcdtTokenPayment, ManagedAddress,
-        MultiValueEncoded,
+        MultiValueEncoded, OptionalValue,
     },
 };
 use dharitri_sc_scenario::{
@@ -32,6 +37,7 @@ const WINNER_SELECTION_START_BLOCK: u64 = 20;
 const CLAIM_START_BLOCK: u64 = 30;
 const LOCK_PERCENTAGE: u32 = 5_000; // 50%
 const UNLOCK_EPOCH: u64 = 10;
+const ADMIN_ACTION_DELAY: u64 = 5;
 
 #[test]
 fn launchpad_with_locked_tokens_out_test() {
@@ -65,6 +71,7 @@ fn launchpad_with_locked_tokens_out_test() {
                 LOCK_PERCENTAGE,
                 UNLOCK_EPOCH,
                 managed_address!(simple_lock_sc.address_ref()),
+                OptionalValue::None,
             );
 
             let mut tickets = MultiValueEncoded::new();
@@ -135,6 +142,334 @@ fn launchpad_with_locked_tokens_out_test() {
     );
 }
 
+#[test]
+#[test]
+fn launchpad_lock_tranches_test() {
+    let _ = DebugApi::dummy();
+    let mut b_mock = BlockchainStateWrapper::new();
+    let rust_zero = rust_biguint!(0);
+
+    let owner = b_mock.create_user_account(&rust_zero);
+    let user = b_mock.create_user_account(&rust_biguint!(TICKET_PRICE));
+    let simple_lock_sc =
+        b_mock.create_sc_account(&rust_zero, None, SimpleLockMock::new, "simple lock wasm");
+    let lp_sc = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        launchpad_locked_tokens::contract_obj,
+        "launchpad wasm",
+    );
+
+    // setup
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.init(
+                managed_token_id!(LAUNCHPAD_TOKEN_ID),
+                managed_biguint!(LAUNCHPAD_TOKENS_PER_TICKET),
+                managed_rewa_token_id!(),
+                managed_biguint!(TICKET_PRICE),
+                NR_WINNING_TICKETS,
+                CONFIRM_START_BLOCK,
+                WINNER_SELECTION_START_BLOCK,
+                CLAIM_START_BLOCK,
+                LOCK_PERCENTAGE,
+                UNLOCK_EPOCH,
+                managed_address!(simple_lock_sc.address_ref()),
+                OptionalValue::None,
+            );
+
+            let mut tickets = MultiValueEncoded::new();
+            tickets.push((managed_address!(&user), 1).into());
+            sc.add_tickets_endpoint(tickets);
+
+            sc.launchpad_tokens_deposited().set(true);
+        })
+        .assert_ok();
+
+    // more than 100% locked
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            let mut tranches = MultiValueEncoded::new();
+            tranches.push((6_000u32, UNLOCK_EPOCH).into());
+            tranches.push((5_000u32, UNLOCK_EPOCH * 2).into());
+            sc.set_launchpad_tokens_lock_tranches_endpoint(tranches);
+        })
+        .assert_user_error("Invalid lock percentage");
+
+    // tranches not ordered by unlock epoch
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            let mut tranches = MultiValueEncoded::new();
+            tranches.push((2_500u32, UNLOCK_EPOCH * 2).into());
+            tranches.push((2_500u32, UNLOCK_EPOCH).into());
+            sc.set_launchpad_tokens_lock_tranches_endpoint(tranches);
+        })
+        .assert_user_error("Invalid unlock epoch");
+
+    // 25% unlocked at UNLOCK_EPOCH, another 25% at UNLOCK_EPOCH * 2
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            let mut tranches = MultiValueEncoded::new();
+            tranches.push((2_500u32, UNLOCK_EPOCH).into());
+            tranches.push((2_500u32, UNLOCK_EPOCH * 2).into());
+            sc.set_launchpad_tokens_lock_tranches_endpoint(tranches);
+        })
+        .assert_ok();
+
+    // the single lock parameters no longer apply
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.set_launchpad_tokens_lock_percentage_endpoint(LOCK_PERCENTAGE);
+        })
+        .assert_user_error("Lock tranches are set");
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.set_launchpad_tokens_unlock_epoch_endpoint(UNLOCK_EPOCH);
+        })
+        .assert_user_error("Lock tranches are set");
+
+    b_mock.set_dcdt_balance(
+        lp_sc.address_ref(),
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(NR_WINNING_TICKETS as u64 * LAUNCHPAD_TOKENS_PER_TICKET),
+    );
+
+    b_mock.set_dcdt_local_roles(
+        simple_lock_sc.address_ref(),
+        LOCKED_TOKEN_ID,
+        &[DcdtLocalRole::NftCreate],
+    );
+
+    // user confirm
+    b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+
+    b_mock
+        .execute_tx(&user, &lp_sc, &rust_biguint!(TICKET_PRICE), |sc| {
+            sc.confirm_tickets(1);
+        })
+        .assert_ok();
+
+    // filter + select winners
+    b_mock.set_block_nonce(WINNER_SELECTION_START_BLOCK);
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.filter_tickets();
+            sc.select_winners();
+        })
+        .assert_ok();
+
+    // user claim
+    b_mock.set_block_nonce(CLAIM_START_BLOCK);
+
+    b_mock
+        .execute_tx(&user, &lp_sc, &rust_zero, |sc| {
+            sc.claim_launchpad_tokens_endpoint();
+        })
+        .assert_ok();
+
+    // each tranche is locked separately
+    b_mock.check_dcdt_balance(
+        &user,
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(LAUNCHPAD_TOKENS_PER_TICKET / 2),
+    );
+
+    b_mock.check_nft_balance(
+        &user,
+        LOCKED_TOKEN_ID,
+        1,
+        &rust_biguint!(LAUNCHPAD_TOKENS_PER_TICKET / 4),
+        Some(&LockedTokenAttributes::<DebugApi> {
+            original_token_id: managed_token_id_wrapped!(LAUNCHPAD_TOKEN_ID),
+            original_token_nonce: 0,
+            unlock_epoch: UNLOCK_EPOCH,
+        }),
+    );
+    b_mock.check_nft_balance(
+        &user,
+        LOCKED_TOKEN_ID,
+        2,
+        &rust_biguint!(LAUNCHPAD_TOKENS_PER_TICKET / 4),
+        Some(&LockedTokenAttributes::<DebugApi> {
+            original_token_id: managed_token_id_wrapped!(LAUNCHPAD_TOKEN_ID),
+            original_token_nonce: 0,
+            unlock_epoch: UNLOCK_EPOCH * 2,
+        }),
+    );
+}
+
+#[test]
+fn launchpad_update_lock_params_test() {
+    let _ = DebugApi::dummy();
+    let mut b_mock = BlockchainStateWrapper::new();
+    let rust_zero = rust_biguint!(0);
+
+    let owner = b_mock.create_user_account(&rust_zero);
+    let user = b_mock.create_user_account(&rust_biguint!(TICKET_PRICE));
+    let simple_lock_sc =
+        b_mock.create_sc_account(&rust_zero, None, SimpleLockMock::new, "simple lock wasm");
+    let lp_sc = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        launchpad_locked_tokens::contract_obj,
+        "launchpad wasm",
+    );
+
+    // setup
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.init(
+                managed_token_id!(LAUNCHPAD_TOKEN_ID),
+                managed_biguint!(LAUNCHPAD_TOKENS_PER_TICKET),
+                managed_rewa_token_id!(),
+                managed_biguint!(TICKET_PRICE),
+                NR_WINNING_TICKETS,
+                CONFIRM_START_BLOCK,
+                WINNER_SELECTION_START_BLOCK,
+                CLAIM_START_BLOCK,
+                LOCK_PERCENTAGE,
+                UNLOCK_EPOCH,
+                managed_address!(simple_lock_sc.address_ref()),
+                OptionalValue::None,
+            );
+
+            let mut tickets = MultiValueEncoded::new();
+            tickets.push((managed_address!(&user), 1).into());
+            sc.add_tickets_endpoint(tickets);
+
+            sc.launchpad_tokens_deposited().set(true);
+        })
+        .assert_ok();
+
+    // the lock contract was moved to a new address
+    let new_simple_lock_sc =
+        b_mock.create_sc_account(&rust_zero, None, SimpleLockMock::new, "simple lock wasm");
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.set_simple_lock_sc_address_endpoint(managed_address!(&user));
+        })
+        .assert_user_error("Invalid SC address");
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.set_launchpad_tokens_lock_percentage_endpoint(0);
+        })
+        .assert_user_error("Invalid lock percentage");
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.set_simple_lock_sc_address_endpoint(managed_address!(
+                new_simple_lock_sc.address_ref()
+            ));
+            sc.set_launchpad_tokens_lock_percentage_endpoint(LOCK_PERCENTAGE / 2);
+            sc.set_admin_action_delay(ADMIN_ACTION_DELAY);
+
+            assert_eq!(
+                sc.simple_lock_sc_address().get(),
+                managed_address!(new_simple_lock_sc.address_ref())
+            );
+        })
+        .assert_ok();
+
+    b_mock.set_dcdt_balance(
+        lp_sc.address_ref(),
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(NR_WINNING_TICKETS as u64 * LAUNCHPAD_TOKENS_PER_TICKET),
+    );
+
+    b_mock.set_dcdt_local_roles(
+        new_simple_lock_sc.address_ref(),
+        LOCKED_TOKEN_ID,
+        &[DcdtLocalRole::NftCreate],
+    );
+
+    // user confirm
+    b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+
+    b_mock
+        .execute_tx(&user, &lp_sc, &rust_biguint!(TICKET_PRICE), |sc| {
+            sc.confirm_tickets(1);
+        })
+        .assert_ok();
+
+    // once the confirmation period starts, changes must go through the queue
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.set_launchpad_tokens_unlock_epoch_endpoint(UNLOCK_EPOCH * 2);
+        })
+        .assert_user_error("Add tickets period has passed");
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            let _ = sc.propose_admin_action_endpoint(AdminAction::SetLaunchpadTokensUnlockEpoch {
+                unlock_epoch: UNLOCK_EPOCH * 2,
+            });
+            let _ = sc.propose_admin_action_endpoint(AdminAction::SetSimpleLockScAddress {
+                sc_address: managed_address!(simple_lock_sc.address_ref()),
+            });
+        })
+        .assert_ok();
+
+    b_mock.set_block_nonce(CONFIRM_START_BLOCK + ADMIN_ACTION_DELAY);
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.execute_admin_action_endpoint(1);
+
+            assert_eq!(sc.launchpad_tokens_unlock_epoch().get(), UNLOCK_EPOCH * 2);
+        })
+        .assert_ok();
+
+    // filter + select winners
+    b_mock.set_block_nonce(WINNER_SELECTION_START_BLOCK);
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.filter_tickets();
+            sc.select_winners();
+        })
+        .assert_ok();
+
+    // user claim
+    b_mock.set_block_nonce(CLAIM_START_BLOCK);
+
+    b_mock
+        .execute_tx(&user, &lp_sc, &rust_zero, |sc| {
+            sc.claim_launchpad_tokens_endpoint();
+        })
+        .assert_ok();
+
+    // the new lock parameters are used
+    b_mock.check_dcdt_balance(
+        &user,
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(LAUNCHPAD_TOKENS_PER_TICKET * 3 / 4),
+    );
+
+    b_mock.check_nft_balance(
+        &user,
+        LOCKED_TOKEN_ID,
+        1,
+        &rust_biguint!(LAUNCHPAD_TOKENS_PER_TICKET / 4),
+        Some(&LockedTokenAttributes::<DebugApi> {
+            original_token_id: managed_token_id_wrapped!(LAUNCHPAD_TOKEN_ID),
+            original_token_nonce: 0,
+            unlock_epoch: UNLOCK_EPOCH * 2,
+        }),
+    );
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.execute_admin_action_endpoint(2);
+        })
+        .assert_user_error("Claim period has started");
+}
+
+#[test]
+
 #[derive(Clone, Default)]
 pub struct SimpleLockMock {}
 
@@ -3,7 +3,9 @@
 dharitri_sc::imports!();
 dharitri_sc::derive_imports!();
 
-use launchpad_common::launch_stage::Flags;
+use launchpad_common::{
+    config::TimelineMode, launch_stage::Flags, permissions::Role, timelock::AdminAction,
+};
 
 use crate::guaranteed_ticket_winners::GuaranteedTicketsSelectionOperation;
 
@@ -22,9 +24,13 @@ pub trait LaunchpadMigrationGuaranteedTickets:
     + launchpad_common::winner_selection::WinnerSelectionModule
     + launchpad_common::ongoing_operation::OngoingOperationModule
     + launchpad_common::permissions::PermissionsModule
+    + launchpad_common::address_groups::AddressGroupsModule
+    + launchpad_common::timelock::TimelockModule
     + launchpad_common::blacklist::BlacklistModule
     + launchpad_common::token_send::TokenSendModule
+    + launchpad_common::price_oracle::PriceOracleModule
     + launchpad_common::user_interactions::UserInteractionsModule
+    + launchpad_common::cancellation::CancellationModule
     + launchpad_common::common_events::CommonEventsModule
     + guaranteed_tickets_init::GuaranteedTicketsInitModule
     + guaranteed_ticket_winners::GuaranteedTicketWinnersModule
@@ -43,6 +49,7 @@ pub trait LaunchpadMigrationGuaranteedTickets:
         winner_selection_start_block: u64,
         claim_start_block: u64,
         min_confirmed_for_guaranteed_ticket: usize,
+        opt_timeline_mode: OptionalValue<TimelineMode>,
     ) {
         self.init_base(
             launchpad_token_id,
@@ -54,6 +61,7 @@ pub trait LaunchpadMigrationGuaranteedTickets:
             winner_selection_start_block,
             claim_start_block,
             Flags::default(),
+            opt_timeline_mode.into_option().unwrap_or_default(),
         );
 
         require!(
@@ -64,19 +72,20 @@ pub trait LaunchpadMigrationGuaranteedTickets:
             .set(min_confirmed_for_guaranteed_ticket);
     }
 
-    #[only_owner]
     #[endpoint(addTickets)]
     fn add_tickets_endpoint(
         &self,
         address_number_pairs: MultiValueEncoded<MultiValue4<ManagedAddress, usize, usize, bool>>,
     ) {
+        self.require_role(Role::TicketManager);
         self.add_tickets_with_guaranteed_winners(address_number_pairs);
     }
 
-    #[only_owner]
     #[payable("*")]
     #[endpoint(depositLaunchpadTokens)]
     fn deposit_launchpad_tokens_endpoint(&self) {
+        self.require_role(Role::Treasurer);
+
         let base_selection_winning_tickets = self.nr_winning_tickets().get();
         let reserved_tickets = self.total_guaranteed_tickets().get();
         let total_tickets = base_selection_winning_tickets + reserved_tickets;
@@ -85,9 +94,13 @@ pub trait LaunchpadMigrationGuaranteedTickets:
     }
 
     #[endpoint(addUsersToBlacklist)]
-    fn add_users_to_blacklist_endpoint(&self, users_list: MultiValueEncoded<ManagedAddress>) {
+    fn add_users_to_blacklist_endpoint(
+        &self,
+        reason: u32,
+        users_list: MultiValueEncoded<ManagedAddress>,
+    ) {
         let users_vec = users_list.to_vec();
-        self.add_users_to_blacklist(&users_vec);
+        self.add_users_to_blacklist(&users_vec, reason);
         self.clear_users_with_guaranteed_ticket_after_blacklist(&users_vec);
     }
 
@@ -154,9 +167,14 @@ pub trait LaunchpadMigrationGuaranteedTickets:
         self.claim_launchpad_tokens(Self::default_send_launchpad_tokens_fn);
     }
 
-    #[only_owner]
+    #[endpoint(claimRefund)]
+    fn claim_refund_endpoint(&self) {
+        self.claim_cancellation_refund();
+    }
+
     #[endpoint(claimTicketPayment)]
     fn claim_ticket_payment_endpoint(&self) {
+        self.require_role(Role::Treasurer);
         self.claim_ticket_payment();
     }
 
@@ -176,4 +194,16 @@ pub trait LaunchpadMigrationGuaranteedTickets:
         )
             .into()
     }
+
+    #[endpoint(proposeAdminAction)]
+    fn propose_admin_action_endpoint(&self, action: AdminAction<Self::Api>) -> u64 {
+        require!(action.is_common(), "Action not supported");
+        self.propose_admin_action(action)
+    }
+
+    #[endpoint(executeAdminAction)]
+    fn execute_admin_action_endpoint(&self, action_id: u64) {
+        let action = self.take_executable_admin_action(action_id);
+        self.execute_admin_action(action);
+    }
 }
@@ -1,5 +1,6 @@
 use dharitri_sc::types::{
     Address, RewaOrDcdtTokenIdentifier, MultiValueEncoded, OperationCompletionStatus,
+    OptionalValue,
 };
 
 use launchpad_common::{
@@ -85,6 +86,7 @@ where
                     WINNER_SELECTION_START_BLOCK,
                     CLAIM_START_BLOCK,
                     MAX_TIER_TICKETS,
+                    OptionalValue::None,
                 );
             })
             .assert_ok();
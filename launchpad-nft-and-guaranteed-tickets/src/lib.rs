@@ -3,7 +3,9 @@
 dharitri_sc::imports!();
 dharitri_sc::derive_imports!();
 
-use launchpad_common::launch_stage::Flags;
+use launchpad_common::{
+    config::TimelineMode, launch_stage::Flags, permissions::Role, timelock::AdminAction,
+};
 use launchpad_with_nft::mystery_sft::SftSetupSteps;
 
 pub mod combined_selection;
@@ -18,9 +20,13 @@ pub trait Launchpad:
     + launchpad_common::winner_selection::WinnerSelectionModule
     + launchpad_common::ongoing_operation::OngoingOperationModule
     + launchpad_common::permissions::PermissionsModule
+    + launchpad_common::address_groups::AddressGroupsModule
+    + launchpad_common::timelock::TimelockModule
     + launchpad_common::blacklist::BlacklistModule
     + launchpad_common::token_send::TokenSendModule
+    + launchpad_common::price_oracle::PriceOracleModule
     + launchpad_common::user_interactions::UserInteractionsModule
+    + launchpad_common::cancellation::CancellationModule
     + launchpad_common::common_events::CommonEventsModule
     + dharitri_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
     + dharitri_sc_modules::pause::PauseModule
@@ -51,6 +57,7 @@ pub trait Launchpad:
         nft_cost_token_amount: BigUint,
         total_available_nfts: usize,
         min_confirmed_for_guaranteed_ticket: usize,
+        opt_timeline_mode: OptionalValue<TimelineMode>,
     ) {
         require!(total_available_nfts > 0, "Invalid total_available_nfts");
 
@@ -71,6 +78,7 @@ pub trait Launchpad:
             winner_selection_start_block,
             claim_start_block,
             Flags::default(),
+            opt_timeline_mode.into_option().unwrap_or_default(),
         );
 
         self.try_set_nft_cost(
@@ -84,19 +92,20 @@ pub trait Launchpad:
             .set_if_empty(SftSetupSteps::default());
     }
 
-    #[only_owner]
     #[endpoint(addTickets)]
     fn add_tickets_endpoint(
         &self,
         address_number_pairs: MultiValueEncoded<MultiValue4<ManagedAddress, usize, usize, bool>>,
     ) {
+        self.require_role(Role::TicketManager);
         self.add_tickets_with_guaranteed_winners(address_number_pairs);
     }
 
-    #[only_owner]
     #[payable("*")]
     #[endpoint(depositLaunchpadTokens)]
     fn deposit_launchpad_tokens_endpoint(&self) {
+        self.require_role(Role::Treasurer);
+
         let base_selection_winning_tickets = self.nr_winning_tickets().get();
         let reserved_tickets = self.users_with_guaranteed_ticket().len();
         let total_tickets = base_selection_winning_tickets + reserved_tickets;
@@ -105,23 +114,61 @@ pub trait Launchpad:
     }
 
     #[endpoint(addUsersToBlacklist)]
-    fn add_users_to_blacklist_endpoint(&self, users_list: MultiValueEncoded<ManagedAddress>) {
+    fn add_users_to_blacklist_endpoint(
+        &self,
+        reason: u32,
+        users_list: MultiValueEncoded<ManagedAddress>,
+    ) {
         let users_list_vec = users_list.to_vec();
-        self.add_users_to_blacklist(&users_list_vec);
+        self.add_users_to_blacklist(&users_list_vec, reason);
         self.clear_users_with_guaranteed_ticket_after_blacklist(&users_list_vec);
         self.refund_nft_cost_after_blacklist(&users_list_vec);
     }
 
+    #[endpoint(removeGuaranteedUsersFromBlacklist)]
+    fn remove_guaranteed_users_from_blacklist_endpoint(
+        &self,
+        users_list: MultiValueEncoded<ManagedAddress>,
+    ) {
+        let users_vec = users_list.to_vec();
+        self.remove_users_from_blacklist(users_list);
+        self.remove_guaranteed_tickets_from_blacklist(&users_vec);
+    }
+
     #[endpoint(claimLaunchpadTokens)]
     fn claim_launchpad_tokens_endpoint(&self) {
         self.claim_launchpad_tokens(Self::default_send_launchpad_tokens_fn);
         self.claim_nft();
     }
 
-    #[only_owner]
+    /// The NFT cost is refunded even if the user unconfirmed all their tickets
+    #[endpoint(claimRefund)]
+    fn claim_refund_endpoint(&self) {
+        let were_tickets_refunded = self.try_claim_cancellation_refund();
+        let was_nft_cost_refunded = self.refund_nft_cost_after_cancellation();
+        require!(
+            were_tickets_refunded || was_nft_cost_refunded,
+            "Nothing to refund"
+        );
+    }
+
     #[endpoint(claimTicketPayment)]
     fn claim_ticket_payment_endpoint(&self) {
+        self.require_role(Role::Treasurer);
+
         self.claim_ticket_payment();
         self.claim_nft_payment();
     }
+
+    #[endpoint(proposeAdminAction)]
+    fn propose_admin_action_endpoint(&self, action: AdminAction<Self::Api>) -> u64 {
+        require!(action.is_common(), "Action not supported");
+        self.propose_admin_action(action)
+    }
+
+    #[endpoint(executeAdminAction)]
+    fn execute_admin_action_endpoint(&self, action_id: u64) {
+        let action = self.take_executable_admin_action(action_id);
+        self.execute_admin_action(action);
+    }
 }
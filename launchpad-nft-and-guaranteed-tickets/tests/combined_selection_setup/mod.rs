@@ -12,7 +12,7 @@ use dharitri_sc::{
     storage::mappers::StorageTokenWrapper,
     types::{
         Address, RewaOrDcdtTokenIdentifier, DcdtLocalRole, MultiValueEncoded,
-        OperationCompletionStatus,
+        OperationCompletionStatus, OptionalValue,
     },
 };
 use dharitri_sc_scenario::{
@@ -98,6 +98,7 @@ where
                     managed_biguint!(NFT_TICKET_COST),
                     TOTAL_NFTS,
                     MAX_TIER_TICKETS,
+                    OptionalValue::None,
                 );
             })
             .assert_ok();
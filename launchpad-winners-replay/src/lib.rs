@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+pub mod random;
+
+use random::{Random, HASH_LEN};
+
+/// Same as `launchpad_common::tickets::FIRST_TICKET_ID`
+pub const FIRST_TICKET_ID: usize = 1;
+
+/// A `ticketBatch` storage entry, as found after `filterTickets` completed.
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TicketBatchEntry {
+    pub first_ticket_id: usize,
+    pub address: String,
+    pub nr_tickets: usize,
+}
+
+/// `seed` is the hex encoded value of the `getWinnerSelectionSeed` view,
+/// `nrWinningTickets` is the total from the `selectWinnersCompleted` event.
+/// `weightedLottery` is the value of the `isWeightedLottery` view,
+/// `nrAddressGroups` the number of entries returned by `getAddressGroups`,
+/// `winnersRedrawn` is set if any winner was blacklisted through `addWinnersToBlacklist`,
+/// and `waitlistWinnersSelected` is the value of the `wereWaitlistWinnersSelected` view.
+/// These modes change the winning tickets, and are not replayed.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelectionSnapshot {
+    pub seed: String,
+    pub nr_winning_tickets: usize,
+    pub ticket_batches: Vec<TicketBatchEntry>,
+    pub weighted_lottery: bool,
+    pub nr_address_groups: usize,
+    pub winners_redrawn: bool,
+    pub waitlist_winners_selected: bool,
+}
+
+#[derive(Serialize, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WinnerEntry {
+    pub address: String,
+    pub winning_ticket_ids: Vec<usize>,
+}
+
+#[derive(Serialize, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SelectionResult {
+    pub winning_ticket_ids: Vec<usize>,
+    pub winners: Vec<WinnerEntry>,
+}
+
+pub fn replay_snapshot(snapshot: &SelectionSnapshot) -> Result<SelectionResult, String> {
+    if snapshot.weighted_lottery {
+        return Err("The weighted lottery is not supported".to_string());
+    }
+    if snapshot.nr_address_groups > 0 {
+        return Err("Address groups are not supported".to_string());
+    }
+    if snapshot.winners_redrawn {
+        return Err("Redrawn winners are not supported".to_string());
+    }
+    if snapshot.waitlist_winners_selected {
+        return Err("Waitlist winners are not supported".to_string());
+    }
+
+    let seed = hex::decode(snapshot.seed.trim_start_matches("0x"))
+        .map_err(|err| format!("Invalid seed: {err}"))?;
+    if seed.len() < HASH_LEN {
+        return Err("Invalid seed length".to_string());
+    }
+
+    let mut ticket_batches = snapshot.ticket_batches.clone();
+    ticket_batches.sort_by_key(|batch| batch.first_ticket_id);
+
+    let mut expected_first_id = FIRST_TICKET_ID;
+    for batch in &ticket_batches {
+        if batch.first_ticket_id != expected_first_id || batch.nr_tickets == 0 {
+            return Err(format!(
+                "Ticket batches are not contiguous at ticket ID {}",
+                batch.first_ticket_id
+            ));
+        }
+
+        expected_first_id += batch.nr_tickets;
+    }
+
+    let total_tickets = expected_first_id - FIRST_TICKET_ID;
+    if snapshot.nr_winning_tickets > total_tickets {
+        return Err("More winning tickets than total tickets".to_string());
+    }
+
+    let winning_ticket_ids =
+        replay_winning_tickets(&seed, total_tickets, snapshot.nr_winning_tickets);
+
+    let mut winners = Vec::new();
+    let mut winning_ids_iter = winning_ticket_ids.iter().peekable();
+    for batch in &ticket_batches {
+        let last_ticket_id = batch.first_ticket_id + batch.nr_tickets - 1;
+
+        let mut batch_winning_ids = Vec::new();
+        while let Some(&&ticket_id) = winning_ids_iter.peek() {
+            if ticket_id > last_ticket_id {
+                break;
+            }
+
+            batch_winning_ids.push(ticket_id);
+            winning_ids_iter.next();
+        }
+
+        if !batch_winning_ids.is_empty() {
+            winners.push(WinnerEntry {
+                address: batch.address.clone(),
+                winning_ticket_ids: batch_winning_ids,
+            });
+        }
+    }
+
+    Ok(SelectionResult {
+        winning_ticket_ids,
+        winners,
+    })
+}
+
+/// Mirrors `WinnerSelectionModule::select_winners` and `shuffle_single_ticket`,
+/// with the `ticketPosToId` storage replaced by an in-memory map.
+/// Returns the winning ticket IDs in ascending order.
+pub fn replay_winning_tickets(
+    seed: &[u8],
+    total_tickets: usize,
+    nr_winning_tickets: usize,
+) -> Vec<usize> {
+    let mut rng = Random::from_seed(seed);
+    let mut ticket_pos_to_id = HashMap::new();
+    let mut winning_ticket_ids = Vec::with_capacity(nr_winning_tickets);
+
+    for current_ticket_position in FIRST_TICKET_ID..=nr_winning_tickets {
+        let rand_pos = rng.next_usize_in_range(current_ticket_position, total_tickets + 1);
+
+        let winning_ticket_id = get_ticket_id_from_pos(&ticket_pos_to_id, rand_pos);
+        winning_ticket_ids.push(winning_ticket_id);
+
+        let current_ticket_id = get_ticket_id_from_pos(&ticket_pos_to_id, current_ticket_position);
+        ticket_pos_to_id.insert(rand_pos, current_ticket_id);
+    }
+
+    winning_ticket_ids.sort_unstable();
+    winning_ticket_ids
+}
+
+fn get_ticket_id_from_pos(ticket_pos_to_id: &HashMap<usize, usize>, ticket_pos: usize) -> usize {
+    ticket_pos_to_id
+        .get(&ticket_pos)
+        .copied()
+        .unwrap_or(ticket_pos)
+}
@@ -0,0 +1,29 @@
+use std::{env, fs, process};
+
+use launchpad_winners_replay::{replay_snapshot, SelectionSnapshot};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() != 2 {
+        eprintln!("Usage: {} <snapshot.json>", args[0]);
+        process::exit(1);
+    }
+
+    if let Err(err) = run(&args[1]) {
+        eprintln!("{err}");
+        process::exit(1);
+    }
+}
+
+fn run(snapshot_path: &str) -> Result<(), String> {
+    let snapshot_json = fs::read_to_string(snapshot_path)
+        .map_err(|err| format!("Failed to read {snapshot_path}: {err}"))?;
+    let snapshot: SelectionSnapshot = serde_json::from_str(&snapshot_json)
+        .map_err(|err| format!("Invalid snapshot: {err}"))?;
+
+    let result = replay_snapshot(&snapshot)?;
+    let result_json = serde_json::to_string_pretty(&result).map_err(|err| err.to_string())?;
+    println!("{result_json}");
+
+    Ok(())
+}
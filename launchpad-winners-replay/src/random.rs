@@ -0,0 +1,51 @@
+use sha2::{Digest, Sha256};
+
+const USIZE_BYTES: usize = 4;
+pub const HASH_LEN: usize = 32;
+
+/// Std-only copy of `launchpad_common::random::Random`.
+/// The contracts run on 32-bit wasm, so each `usize` is read as a big endian `u32`.
+pub struct Random {
+    seed: Vec<u8>,
+    index: usize,
+}
+
+impl Random {
+    /// The seed must hold at least `HASH_LEN` bytes, same as on-chain
+    pub fn from_seed(seed: &[u8]) -> Self {
+        Self {
+            seed: seed.to_vec(),
+            index: 0,
+        }
+    }
+
+    pub fn next_usize(&mut self) -> usize {
+        if self.index + USIZE_BYTES > HASH_LEN {
+            self.hash_seed();
+        }
+
+        let mut raw_bytes = [0u8; USIZE_BYTES];
+        raw_bytes.copy_from_slice(&self.seed[self.index..self.index + USIZE_BYTES]);
+
+        self.index += USIZE_BYTES;
+
+        u32::from_be_bytes(raw_bytes) as usize
+    }
+
+    /// Range is [min, max)
+    pub fn next_usize_in_range(&mut self, min: usize, max: usize) -> usize {
+        let rand = self.next_usize();
+
+        if min >= max {
+            min
+        } else {
+            min + rand % (max - min)
+        }
+    }
+
+    fn hash_seed(&mut self) {
+        self.seed = Sha256::digest(&self.seed).to_vec();
+
+        self.index = 0;
+    }
+}
@@ -0,0 +1,254 @@
+use dharitri_sc::types::{
+    RewaOrDcdtTokenIdentifier, ManagedBuffer, MultiValueEncoded, OptionalValue,
+};
+use dharitri_sc_scenario::{
+    api::StaticApi, managed_address, managed_biguint, managed_token_id, rust_biguint,
+    testing_framework::BlockchainStateWrapper,
+};
+use launchpad::Launchpad;
+use launchpad_common::{
+    config::ConfigModule,
+    random::Random as ContractRandom,
+    tickets::{TicketsModule, WINNING_TICKET},
+    user_interactions::UserInteractionsModule,
+    winner_selection::WinnerSelectionModule,
+};
+use launchpad_winners_replay::{
+    random::Random, replay_snapshot, replay_winning_tickets, SelectionSnapshot, TicketBatchEntry,
+};
+
+static LAUNCHPAD_TOKEN_ID: &[u8] = b"LAUNCH-123456";
+const LAUNCHPAD_TOKENS_PER_TICKET: u64 = 100;
+const TICKET_PRICE: u64 = 10;
+const NR_WINNING_TICKETS: usize = 4;
+const CONFIRM_START_BLOCK: u64 = 5;
+const WINNER_SELECTION_START_BLOCK: u64 = 10;
+const CLAIM_START_BLOCK: u64 = 15;
+
+// (allocated tickets, confirmed tickets)
+const PARTICIPANT_TICKETS: [(usize, usize); 4] = [(2, 2), (5, 1), (4, 4), (3, 0)];
+
+#[test]
+fn replay_matches_on_chain_selection_test() {
+    let mut b_mock = BlockchainStateWrapper::new();
+    let rust_zero = rust_biguint!(0);
+
+    let owner = b_mock.create_user_account(&rust_zero);
+    let participants: Vec<_> = PARTICIPANT_TICKETS
+        .iter()
+        .map(|(nr_tickets, _)| {
+            b_mock.create_user_account(&rust_biguint!(TICKET_PRICE * *nr_tickets as u64))
+        })
+        .collect();
+    let lp_sc = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        launchpad::contract_obj,
+        "launchpad wasm",
+    );
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.init(
+                managed_token_id!(LAUNCHPAD_TOKEN_ID),
+                managed_biguint!(LAUNCHPAD_TOKENS_PER_TICKET),
+                RewaOrDcdtTokenIdentifier::rewa(),
+                managed_biguint!(TICKET_PRICE),
+                NR_WINNING_TICKETS,
+                CONFIRM_START_BLOCK,
+                WINNER_SELECTION_START_BLOCK,
+                CLAIM_START_BLOCK,
+                OptionalValue::None,
+            );
+
+            let mut tickets = MultiValueEncoded::new();
+            for (participant, (nr_tickets, _)) in participants.iter().zip(PARTICIPANT_TICKETS) {
+                tickets.push((managed_address!(participant), nr_tickets).into());
+            }
+            sc.add_tickets_endpoint(tickets);
+
+            sc.launchpad_tokens_deposited().set(true);
+        })
+        .assert_ok();
+
+    b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+    for (participant, (_, nr_confirmed)) in participants.iter().zip(PARTICIPANT_TICKETS) {
+        if nr_confirmed == 0 {
+            continue;
+        }
+
+        b_mock
+            .execute_tx(
+                participant,
+                &lp_sc,
+                &rust_biguint!(TICKET_PRICE * nr_confirmed as u64),
+                |sc| {
+                    sc.confirm_tickets(nr_confirmed);
+                },
+            )
+            .assert_ok();
+    }
+
+    b_mock.set_block_nonce(WINNER_SELECTION_START_BLOCK);
+    b_mock.set_block_random_seed(&[42u8; 48]);
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.filter_tickets();
+            sc.select_winners();
+        })
+        .assert_ok();
+
+    let mut seed = Vec::new();
+    let mut ticket_batches = Vec::new();
+    let mut on_chain_winning_ids = Vec::new();
+    let mut waitlist_winners_selected = true;
+    b_mock
+        .execute_query(&lp_sc, |sc| {
+            seed = sc
+                .winner_selection_seed()
+                .get()
+                .to_boxed_bytes()
+                .as_slice()
+                .to_vec();
+
+            for (i, participant) in participants.iter().enumerate() {
+                let address = managed_address!(participant);
+                if sc.ticket_range_for_address(&address).is_empty() {
+                    continue;
+                }
+
+                let ticket_range = sc.ticket_range_for_address(&address).get();
+                ticket_batches.push(TicketBatchEntry {
+                    first_ticket_id: ticket_range.first_id,
+                    address: format!("participant{i}"),
+                    nr_tickets: ticket_range.last_id - ticket_range.first_id + 1,
+                });
+            }
+
+            for ticket_id in 1..=sc.last_ticket_id().get() {
+                if sc.ticket_status(ticket_id).get() == WINNING_TICKET {
+                    on_chain_winning_ids.push(ticket_id);
+                }
+            }
+
+            assert_eq!(sc.nr_winning_tickets().get(), NR_WINNING_TICKETS);
+            waitlist_winners_selected = sc.waitlist_winners_selected().get();
+        })
+        .assert_ok();
+
+    // 7 tickets left after filtering
+    let total_tickets = ticket_batches.iter().map(|batch| batch.nr_tickets).sum();
+    assert_eq!(total_tickets, 7);
+    assert_eq!(
+        replay_winning_tickets(&seed, total_tickets, NR_WINNING_TICKETS),
+        on_chain_winning_ids
+    );
+
+    let snapshot = SelectionSnapshot {
+        seed: hex::encode(&seed),
+        nr_winning_tickets: NR_WINNING_TICKETS,
+        ticket_batches,
+        weighted_lottery: false,
+        nr_address_groups: 0,
+        winners_redrawn: false,
+        waitlist_winners_selected,
+    };
+    let result = replay_snapshot(&snapshot).unwrap();
+    assert_eq!(result.winning_ticket_ids, on_chain_winning_ids);
+
+    let nr_replayed_winning_tickets: usize = result
+        .winners
+        .iter()
+        .map(|winner| winner.winning_ticket_ids.len())
+        .sum();
+    assert_eq!(nr_replayed_winning_tickets, NR_WINNING_TICKETS);
+}
+
+#[test]
+fn replay_invalid_snapshot_test() {
+    let snapshot = SelectionSnapshot {
+        seed: hex::encode([1u8; 32]),
+        nr_winning_tickets: 1,
+        ticket_batches: vec![
+            TicketBatchEntry {
+                first_ticket_id: 1,
+                address: "first".to_string(),
+                nr_tickets: 2,
+            },
+            TicketBatchEntry {
+                first_ticket_id: 4,
+                address: "second".to_string(),
+                nr_tickets: 1,
+            },
+        ],
+        weighted_lottery: false,
+        nr_address_groups: 0,
+        winners_redrawn: false,
+        waitlist_winners_selected: false,
+    };
+
+    assert!(replay_snapshot(&snapshot).is_err());
+}
+
+#[test]
+fn replay_unsupported_modes_test() {
+    let new_snapshot = || SelectionSnapshot {
+        seed: hex::encode([1u8; 32]),
+        nr_winning_tickets: 1,
+        ticket_batches: vec![TicketBatchEntry {
+            first_ticket_id: 1,
+            address: "first".to_string(),
+            nr_tickets: 2,
+        }],
+        weighted_lottery: false,
+        nr_address_groups: 0,
+        winners_redrawn: false,
+        waitlist_winners_selected: false,
+    };
+    assert!(replay_snapshot(&new_snapshot()).is_ok());
+
+    let mut snapshot = new_snapshot();
+    snapshot.weighted_lottery = true;
+    assert_eq!(
+        replay_snapshot(&snapshot).unwrap_err(),
+        "The weighted lottery is not supported"
+    );
+
+    let mut snapshot = new_snapshot();
+    snapshot.nr_address_groups = 1;
+    assert_eq!(
+        replay_snapshot(&snapshot).unwrap_err(),
+        "Address groups are not supported"
+    );
+
+    let mut snapshot = new_snapshot();
+    snapshot.winners_redrawn = true;
+    assert_eq!(
+        replay_snapshot(&snapshot).unwrap_err(),
+        "Redrawn winners are not supported"
+    );
+
+    let mut snapshot = new_snapshot();
+    snapshot.waitlist_winners_selected = true;
+    assert_eq!(
+        replay_snapshot(&snapshot).unwrap_err(),
+        "Waitlist winners are not supported"
+    );
+}
+
+#[test]
+fn replay_random_matches_contract_random_test() {
+    // the block random seed is longer than a hash
+    let seed = [7u8; 48];
+    let mut rng = Random::from_seed(&seed);
+    let mut contract_rng =
+        ContractRandom::<StaticApi>::from_seed(ManagedBuffer::new_from_bytes(&seed));
+
+    // 8 values per hash, so the seed is hashed again several times
+    for max in 2..50 {
+        assert_eq!(
+            rng.next_usize_in_range(1, max),
+            contract_rng.next_usize_in_range(1, max)
+        );
+    }
+}
@@ -46,4 +46,24 @@ pub trait ClaimNftModule:
             );
         }
     }
+
+    /// Returns false if the caller did not confirm the NFT
+    fn refund_nft_cost_after_cancellation(&self) -> bool {
+        let caller = self.blockchain().get_caller();
+        let did_user_confirm = self.nft_selection_winners().swap_remove(&caller)
+            || self.confirmed_nft_user_list().swap_remove(&caller);
+        if !did_user_confirm {
+            return false;
+        }
+
+        let nft_cost = self.nft_cost().get();
+        self.send().direct(
+            &caller,
+            &nft_cost.token_identifier,
+            nft_cost.token_nonce,
+            &nft_cost.amount,
+        );
+
+        true
+    }
 }
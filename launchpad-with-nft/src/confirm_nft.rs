@@ -39,7 +39,7 @@ pub trait ConfirmNftModule:
             let mut payment = self.nft_cost().get();
             payment.amount = claimable_amount;
 
-            let owner = self.blockchain().get_caller();
+            let owner = self.blockchain().get_owner_address();
             self.send().direct(
                 &owner,
                 &payment.token_identifier,
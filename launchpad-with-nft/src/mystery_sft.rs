@@ -1,4 +1,5 @@
 use dharitri_sc::codec::Empty;
+use launchpad_common::permissions::Role;
 
 dharitri_sc::imports!();
 dharitri_sc::derive_imports!();
@@ -37,7 +38,7 @@ pub trait MysterySftModule:
     #[payable("*")]
     #[endpoint(issueMysterySft)]
     fn issue_mystery_sft(&self, token_display_name: ManagedBuffer, token_ticker: ManagedBuffer) {
-        self.require_extended_permissions();
+        self.require_role(Role::Operator);
 
         let issue_cost = self.call_value().rewa_value().clone_value();
         self.mystery_sft().issue_and_set_all_roles(
@@ -52,7 +53,7 @@ pub trait MysterySftModule:
 
     #[endpoint(createInitialSfts)]
     fn create_initial_sfts(&self) {
-        self.require_extended_permissions();
+        self.require_role(Role::Operator);
 
         let steps_mapper = self.sft_setup_steps();
         let mut steps = steps_mapper.get();
@@ -80,7 +81,7 @@ pub trait MysterySftModule:
 
     #[endpoint(setTransferRole)]
     fn set_transfer_role(&self, opt_addr_to_set: OptionalValue<ManagedAddress>) {
-        self.require_extended_permissions();
+        self.require_role(Role::Operator);
 
         let addr = match opt_addr_to_set {
             OptionalValue::Some(addr) => addr,
@@ -1,10 +1,13 @@
 dharitri_sc::imports!();
 
+use launchpad_common::permissions::Role;
+
 #[dharitri_sc::module]
 pub trait NftConfigModule:
-    launchpad_common::launch_stage::LaunchStageModule + launchpad_common::config::ConfigModule
+    launchpad_common::launch_stage::LaunchStageModule
+    + launchpad_common::config::ConfigModule
+    + launchpad_common::permissions::PermissionsModule
 {
-    #[only_owner]
     #[endpoint(setNftCost)]
     fn set_nft_cost(
         &self,
@@ -12,6 +15,7 @@ pub trait NftConfigModule:
         nft_cost_token_nonce: u64,
         nft_cost_token_amount: BigUint,
     ) {
+        self.require_role(Role::TicketManager);
         self.require_add_tickets_period();
         self.try_set_nft_cost(
             nft_cost_token_id,
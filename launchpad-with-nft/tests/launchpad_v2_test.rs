@@ -2,7 +2,13 @@
 
 mod launchpad_with_nft_setup;
 
-use launchpad_common::tickets::{TicketsModule, WINNING_TICKET};
+use launchpad_common::{
+    cancellation::CancellationModule,
+    permissions::{PermissionsModule, Role},
+    registration::{RegistrationModule, RegistrationRule},
+    tickets::{TicketsModule, WINNING_TICKET},
+    user_interactions::UserInteractionsModule,
+};
 use launchpad_with_nft::{
     confirm_nft::ConfirmNftModule, mystery_sft::MysterySftTypes,
     nft_winners_selection::NftWinnersSelectionModule, Launchpad,
@@ -200,6 +206,62 @@ fn claim_test() {
         .assert_ok();
 }
 
+#[test]
+fn treasurer_claim_test() {
+    let mut lp_setup = LaunchpadSetup::new(launchpad_with_nft::contract_obj);
+    let users = lp_setup.participants.clone();
+    let treasurer = lp_setup.b_mock.create_user_account(&rust_biguint!(0));
+
+    lp_setup
+        .b_mock
+        .execute_tx(
+            &lp_setup.owner_address,
+            &lp_setup.lp_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut treasurers = MultiValueEncoded::new();
+                treasurers.push(managed_address!(&treasurer));
+                sc.grant_role(Role::Treasurer, treasurers);
+            },
+        )
+        .assert_ok();
+
+    lp_setup.confirm_nft(&users[0]).assert_ok();
+    lp_setup.confirm_nft(&users[1]).assert_ok();
+
+    lp_setup
+        .b_mock
+        .set_block_nonce(WINNER_SELECTION_START_BLOCK);
+
+    lp_setup.select_base_launchpad_winners().assert_ok();
+    lp_setup.select_nft_winners().assert_ok();
+
+    lp_setup.b_mock.set_block_nonce(CLAIM_START_BLOCK);
+
+    for user in &users {
+        lp_setup.claim(user).assert_ok();
+    }
+
+    // the payments go to the owner, not to the treasurer
+    lp_setup
+        .b_mock
+        .execute_tx(&treasurer, &lp_setup.lp_wrapper, &rust_biguint!(0), |sc| {
+            sc.claim_ticket_payment_endpoint();
+        })
+        .assert_ok();
+
+    lp_setup.b_mock.check_rewa_balance(
+        &lp_setup.owner_address,
+        &rust_biguint!(BASE_TICKET_COST + NFT_TICKET_COST),
+    );
+    lp_setup
+        .b_mock
+        .check_rewa_balance(&treasurer, &rust_biguint!(0));
+    lp_setup
+        .b_mock
+        .check_rewa_balance(lp_setup.lp_wrapper.address_ref(), &rust_biguint!(0));
+}
+
 #[test]
 fn blacklist_refund_test() {
     let mut lp_setup = LaunchpadSetup::new(launchpad_with_nft::contract_obj);
@@ -220,13 +282,166 @@ fn blacklist_refund_test() {
                 let mut args = MultiValueEncoded::new();
                 args.push(managed_address!(&users[0]));
 
-                sc.add_users_to_blacklist_endpoint(args);
+                sc.add_users_to_blacklist_endpoint(0, args);
+            },
+        )
+        .assert_ok();
+
+    lp_setup.b_mock.check_rewa_balance(
+        &users[0],
+        &rust_biguint!(BASE_TICKET_COST + NFT_TICKET_COST),
+    );
+}
+
+#[test]
+fn cancellation_refund_nft_cost_test() {
+    let mut lp_setup = LaunchpadSetup::new(launchpad_with_nft::contract_obj);
+    let users = lp_setup.participants.clone();
+
+    lp_setup.confirm_nft(&users[0]).assert_ok();
+
+    // the user unconfirms all their tickets, but keeps the NFT confirmed
+    lp_setup
+        .b_mock
+        .execute_tx(&users[0], &lp_setup.lp_wrapper, &rust_biguint!(0), |sc| {
+            sc.unconfirm_tickets(1);
+        })
+        .assert_ok();
+
+    lp_setup
+        .b_mock
+        .check_rewa_balance(&users[0], &rust_biguint!(BASE_TICKET_COST));
+
+    lp_setup
+        .b_mock
+        .execute_tx(
+            &lp_setup.owner_address,
+            &lp_setup.lp_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.cancel_launchpad();
             },
         )
         .assert_ok();
 
+    lp_setup
+        .b_mock
+        .execute_tx(&users[0], &lp_setup.lp_wrapper, &rust_biguint!(0), |sc| {
+            sc.claim_refund_endpoint();
+        })
+        .assert_ok();
+
     lp_setup.b_mock.check_rewa_balance(
         &users[0],
         &rust_biguint!(BASE_TICKET_COST + NFT_TICKET_COST),
     );
+
+    lp_setup
+        .b_mock
+        .execute_tx(&users[0], &lp_setup.lp_wrapper, &rust_biguint!(0), |sc| {
+            sc.claim_refund_endpoint();
+        })
+        .assert_user_error("Nothing to refund");
+}
+
+#[test]
+fn self_registration_test() {
+    let mut lp_setup = LaunchpadSetup::new(launchpad_with_nft::contract_obj);
+    let new_user = lp_setup
+        .b_mock
+        .create_user_account(&rust_biguint!(BASE_TICKET_COST * 2));
+
+    lp_setup.b_mock.set_block_nonce(CONFIRM_START_BLOCK - 1);
+
+    // registration disabled by default
+    lp_setup
+        .b_mock
+        .execute_tx(&new_user, &lp_setup.lp_wrapper, &rust_biguint!(0), |sc| {
+            sc.register_tickets(1);
+        })
+        .assert_user_error("Invalid number of tickets");
+
+    lp_setup
+        .b_mock
+        .execute_tx(
+            &lp_setup.owner_address,
+            &lp_setup.lp_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_registration_rule(RegistrationRule::WhitelistWithCap {
+                    max_tickets_per_user: 2,
+                });
+            },
+        )
+        .assert_ok();
+
+    // not whitelisted yet
+    lp_setup
+        .b_mock
+        .execute_tx(&new_user, &lp_setup.lp_wrapper, &rust_biguint!(0), |sc| {
+            sc.register_tickets(1);
+        })
+        .assert_user_error("Invalid number of tickets");
+
+    lp_setup
+        .b_mock
+        .execute_tx(
+            &lp_setup.owner_address,
+            &lp_setup.lp_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut users = MultiValueEncoded::new();
+                users.push(managed_address!(&new_user));
+                sc.add_users_to_registration_whitelist(users);
+            },
+        )
+        .assert_ok();
+
+    lp_setup
+        .b_mock
+        .execute_tx(&new_user, &lp_setup.lp_wrapper, &rust_biguint!(0), |sc| {
+            sc.register_tickets(3);
+        })
+        .assert_user_error("Invalid number of tickets");
+
+    lp_setup
+        .b_mock
+        .execute_tx(&new_user, &lp_setup.lp_wrapper, &rust_biguint!(0), |sc| {
+            sc.register_tickets(2);
+
+            let ticket_range = sc
+                .ticket_range_for_address(&managed_address!(&new_user))
+                .get();
+            assert_eq!(ticket_range.first_id, NR_LAUNCHPAD_PARTICIPANTS + 1);
+            assert_eq!(ticket_range.last_id, NR_LAUNCHPAD_PARTICIPANTS + 2);
+        })
+        .assert_ok();
+
+    lp_setup
+        .b_mock
+        .execute_tx(&new_user, &lp_setup.lp_wrapper, &rust_biguint!(0), |sc| {
+            sc.register_tickets(1);
+        })
+        .assert_user_error("Duplicate entry for user");
+
+    // registered tickets are confirmed like any other tickets
+    lp_setup.b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+    lp_setup
+        .b_mock
+        .execute_tx(&new_user, &lp_setup.lp_wrapper, &rust_biguint!(0), |sc| {
+            sc.register_tickets(1);
+        })
+        .assert_user_error("Add tickets period has passed");
+
+    lp_setup
+        .b_mock
+        .execute_tx(
+            &new_user,
+            &lp_setup.lp_wrapper,
+            &rust_biguint!(BASE_TICKET_COST * 2),
+            |sc| {
+                sc.confirm_tickets(2);
+            },
+        )
+        .assert_ok();
 }
@@ -11,7 +11,7 @@ use dharitri_sc::{
     storage::mappers::StorageTokenWrapper,
     types::{
         Address, RewaOrDcdtTokenIdentifier, DcdtLocalRole, MultiValueEncoded,
-        OperationCompletionStatus,
+        OperationCompletionStatus, OptionalValue,
     },
 };
 use dharitri_sc_scenario::{
@@ -88,6 +88,7 @@ where
                     0,
                     managed_biguint!(NFT_TICKET_COST),
                     TOTAL_NFTS,
+                    OptionalValue::None,
                 );
             })
             .assert_ok();
@@ -0,0 +1,143 @@
+use launchpad::Launchpad;
+use launchpad_common::{
+    config::ConfigModule,
+    permissions::{PermissionsModule, Role},
+    tickets::TicketsModule,
+    user_interactions::UserInteractionsModule,
+    winner_selection::WinnerSelectionModule,
+};
+use dharitri_sc::types::{Address, MultiValueEncoded, OptionalValue};
+use dharitri_sc_scenario::{
+    managed_address, managed_biguint, managed_rewa_token_id, managed_token_id, rust_biguint,
+    testing_framework::{BlockchainStateWrapper, ContractObjWrapper, TxResult},
+    DebugApi,
+};
+
+pub static LAUNCHPAD_TOKEN_ID: &[u8] = b"LAUNCH-123456";
+pub const LAUNCHPAD_TOKENS_PER_TICKET: u64 = 100_000;
+pub const TICKET_PRICE: u64 = 100;
+pub const NR_WINNING_TICKETS: usize = 1;
+pub const CONFIRM_START_BLOCK: u64 = 10;
+pub const WINNER_SELECTION_START_BLOCK: u64 = 20;
+pub const CLAIM_START_BLOCK: u64 = 30;
+
+pub struct LaunchpadSetup<LaunchpadBuilder>
+where
+    LaunchpadBuilder: 'static + Copy + Fn() -> launchpad::ContractObj<DebugApi>,
+{
+    pub b_mock: BlockchainStateWrapper,
+    pub owner_address: Address,
+    pub participants: Vec<Address>,
+    pub lp_wrapper: ContractObjWrapper<launchpad::ContractObj<DebugApi>, LaunchpadBuilder>,
+}
+
+impl<LaunchpadBuilder> LaunchpadSetup<LaunchpadBuilder>
+where
+    LaunchpadBuilder: 'static + Copy + Fn() -> launchpad::ContractObj<DebugApi>,
+{
+    /// Creates one participant per entry of `participant_tickets`, funded for exactly that many tickets.
+    /// Participants with zero tickets are not added to the launchpad.
+    pub fn new(
+        nr_winning_tickets: usize,
+        participant_tickets: &[usize],
+        lp_builder: LaunchpadBuilder,
+    ) -> Self {
+        let _ = DebugApi::dummy();
+        let rust_zero = rust_biguint!(0);
+
+        let mut b_mock = BlockchainStateWrapper::new();
+        let owner_address = b_mock.create_user_account(&rust_zero);
+        let mut participants = Vec::new();
+        for nr_tickets in participant_tickets {
+            let addr =
+                b_mock.create_user_account(&rust_biguint!(TICKET_PRICE * *nr_tickets as u64));
+            participants.push(addr);
+        }
+
+        let lp_wrapper = b_mock.create_sc_account(
+            &rust_zero,
+            Some(&owner_address),
+            lp_builder,
+            "launchpad wasm",
+        );
+
+        b_mock
+            .execute_tx(&owner_address, &lp_wrapper, &rust_zero, |sc| {
+                sc.init(
+                    managed_token_id!(LAUNCHPAD_TOKEN_ID),
+                    managed_biguint!(LAUNCHPAD_TOKENS_PER_TICKET),
+                    managed_rewa_token_id!(),
+                    managed_biguint!(TICKET_PRICE),
+                    nr_winning_tickets,
+                    CONFIRM_START_BLOCK,
+                    WINNER_SELECTION_START_BLOCK,
+                    CLAIM_START_BLOCK,
+                    OptionalValue::None,
+                );
+
+                let mut tickets = MultiValueEncoded::new();
+                for (participant, nr_tickets) in participants.iter().zip(participant_tickets) {
+                    if *nr_tickets > 0 {
+                        tickets.push((managed_address!(participant), *nr_tickets).into());
+                    }
+                }
+                if !tickets.is_empty() {
+                    sc.add_tickets_endpoint(tickets);
+                }
+
+                sc.launchpad_tokens_deposited().set(true);
+            })
+            .assert_ok();
+
+        b_mock.set_dcdt_balance(
+            lp_wrapper.address_ref(),
+            LAUNCHPAD_TOKEN_ID,
+            &rust_biguint!(nr_winning_tickets as u64 * LAUNCHPAD_TOKENS_PER_TICKET),
+        );
+
+        Self {
+            b_mock,
+            owner_address,
+            participants,
+            lp_wrapper,
+        }
+    }
+
+    pub fn grant_role(&mut self, role: Role, address: &Address) {
+        self.b_mock
+            .execute_tx(
+                &self.owner_address,
+                &self.lp_wrapper,
+                &rust_biguint!(0),
+                |sc| {
+                    let mut addresses = MultiValueEncoded::new();
+                    addresses.push(managed_address!(address));
+                    sc.grant_role(role, addresses);
+                },
+            )
+            .assert_ok();
+    }
+
+    pub fn confirm(&mut self, caller: &Address, nr_tickets: usize) -> TxResult {
+        self.b_mock.execute_tx(
+            caller,
+            &self.lp_wrapper,
+            &rust_biguint!(TICKET_PRICE * nr_tickets as u64),
+            |sc| {
+                sc.confirm_tickets(nr_tickets);
+            },
+        )
+    }
+
+    pub fn select_winners(&mut self) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.owner_address,
+            &self.lp_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.filter_tickets();
+                sc.select_winners();
+            },
+        )
+    }
+}
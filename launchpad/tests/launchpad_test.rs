@@ -0,0 +1,2445 @@
+mod launchpad_setup;
+
+use launchpad::Launchpad;
+use launchpad_common::{
+    address_groups::AddressGroupsModule,
+    blacklist::BlacklistModule,
+    cancellation::CancellationModule,
+    config::{ConfigModule, TimelineMode},
+    launch_stage::LaunchStageModule,
+    permissions::{PermissionsModule, Role},
+    price_oracle::PriceOracleModule,
+    pro_rata::ProRataModule,
+    registration::{RegistrationModule, RegistrationRule},
+    setup::SetupModule,
+    tickets::TicketsModule,
+    timelock::{AdminAction, TimelockModule},
+    user_interactions::UserInteractionsModule,
+    winner_selection::WinnerSelectionModule,
+};
+use launchpad_setup::{
+    LaunchpadSetup, CLAIM_START_BLOCK, CONFIRM_START_BLOCK, LAUNCHPAD_TOKENS_PER_TICKET,
+    LAUNCHPAD_TOKEN_ID, NR_WINNING_TICKETS, TICKET_PRICE, WINNER_SELECTION_START_BLOCK,
+};
+use dharitri_sc::{
+    codec::TopEncode,
+    contract_base::{CallableContract, ContractBase},
+    types::{ManagedVec, MultiValueEncoded, OptionalValue},
+};
+use dharitri_sc_scenario::{
+    managed_address, managed_biguint, managed_buffer, managed_rewa_token_id, managed_token_id,
+    managed_token_id_wrapped, rust_biguint,
+    testing_framework::{BlockchainStateWrapper, TxContextStack},
+    DebugApi,
+};
+
+static PRICE_FEED_FN_NAME: &str = "latestPriceFeedOptional";
+const CLAIM_DEADLINE_BLOCK: u64 = 40;
+const CONFIRM_START_TIMESTAMP: u64 = 1_000;
+const WINNER_SELECTION_START_TIMESTAMP: u64 = 2_000;
+const CLAIM_START_TIMESTAMP: u64 = 3_000;
+static STABLE_TOKEN_ID: &[u8] = b"STABLE-123456";
+static OTHER_TOKEN_ID: &[u8] = b"OTHER-123456";
+const STABLE_TICKET_PRICE: u64 = 50;
+const REWA_USD_PRICE: u64 = 2_500; // 25.00 USD
+const REFERENCE_TICKET_PRICE: u64 = 5_000; // 50.00 USD
+const PRICE_DECIMALS: u32 = 2;
+const ORACLE_TICKET_PRICE: u64 = 200; // 2.00 REWA with 2 decimals
+const PRICE_FEED_TIMESTAMP: u64 = 1_000;
+const MAX_PRICE_AGE: u64 = 60;
+const BRONZE_TICKET_PRICE: u64 = 50;
+const BRONZE_TOKENS_PER_TICKET: u64 = 1_000;
+const GOLD_TICKET_PRICE: u64 = 200;
+const GOLD_TOKENS_PER_TICKET: u64 = 10_000;
+const HEAVY_TICKET_WEIGHT: u64 = 1_000_000_000_000;
+const DUTCH_START_PRICE: u64 = 200;
+const DUTCH_FLOOR_PRICE: u64 = 100;
+const ADMIN_ACTION_DELAY: u64 = 5;
+const BLACKLIST_REASON: u32 = 7;
+
+#[test]
+fn launchpad_with_timestamp_timeline_test() {
+    let _ = DebugApi::dummy();
+    let mut b_mock = BlockchainStateWrapper::new();
+    let rust_zero = rust_biguint!(0);
+
+    let owner = b_mock.create_user_account(&rust_zero);
+    let user = b_mock.create_user_account(&rust_biguint!(TICKET_PRICE));
+    let lp_sc = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        launchpad::contract_obj,
+        "launchpad wasm",
+    );
+
+    // setup
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.init(
+                managed_token_id!(LAUNCHPAD_TOKEN_ID),
+                managed_biguint!(LAUNCHPAD_TOKENS_PER_TICKET),
+                managed_rewa_token_id!(),
+                managed_biguint!(TICKET_PRICE),
+                NR_WINNING_TICKETS,
+                CONFIRM_START_TIMESTAMP,
+                WINNER_SELECTION_START_TIMESTAMP,
+                CLAIM_START_TIMESTAMP,
+                OptionalValue::Some(TimelineMode::Timestamp),
+            );
+
+            let mut tickets = MultiValueEncoded::new();
+            tickets.push((managed_address!(&user), 1).into());
+            sc.add_tickets_endpoint(tickets);
+
+            sc.launchpad_tokens_deposited().set(true);
+        })
+        .assert_ok();
+
+    b_mock.set_dcdt_balance(
+        lp_sc.address_ref(),
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(NR_WINNING_TICKETS as u64 * LAUNCHPAD_TOKENS_PER_TICKET),
+    );
+
+    // block based setters are not available in timestamp mode
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.set_claim_start_block(CLAIM_START_BLOCK);
+        })
+        .assert_user_error("Invalid timeline mode");
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.set_claim_start_timestamp(CLAIM_START_TIMESTAMP + 1);
+            assert_eq!(
+                sc.configuration().get().claim_start_block,
+                CLAIM_START_TIMESTAMP + 1
+            );
+        })
+        .assert_ok();
+
+    // block nonce is ignored
+    b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+    b_mock
+        .execute_tx(&user, &lp_sc, &rust_biguint!(TICKET_PRICE), |sc| {
+            sc.confirm_tickets(1);
+        })
+        .assert_user_error("Not in confirmation period");
+
+    // user confirm
+    b_mock.set_block_timestamp(CONFIRM_START_TIMESTAMP);
+    b_mock
+        .execute_tx(&user, &lp_sc, &rust_biguint!(TICKET_PRICE), |sc| {
+            sc.confirm_tickets(1);
+        })
+        .assert_ok();
+
+    // filter + select winners
+    b_mock.set_block_timestamp(WINNER_SELECTION_START_TIMESTAMP);
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.filter_tickets();
+            sc.select_winners();
+        })
+        .assert_ok();
+
+    // claim start was moved one second later
+    b_mock.set_block_timestamp(CLAIM_START_TIMESTAMP);
+    b_mock
+        .execute_tx(&user, &lp_sc, &rust_zero, |sc| {
+            sc.claim_launchpad_tokens_endpoint();
+        })
+        .assert_user_error("Not in claim period");
+
+    b_mock.set_block_timestamp(CLAIM_START_TIMESTAMP + 1);
+    b_mock
+        .execute_tx(&user, &lp_sc, &rust_zero, |sc| {
+            sc.claim_launchpad_tokens_endpoint();
+        })
+        .assert_ok();
+
+    b_mock.check_dcdt_balance(
+        &user,
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(LAUNCHPAD_TOKENS_PER_TICKET),
+    );
+}
+
+#[test]
+fn launchpad_multi_token_payment_test() {
+    let _ = DebugApi::dummy();
+    let mut b_mock = BlockchainStateWrapper::new();
+    let rust_zero = rust_biguint!(0);
+
+    let owner = b_mock.create_user_account(&rust_zero);
+    let user = b_mock.create_user_account(&rust_biguint!(TICKET_PRICE));
+    b_mock.set_dcdt_balance(&user, STABLE_TOKEN_ID, &rust_biguint!(STABLE_TICKET_PRICE));
+    b_mock.set_dcdt_balance(&user, OTHER_TOKEN_ID, &rust_biguint!(STABLE_TICKET_PRICE));
+    let lp_sc = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        launchpad::contract_obj,
+        "launchpad wasm",
+    );
+
+    // setup
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.init(
+                managed_token_id!(LAUNCHPAD_TOKEN_ID),
+                managed_biguint!(LAUNCHPAD_TOKENS_PER_TICKET),
+                managed_rewa_token_id!(),
+                managed_biguint!(TICKET_PRICE),
+                NR_WINNING_TICKETS,
+                CONFIRM_START_BLOCK,
+                WINNER_SELECTION_START_BLOCK,
+                CLAIM_START_BLOCK,
+                OptionalValue::None,
+            );
+
+            let mut tickets = MultiValueEncoded::new();
+            tickets.push((managed_address!(&user), 2).into());
+            sc.add_tickets_endpoint(tickets);
+
+            sc.launchpad_tokens_deposited().set(true);
+        })
+        .assert_ok();
+
+    b_mock.set_dcdt_balance(
+        lp_sc.address_ref(),
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(NR_WINNING_TICKETS as u64 * LAUNCHPAD_TOKENS_PER_TICKET),
+    );
+
+    // the main payment token already has a price
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.add_ticket_payment_token(managed_rewa_token_id!(), managed_biguint!(1));
+        })
+        .assert_user_error("Token is already the main payment token");
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.add_ticket_payment_token(
+                managed_token_id_wrapped!(STABLE_TOKEN_ID),
+                managed_biguint!(STABLE_TICKET_PRICE),
+            );
+        })
+        .assert_ok();
+
+    // user confirms one ticket with each accepted token
+    b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+    b_mock
+        .execute_dcdt_transfer(
+            &user,
+            &lp_sc,
+            OTHER_TOKEN_ID,
+            0,
+            &rust_biguint!(STABLE_TICKET_PRICE),
+            |sc| {
+                sc.confirm_tickets(1);
+            },
+        )
+        .assert_user_error("Wrong payment token used");
+
+    b_mock
+        .execute_tx(&user, &lp_sc, &rust_biguint!(TICKET_PRICE), |sc| {
+            sc.confirm_tickets(1);
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_dcdt_transfer(
+            &user,
+            &lp_sc,
+            STABLE_TOKEN_ID,
+            0,
+            &rust_biguint!(STABLE_TICKET_PRICE),
+            |sc| {
+                sc.confirm_tickets(1);
+
+                let payments = sc.user_ticket_payments(&managed_address!(&user)).get();
+                assert_eq!(payments.len(), 2);
+                assert_eq!(
+                    payments.get(1).token_id,
+                    managed_token_id_wrapped!(STABLE_TOKEN_ID)
+                );
+            },
+        )
+        .assert_ok();
+
+    // filter + select winners
+    b_mock.set_block_nonce(WINNER_SELECTION_START_BLOCK);
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.filter_tickets();
+            sc.select_winners();
+        })
+        .assert_ok();
+
+    // the last confirmed ticket is refunded in the token it was paid with
+    b_mock.set_block_nonce(CLAIM_START_BLOCK);
+    b_mock
+        .execute_tx(&user, &lp_sc, &rust_zero, |sc| {
+            sc.claim_launchpad_tokens_endpoint();
+        })
+        .assert_ok();
+
+    b_mock.check_rewa_balance(&user, &rust_zero);
+    b_mock.check_dcdt_balance(&user, STABLE_TOKEN_ID, &rust_biguint!(STABLE_TICKET_PRICE));
+    b_mock.check_dcdt_balance(
+        &user,
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(LAUNCHPAD_TOKENS_PER_TICKET),
+    );
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.claim_ticket_payment_endpoint();
+        })
+        .assert_ok();
+
+    b_mock.check_rewa_balance(&owner, &rust_biguint!(TICKET_PRICE));
+    b_mock.check_dcdt_balance(&owner, STABLE_TOKEN_ID, &rust_zero);
+}
+
+#[test]
+fn launchpad_settle_unclaimed_ticket_payments_test() {
+    let _ = DebugApi::dummy();
+    let mut b_mock = BlockchainStateWrapper::new();
+    let rust_zero = rust_biguint!(0);
+
+    let owner = b_mock.create_user_account(&rust_zero);
+    let user = b_mock.create_user_account(&rust_biguint!(TICKET_PRICE));
+    b_mock.set_dcdt_balance(&user, STABLE_TOKEN_ID, &rust_biguint!(STABLE_TICKET_PRICE));
+    let lp_sc = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        launchpad::contract_obj,
+        "launchpad wasm",
+    );
+
+    // setup
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.init(
+                managed_token_id!(LAUNCHPAD_TOKEN_ID),
+                managed_biguint!(LAUNCHPAD_TOKENS_PER_TICKET),
+                managed_rewa_token_id!(),
+                managed_biguint!(TICKET_PRICE),
+                NR_WINNING_TICKETS,
+                CONFIRM_START_BLOCK,
+                WINNER_SELECTION_START_BLOCK,
+                CLAIM_START_BLOCK,
+                OptionalValue::None,
+            );
+
+            let mut tickets = MultiValueEncoded::new();
+            tickets.push((managed_address!(&user), 2).into());
+            sc.add_tickets_endpoint(tickets);
+
+            sc.add_ticket_payment_token(
+                managed_token_id_wrapped!(STABLE_TOKEN_ID),
+                managed_biguint!(STABLE_TICKET_PRICE),
+            );
+
+            sc.launchpad_tokens_deposited().set(true);
+        })
+        .assert_ok();
+
+    b_mock.set_dcdt_balance(
+        lp_sc.address_ref(),
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(NR_WINNING_TICKETS as u64 * LAUNCHPAD_TOKENS_PER_TICKET),
+    );
+
+    // user confirms one ticket with each accepted token
+    b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+    b_mock
+        .execute_tx(&user, &lp_sc, &rust_biguint!(TICKET_PRICE), |sc| {
+            sc.confirm_tickets(1);
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_dcdt_transfer(
+            &user,
+            &lp_sc,
+            STABLE_TOKEN_ID,
+            0,
+            &rust_biguint!(STABLE_TICKET_PRICE),
+            |sc| {
+                sc.confirm_tickets(1);
+            },
+        )
+        .assert_ok();
+
+    // filter + select winners
+    b_mock.set_block_nonce(WINNER_SELECTION_START_BLOCK);
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.filter_tickets();
+            sc.select_winners();
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            let mut users = MultiValueEncoded::new();
+            users.push(managed_address!(&user));
+            sc.settle_unclaimed_ticket_payments(users);
+        })
+        .assert_user_error("Not in claim period");
+
+    // the payment of the winning ticket is settled without the user claiming
+    b_mock.set_block_nonce(CLAIM_START_BLOCK);
+    b_mock
+        .execute_tx(&user, &lp_sc, &rust_zero, |sc| {
+            let mut users = MultiValueEncoded::new();
+            users.push(managed_address!(&user));
+            sc.settle_unclaimed_ticket_payments(users);
+        })
+        .assert_user_error("Permission denied");
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            // settling twice doesn't take the payment of the losing ticket
+            for _ in 0..2 {
+                let mut users = MultiValueEncoded::new();
+                users.push(managed_address!(&user));
+                sc.settle_unclaimed_ticket_payments(users);
+            }
+
+            let payments = sc.user_ticket_payments(&managed_address!(&user)).get();
+            assert_eq!(payments.len(), 1);
+            assert_eq!(
+                payments.get(0).token_id,
+                managed_token_id_wrapped!(STABLE_TOKEN_ID)
+            );
+
+            sc.claim_ticket_payment_endpoint();
+        })
+        .assert_ok();
+
+    b_mock.check_rewa_balance(&owner, &rust_biguint!(TICKET_PRICE));
+
+    // the losing ticket is still refunded when the user claims
+    b_mock
+        .execute_tx(&user, &lp_sc, &rust_zero, |sc| {
+            sc.claim_launchpad_tokens_endpoint();
+        })
+        .assert_ok();
+
+    b_mock.check_dcdt_balance(&user, STABLE_TOKEN_ID, &rust_biguint!(STABLE_TICKET_PRICE));
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.claim_ticket_payment_endpoint();
+        })
+        .assert_ok();
+
+    b_mock.check_rewa_balance(&owner, &rust_biguint!(TICKET_PRICE));
+    b_mock.check_dcdt_balance(&owner, STABLE_TOKEN_ID, &rust_zero);
+}
+
+#[test]
+fn launchpad_ticket_tiers_test() {
+    let _ = DebugApi::dummy();
+    let mut b_mock = BlockchainStateWrapper::new();
+    let rust_zero = rust_biguint!(0);
+
+    let owner = b_mock.create_user_account(&rust_zero);
+    let bronze_user = b_mock.create_user_account(&rust_biguint!(BRONZE_TICKET_PRICE));
+    let gold_user = b_mock.create_user_account(&rust_biguint!(GOLD_TICKET_PRICE));
+    let lp_sc = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        launchpad::contract_obj,
+        "launchpad wasm",
+    );
+
+    // setup
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.init(
+                managed_token_id!(LAUNCHPAD_TOKEN_ID),
+                managed_biguint!(LAUNCHPAD_TOKENS_PER_TICKET),
+                managed_rewa_token_id!(),
+                managed_biguint!(TICKET_PRICE),
+                2,
+                CONFIRM_START_BLOCK,
+                WINNER_SELECTION_START_BLOCK,
+                CLAIM_START_BLOCK,
+                OptionalValue::None,
+            );
+
+            let bronze_tier_id = sc.add_ticket_tier_endpoint(
+                managed_biguint!(BRONZE_TICKET_PRICE),
+                managed_biguint!(BRONZE_TOKENS_PER_TICKET),
+            );
+            let gold_tier_id = sc.add_ticket_tier_endpoint(
+                managed_biguint!(GOLD_TICKET_PRICE),
+                managed_biguint!(GOLD_TOKENS_PER_TICKET),
+            );
+            assert_eq!(bronze_tier_id, 1);
+            assert_eq!(gold_tier_id, 2);
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            let mut tickets = MultiValueEncoded::new();
+            tickets.push((managed_address!(&bronze_user), 1).into());
+            sc.add_tickets_endpoint(tickets);
+        })
+        .assert_user_error("Tickets must be added with a tier");
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            let mut tickets = MultiValueEncoded::new();
+            tickets.push((managed_address!(&gold_user), 2, 2).into());
+            tickets.push((managed_address!(&bronze_user), 1, 1).into());
+            sc.add_tickets_with_tier_endpoint(tickets);
+        })
+        .assert_user_error("Tickets must be added in ascending tier order");
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            let mut tickets = MultiValueEncoded::new();
+            tickets.push((managed_address!(&bronze_user), 1, 1).into());
+            tickets.push((managed_address!(&gold_user), 2, 2).into());
+            sc.add_tickets_with_tier_endpoint(tickets);
+        })
+        .assert_ok();
+
+    // each tier may win at most min(tier tickets, winning tickets)
+    let deposit_amount = BRONZE_TOKENS_PER_TICKET + 2 * GOLD_TOKENS_PER_TICKET;
+    b_mock.set_dcdt_balance(&owner, LAUNCHPAD_TOKEN_ID, &rust_biguint!(deposit_amount));
+    b_mock
+        .execute_dcdt_transfer(
+            &owner,
+            &lp_sc,
+            LAUNCHPAD_TOKEN_ID,
+            0,
+            &rust_biguint!(deposit_amount - 1),
+            |sc| {
+                sc.deposit_launchpad_tokens_endpoint();
+            },
+        )
+        .assert_user_error("Wrong amount");
+    b_mock
+        .execute_dcdt_transfer(
+            &owner,
+            &lp_sc,
+            LAUNCHPAD_TOKEN_ID,
+            0,
+            &rust_biguint!(deposit_amount),
+            |sc| {
+                sc.deposit_launchpad_tokens_endpoint();
+            },
+        )
+        .assert_ok();
+
+    // users pay the price of their own tier
+    b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+    b_mock
+        .execute_tx(
+            &gold_user,
+            &lp_sc,
+            &rust_biguint!(BRONZE_TICKET_PRICE),
+            |sc| {
+                sc.confirm_tickets(1);
+            },
+        )
+        .assert_user_error("Wrong amount sent");
+    b_mock
+        .execute_tx(
+            &gold_user,
+            &lp_sc,
+            &rust_biguint!(GOLD_TICKET_PRICE),
+            |sc| {
+                sc.confirm_tickets(1);
+            },
+        )
+        .assert_ok();
+    b_mock
+        .execute_tx(
+            &bronze_user,
+            &lp_sc,
+            &rust_biguint!(BRONZE_TICKET_PRICE),
+            |sc| {
+                sc.confirm_tickets(1);
+            },
+        )
+        .assert_ok();
+
+    // filter + select winners
+    b_mock.set_block_nonce(WINNER_SELECTION_START_BLOCK);
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.filter_tickets();
+            sc.select_winners();
+
+            assert_eq!(sc.tier_nr_winning_tickets(1).get(), 1);
+            assert_eq!(sc.tier_nr_winning_tickets(2).get(), 1);
+        })
+        .assert_ok();
+
+    // users get the launchpad tokens of their own tier
+    b_mock.set_block_nonce(CLAIM_START_BLOCK);
+    b_mock
+        .execute_tx(&bronze_user, &lp_sc, &rust_zero, |sc| {
+            sc.claim_launchpad_tokens_endpoint();
+        })
+        .assert_ok();
+    b_mock
+        .execute_tx(&gold_user, &lp_sc, &rust_zero, |sc| {
+            sc.claim_launchpad_tokens_endpoint();
+        })
+        .assert_ok();
+
+    b_mock.check_dcdt_balance(
+        &bronze_user,
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(BRONZE_TOKENS_PER_TICKET),
+    );
+    b_mock.check_dcdt_balance(
+        &gold_user,
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(GOLD_TOKENS_PER_TICKET),
+    );
+
+    // the unconfirmed gold ticket's tokens are returned to the owner
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.claim_ticket_payment_endpoint();
+        })
+        .assert_ok();
+
+    b_mock.check_rewa_balance(
+        &owner,
+        &rust_biguint!(BRONZE_TICKET_PRICE + GOLD_TICKET_PRICE),
+    );
+    b_mock.check_dcdt_balance(
+        &owner,
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(GOLD_TOKENS_PER_TICKET),
+    );
+}
+
+#[test]
+fn launchpad_ticket_tiers_with_registration_test() {
+    let _ = DebugApi::dummy();
+    let mut b_mock = BlockchainStateWrapper::new();
+    let rust_zero = rust_biguint!(0);
+
+    let owner = b_mock.create_user_account(&rust_zero);
+    let user = b_mock.create_user_account(&rust_zero);
+    let lp_sc = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        launchpad::contract_obj,
+        "launchpad wasm",
+    );
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.init(
+                managed_token_id!(LAUNCHPAD_TOKEN_ID),
+                managed_biguint!(LAUNCHPAD_TOKENS_PER_TICKET),
+                managed_rewa_token_id!(),
+                managed_biguint!(TICKET_PRICE),
+                NR_WINNING_TICKETS,
+                CONFIRM_START_BLOCK,
+                WINNER_SELECTION_START_BLOCK,
+                CLAIM_START_BLOCK,
+                OptionalValue::None,
+            );
+
+            sc.set_registration_rule(RegistrationRule::FlatCap {
+                max_tickets_per_user: 1,
+            });
+        })
+        .assert_ok();
+
+    // tiers may not be added while users can register
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            let _ = sc.add_ticket_tier_endpoint(
+                managed_biguint!(BRONZE_TICKET_PRICE),
+                managed_biguint!(BRONZE_TOKENS_PER_TICKET),
+            );
+        })
+        .assert_user_error("Ticket tiers cannot be used with ticket registration");
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.set_registration_rule(RegistrationRule::Disabled);
+            let _ = sc.add_ticket_tier_endpoint(
+                managed_biguint!(BRONZE_TICKET_PRICE),
+                managed_biguint!(BRONZE_TOKENS_PER_TICKET),
+            );
+            sc.set_registration_rule(RegistrationRule::FlatCap {
+                max_tickets_per_user: 1,
+            });
+        })
+        .assert_ok();
+
+    // registered tickets would have no tier
+    b_mock
+        .execute_tx(&user, &lp_sc, &rust_zero, |sc| {
+            sc.register_tickets(1);
+        })
+        .assert_user_error("Tickets must be added with a tier");
+}
+
+#[test]
+fn launchpad_weighted_lottery_test() {
+    let _ = DebugApi::dummy();
+    let mut b_mock = BlockchainStateWrapper::new();
+    let rust_zero = rust_biguint!(0);
+
+    let owner = b_mock.create_user_account(&rust_zero);
+    let heavy_user = b_mock.create_user_account(&rust_biguint!(TICKET_PRICE));
+    let light_user = b_mock.create_user_account(&rust_biguint!(TICKET_PRICE * 2));
+    let unconfirmed_user = b_mock.create_user_account(&rust_zero);
+    let lp_sc = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        launchpad::contract_obj,
+        "launchpad wasm",
+    );
+
+    // setup
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.init(
+                managed_token_id!(LAUNCHPAD_TOKEN_ID),
+                managed_biguint!(LAUNCHPAD_TOKENS_PER_TICKET),
+                managed_rewa_token_id!(),
+                managed_biguint!(TICKET_PRICE),
+                2,
+                CONFIRM_START_BLOCK,
+                WINNER_SELECTION_START_BLOCK,
+                CLAIM_START_BLOCK,
+                OptionalValue::None,
+            );
+
+            let mut tickets = MultiValueEncoded::new();
+            tickets.push((managed_address!(&heavy_user), 1).into());
+            tickets.push((managed_address!(&light_user), 2).into());
+            tickets.push((managed_address!(&unconfirmed_user), 1).into());
+            sc.add_tickets_endpoint(tickets);
+
+            sc.launchpad_tokens_deposited().set(true);
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            let mut weights = MultiValueEncoded::new();
+            weights.push((managed_address!(&heavy_user), 0).into());
+            sc.set_ticket_weights_endpoint(weights);
+        })
+        .assert_user_error("Invalid ticket weight");
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            let mut weights = MultiValueEncoded::new();
+            weights.push((managed_address!(&heavy_user), HEAVY_TICKET_WEIGHT).into());
+            sc.set_ticket_weights_endpoint(weights);
+
+            assert!(sc.weighted_lottery().get());
+            assert_eq!(
+                sc.get_ticket_weight(&managed_address!(&heavy_user)),
+                HEAVY_TICKET_WEIGHT
+            );
+            assert_eq!(sc.get_ticket_weight(&managed_address!(&light_user)), 1);
+        })
+        .assert_ok();
+
+    b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+    b_mock
+        .execute_tx(&heavy_user, &lp_sc, &rust_biguint!(TICKET_PRICE), |sc| {
+            sc.confirm_tickets(1);
+        })
+        .assert_ok();
+    b_mock
+        .execute_tx(
+            &light_user,
+            &lp_sc,
+            &rust_biguint!(TICKET_PRICE * 2),
+            |sc| {
+                sc.confirm_tickets(2);
+            },
+        )
+        .assert_ok();
+
+    // filter + select winners
+    b_mock.set_block_nonce(WINNER_SELECTION_START_BLOCK);
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.filter_tickets();
+
+            let weighted_batches = sc.weighted_ticket_batches();
+            assert_eq!(weighted_batches.len(), 2);
+            assert_eq!(weighted_batches.get(2).first_ticket_id, 2);
+            assert_eq!(weighted_batches.get(2).nr_tickets, 2);
+            assert_eq!(sc.remaining_ticket_weight().get(), HEAVY_TICKET_WEIGHT + 2);
+
+            sc.select_winners();
+
+            // drawn tickets no longer count towards the remaining weight
+            assert_eq!(sc.remaining_ticket_weight().get(), 1);
+        })
+        .assert_ok();
+
+    // the heavy user's single ticket is practically certain to win
+    b_mock
+        .execute_query(&lp_sc, |sc| {
+            assert_eq!(
+                sc.get_number_of_winning_tickets_for_address(managed_address!(&heavy_user)),
+                1
+            );
+            assert_eq!(
+                sc.get_number_of_winning_tickets_for_address(managed_address!(&light_user)),
+                1
+            );
+        })
+        .assert_ok();
+}
+
+#[test]
+fn launchpad_claim_deadline_waitlist_test() {
+    let _ = DebugApi::dummy();
+    let mut b_mock = BlockchainStateWrapper::new();
+    let rust_zero = rust_biguint!(0);
+
+    let owner = b_mock.create_user_account(&rust_zero);
+    let first_user = b_mock.create_user_account(&rust_biguint!(TICKET_PRICE));
+    let second_user = b_mock.create_user_account(&rust_biguint!(TICKET_PRICE));
+    let lp_sc = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        launchpad::contract_obj,
+        "launchpad wasm",
+    );
+
+    // setup
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.init(
+                managed_token_id!(LAUNCHPAD_TOKEN_ID),
+                managed_biguint!(LAUNCHPAD_TOKENS_PER_TICKET),
+                managed_rewa_token_id!(),
+                managed_biguint!(TICKET_PRICE),
+                NR_WINNING_TICKETS,
+                CONFIRM_START_BLOCK,
+                WINNER_SELECTION_START_BLOCK,
+                CLAIM_START_BLOCK,
+                OptionalValue::None,
+            );
+
+            let mut tickets = MultiValueEncoded::new();
+            tickets.push((managed_address!(&first_user), 1).into());
+            tickets.push((managed_address!(&second_user), 1).into());
+            sc.add_tickets_endpoint(tickets);
+
+            sc.launchpad_tokens_deposited().set(true);
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.set_claim_deadline_endpoint(CLAIM_START_BLOCK);
+        })
+        .assert_user_error("Claim deadline must be after claim start");
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.set_claim_deadline_endpoint(CLAIM_DEADLINE_BLOCK);
+        })
+        .assert_ok();
+
+    b_mock.set_dcdt_balance(
+        lp_sc.address_ref(),
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(NR_WINNING_TICKETS as u64 * LAUNCHPAD_TOKENS_PER_TICKET),
+    );
+
+    b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+    for user in [&first_user, &second_user] {
+        b_mock
+            .execute_tx(user, &lp_sc, &rust_biguint!(TICKET_PRICE), |sc| {
+                sc.confirm_tickets(1);
+            })
+            .assert_ok();
+    }
+
+    // filter + select winners
+    b_mock.set_block_nonce(WINNER_SELECTION_START_BLOCK);
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.filter_tickets();
+            sc.select_winners();
+        })
+        .assert_ok();
+
+    let mut first_user_won = false;
+    b_mock
+        .execute_query(&lp_sc, |sc| {
+            first_user_won =
+                sc.get_number_of_winning_tickets_for_address(managed_address!(&first_user)) == 1;
+        })
+        .assert_ok();
+    let (winner, loser) = if first_user_won {
+        (&first_user, &second_user)
+    } else {
+        (&second_user, &first_user)
+    };
+
+    // losing tickets may only be refunded after the waitlist draw
+    b_mock.set_block_nonce(CLAIM_START_BLOCK);
+    b_mock
+        .execute_tx(loser, &lp_sc, &rust_zero, |sc| {
+            sc.claim_launchpad_tokens_endpoint();
+        })
+        .assert_user_error("Waitlist winners not selected yet");
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.select_waitlist_winners_endpoint();
+        })
+        .assert_user_error("Claim deadline has not passed yet");
+
+    // the winner does not claim before the deadline, so their ticket is forfeited
+    b_mock.set_block_nonce(CLAIM_DEADLINE_BLOCK);
+    b_mock
+        .execute_tx(winner, &lp_sc, &rust_zero, |sc| {
+            sc.claim_launchpad_tokens_endpoint();
+        })
+        .assert_user_error("Waitlist winners not selected yet");
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.claim_ticket_payment_endpoint();
+        })
+        .assert_user_error("Waitlist winners not selected yet");
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.select_waitlist_winners_endpoint();
+
+            assert!(sc.waitlist_winners_selected().get());
+            assert_eq!(sc.nr_winning_tickets().get(), 1);
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_tx(loser, &lp_sc, &rust_zero, |sc| {
+            sc.claim_launchpad_tokens_endpoint();
+        })
+        .assert_ok();
+    b_mock
+        .execute_tx(winner, &lp_sc, &rust_zero, |sc| {
+            sc.claim_launchpad_tokens_endpoint();
+        })
+        .assert_ok();
+
+    b_mock.check_rewa_balance(loser, &rust_zero);
+    b_mock.check_dcdt_balance(
+        loser,
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(LAUNCHPAD_TOKENS_PER_TICKET),
+    );
+    b_mock.check_rewa_balance(winner, &rust_biguint!(TICKET_PRICE));
+    b_mock.check_dcdt_balance(winner, LAUNCHPAD_TOKEN_ID, &rust_zero);
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.claim_ticket_payment_endpoint();
+        })
+        .assert_ok();
+
+    b_mock.check_rewa_balance(&owner, &rust_biguint!(TICKET_PRICE));
+}
+
+#[test]
+fn launchpad_cancellation_test() {
+    let _ = DebugApi::dummy();
+    let mut b_mock = BlockchainStateWrapper::new();
+    let rust_zero = rust_biguint!(0);
+
+    let owner = b_mock.create_user_account(&rust_zero);
+    let first_user = b_mock.create_user_account(&rust_biguint!(TICKET_PRICE));
+    let second_user = b_mock.create_user_account(&rust_biguint!(TICKET_PRICE));
+    let lp_sc = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        launchpad::contract_obj,
+        "launchpad wasm",
+    );
+
+    // setup
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.init(
+                managed_token_id!(LAUNCHPAD_TOKEN_ID),
+                managed_biguint!(LAUNCHPAD_TOKENS_PER_TICKET),
+                managed_rewa_token_id!(),
+                managed_biguint!(TICKET_PRICE),
+                NR_WINNING_TICKETS,
+                CONFIRM_START_BLOCK,
+                WINNER_SELECTION_START_BLOCK,
+                CLAIM_START_BLOCK,
+                OptionalValue::None,
+            );
+
+            let mut tickets = MultiValueEncoded::new();
+            tickets.push((managed_address!(&first_user), 1).into());
+            tickets.push((managed_address!(&second_user), 1).into());
+            sc.add_tickets_endpoint(tickets);
+
+            sc.launchpad_tokens_deposited().set(true);
+            sc.total_launchpad_tokens_deposited().set(managed_biguint!(
+                NR_WINNING_TICKETS as u64 * LAUNCHPAD_TOKENS_PER_TICKET
+            ));
+        })
+        .assert_ok();
+
+    b_mock.set_dcdt_balance(
+        lp_sc.address_ref(),
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(NR_WINNING_TICKETS as u64 * LAUNCHPAD_TOKENS_PER_TICKET),
+    );
+
+    b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+    for user in [&first_user, &second_user] {
+        b_mock
+            .execute_tx(user, &lp_sc, &rust_biguint!(TICKET_PRICE), |sc| {
+                sc.confirm_tickets(1);
+            })
+            .assert_ok();
+    }
+
+    // filter + select winners
+    b_mock.set_block_nonce(WINNER_SELECTION_START_BLOCK);
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.filter_tickets();
+            sc.select_winners();
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_tx(&first_user, &lp_sc, &rust_zero, |sc| {
+            sc.claim_refund_endpoint();
+        })
+        .assert_user_error("Launchpad not cancelled");
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.cancel_launchpad();
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.cancel_launchpad();
+        })
+        .assert_user_error("Launchpad already cancelled");
+
+    // the claim period never starts for a cancelled launchpad
+    b_mock.set_block_nonce(CLAIM_START_BLOCK);
+    b_mock
+        .execute_tx(&first_user, &lp_sc, &rust_zero, |sc| {
+            sc.claim_launchpad_tokens_endpoint();
+        })
+        .assert_user_error("Not in claim period");
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.claim_ticket_payment_endpoint();
+        })
+        .assert_user_error("Not in claim period");
+
+    for user in [&first_user, &second_user] {
+        b_mock
+            .execute_tx(user, &lp_sc, &rust_zero, |sc| {
+                sc.claim_refund_endpoint();
+            })
+            .assert_ok();
+
+        b_mock
+            .execute_tx(user, &lp_sc, &rust_zero, |sc| {
+                sc.claim_refund_endpoint();
+            })
+            .assert_user_error("Nothing to refund");
+
+        b_mock.check_rewa_balance(user, &rust_biguint!(TICKET_PRICE));
+    }
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.withdraw_launchpad_tokens();
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.withdraw_launchpad_tokens();
+        })
+        .assert_user_error("No launchpad tokens to withdraw");
+
+    b_mock.check_dcdt_balance(
+        &owner,
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(NR_WINNING_TICKETS as u64 * LAUNCHPAD_TOKENS_PER_TICKET),
+    );
+    b_mock.check_rewa_balance(lp_sc.address_ref(), &rust_zero);
+}
+
+#[test]
+fn launchpad_soft_cap_test() {
+    let rust_zero = rust_biguint!(0);
+    let mut setup = LaunchpadSetup::new(NR_WINNING_TICKETS, &[3], launchpad::contract_obj);
+    let user = setup.participants[0].clone();
+    setup
+        .b_mock
+        .set_rewa_balance(&user, &rust_biguint!(TICKET_PRICE * 2));
+
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.set_soft_cap(2);
+            sc.total_launchpad_tokens_deposited().set(managed_biguint!(
+                NR_WINNING_TICKETS as u64 * LAUNCHPAD_TOKENS_PER_TICKET
+            ));
+        })
+        .assert_ok();
+
+    setup.b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.set_soft_cap(1);
+        })
+        .assert_user_error("Add tickets period has passed");
+
+    setup.confirm(&user, 2).assert_ok();
+    setup
+        .b_mock
+        .execute_tx(&user, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.unconfirm_tickets(1);
+
+            assert_eq!(sc.total_confirmed_tickets().get(), 1);
+        })
+        .assert_ok();
+
+    // too few tickets confirmed, so the launchpad is cancelled
+    setup.b_mock.set_block_nonce(WINNER_SELECTION_START_BLOCK);
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.filter_tickets();
+
+            assert!(sc.launchpad_cancelled().get());
+            assert!(!sc.flags().get().were_tickets_filtered);
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.select_winners();
+        })
+        .assert_user_error("Not in winner selection period");
+
+    setup
+        .b_mock
+        .execute_tx(&user, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.claim_refund_endpoint();
+        })
+        .assert_ok();
+    setup
+        .b_mock
+        .check_rewa_balance(&user, &rust_biguint!(TICKET_PRICE * 2));
+
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.withdraw_launchpad_tokens();
+        })
+        .assert_ok();
+    setup.b_mock.check_dcdt_balance(
+        &setup.owner_address,
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(NR_WINNING_TICKETS as u64 * LAUNCHPAD_TOKENS_PER_TICKET),
+    );
+}
+
+#[test]
+fn launchpad_pro_rata_allocation_test() {
+    let rust_zero = rust_biguint!(0);
+    let mut setup = LaunchpadSetup::new(NR_WINNING_TICKETS, &[2, 1], launchpad::contract_obj);
+    let first_user = setup.participants[0].clone();
+    let second_user = setup.participants[1].clone();
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.set_pro_rata_allocation(true);
+            sc.total_launchpad_tokens_deposited().set(managed_biguint!(
+                NR_WINNING_TICKETS as u64 * LAUNCHPAD_TOKENS_PER_TICKET
+            ));
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            let mut weights = MultiValueEncoded::new();
+            weights.push((managed_address!(&first_user), 2).into());
+            sc.set_ticket_weights_endpoint(weights);
+        })
+        .assert_user_error("Weighted lottery cannot be used with pro-rata allocation");
+
+    setup.b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+    setup.confirm(&first_user, 2).assert_ok();
+    setup.confirm(&second_user, 1).assert_ok();
+
+    setup.b_mock.set_block_nonce(WINNER_SELECTION_START_BLOCK);
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.filter_tickets();
+        })
+        .assert_user_error("No winners are selected with pro-rata allocation");
+
+    setup.b_mock.set_block_nonce(CLAIM_START_BLOCK);
+
+    // 3 tickets were confirmed for a single winning ticket, so each gets a third of it
+    let first_user_tokens = 2 * LAUNCHPAD_TOKENS_PER_TICKET / 3;
+    let second_user_tokens = LAUNCHPAD_TOKENS_PER_TICKET / 3;
+    let first_user_payment = 2 * TICKET_PRICE / 3;
+    let second_user_payment = TICKET_PRICE / 3;
+
+    setup
+        .b_mock
+        .execute_tx(&first_user, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.claim_launchpad_tokens_endpoint();
+        })
+        .assert_ok();
+
+    setup.b_mock.check_dcdt_balance(
+        &first_user,
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(first_user_tokens),
+    );
+    setup.b_mock.check_rewa_balance(
+        &first_user,
+        &rust_biguint!(2 * TICKET_PRICE - first_user_payment),
+    );
+
+    // the rounding dust is sent before everyone claimed
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.claim_ticket_payment_endpoint();
+        })
+        .assert_ok();
+
+    let dust = LAUNCHPAD_TOKENS_PER_TICKET - first_user_tokens - second_user_tokens;
+    setup.b_mock.check_dcdt_balance(
+        &setup.owner_address,
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(dust),
+    );
+    setup
+        .b_mock
+        .check_rewa_balance(&setup.owner_address, &rust_biguint!(first_user_payment));
+
+    setup
+        .b_mock
+        .execute_tx(&second_user, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.claim_launchpad_tokens_endpoint();
+        })
+        .assert_ok();
+    setup
+        .b_mock
+        .execute_tx(&second_user, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.claim_launchpad_tokens_endpoint();
+        })
+        .assert_user_error("Already claimed");
+
+    setup.b_mock.check_dcdt_balance(
+        &second_user,
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(second_user_tokens),
+    );
+    setup.b_mock.check_rewa_balance(
+        &second_user,
+        &rust_biguint!(TICKET_PRICE - second_user_payment),
+    );
+
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.claim_ticket_payment_endpoint();
+        })
+        .assert_ok();
+
+    setup.b_mock.check_dcdt_balance(
+        &setup.owner_address,
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(dust),
+    );
+    setup.b_mock.check_rewa_balance(
+        &setup.owner_address,
+        &rust_biguint!(first_user_payment + second_user_payment),
+    );
+    setup.b_mock.check_dcdt_balance(
+        setup.lp_wrapper.address_ref(),
+        LAUNCHPAD_TOKEN_ID,
+        &rust_zero,
+    );
+    setup
+        .b_mock
+        .check_rewa_balance(setup.lp_wrapper.address_ref(), &rust_zero);
+}
+
+#[test]
+fn launchpad_dutch_auction_test() {
+    let rust_zero = rust_biguint!(0);
+    let nr_winning_tickets = 2;
+    let mut setup = LaunchpadSetup::new(nr_winning_tickets, &[1, 1, 1], launchpad::contract_obj);
+    let users = setup.participants.clone();
+    for user in &users {
+        setup
+            .b_mock
+            .set_rewa_balance(user, &rust_biguint!(DUTCH_START_PRICE));
+    }
+
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.set_dutch_auction_endpoint(
+                managed_biguint!(DUTCH_START_PRICE),
+                managed_biguint!(DUTCH_FLOOR_PRICE),
+                CONFIRM_START_BLOCK,
+                CLAIM_START_BLOCK,
+            );
+        })
+        .assert_user_error("Dutch auction must take place during the confirmation period");
+
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.set_dutch_auction_endpoint(
+                managed_biguint!(DUTCH_START_PRICE),
+                managed_biguint!(DUTCH_FLOOR_PRICE),
+                CONFIRM_START_BLOCK,
+                WINNER_SELECTION_START_BLOCK,
+            );
+        })
+        .assert_ok();
+
+    // the first user confirms at the start price
+    setup.b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+    setup
+        .b_mock
+        .execute_tx(
+            &users[0],
+            &setup.lp_wrapper,
+            &rust_biguint!(DUTCH_START_PRICE),
+            |sc| {
+                sc.confirm_tickets(1);
+            },
+        )
+        .assert_ok();
+
+    // halfway through the auction, the second confirmation reaches the number of winning tickets
+    let clearing_price = (DUTCH_START_PRICE + DUTCH_FLOOR_PRICE) / 2;
+    setup
+        .b_mock
+        .set_block_nonce((CONFIRM_START_BLOCK + WINNER_SELECTION_START_BLOCK) / 2);
+    setup
+        .b_mock
+        .execute_tx(
+            &users[1],
+            &setup.lp_wrapper,
+            &rust_biguint!(DUTCH_START_PRICE),
+            |sc| {
+                sc.confirm_tickets(1);
+
+                assert_eq!(
+                    sc.clearing_ticket_price().get(),
+                    managed_biguint!(clearing_price)
+                );
+            },
+        )
+        .assert_ok();
+    setup.b_mock.check_rewa_balance(
+        &users[1],
+        &rust_biguint!(DUTCH_START_PRICE - clearing_price),
+    );
+
+    // the price no longer descends afterwards
+    setup
+        .b_mock
+        .set_block_nonce(WINNER_SELECTION_START_BLOCK - 1);
+    setup
+        .b_mock
+        .execute_query(&setup.lp_wrapper, |sc| {
+            assert_eq!(
+                sc.get_current_ticket_price(),
+                managed_biguint!(clearing_price)
+            );
+        })
+        .assert_ok();
+    setup
+        .b_mock
+        .execute_tx(
+            &users[2],
+            &setup.lp_wrapper,
+            &rust_biguint!(DUTCH_START_PRICE),
+            |sc| {
+                sc.confirm_tickets(1);
+            },
+        )
+        .assert_ok();
+
+    // filter + select winners
+    setup.b_mock.set_block_nonce(WINNER_SELECTION_START_BLOCK);
+    setup.select_winners().assert_ok();
+
+    setup.b_mock.set_block_nonce(CLAIM_START_BLOCK);
+
+    // all the winners pay the clearing price
+    for user in &users {
+        let mut is_winner = false;
+        setup
+            .b_mock
+            .execute_query(&setup.lp_wrapper, |sc| {
+                is_winner =
+                    sc.get_number_of_winning_tickets_for_address(managed_address!(user)) == 1;
+            })
+            .assert_ok();
+
+        setup
+            .b_mock
+            .execute_tx(user, &setup.lp_wrapper, &rust_zero, |sc| {
+                sc.claim_launchpad_tokens_endpoint();
+            })
+            .assert_ok();
+
+        if is_winner {
+            setup
+                .b_mock
+                .check_rewa_balance(user, &rust_biguint!(DUTCH_START_PRICE - clearing_price));
+            setup.b_mock.check_dcdt_balance(
+                user,
+                LAUNCHPAD_TOKEN_ID,
+                &rust_biguint!(LAUNCHPAD_TOKENS_PER_TICKET),
+            );
+        } else {
+            setup
+                .b_mock
+                .check_rewa_balance(user, &rust_biguint!(DUTCH_START_PRICE));
+            setup
+                .b_mock
+                .check_dcdt_balance(user, LAUNCHPAD_TOKEN_ID, &rust_zero);
+        }
+    }
+
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.claim_ticket_payment_endpoint();
+        })
+        .assert_ok();
+
+    setup.b_mock.check_rewa_balance(
+        &setup.owner_address,
+        &rust_biguint!(nr_winning_tickets as u64 * clearing_price),
+    );
+    setup
+        .b_mock
+        .check_rewa_balance(setup.lp_wrapper.address_ref(), &rust_zero);
+}
+
+#[test]
+fn launchpad_dutch_auction_sold_out_refresh_test() {
+    let rust_zero = rust_biguint!(0);
+    let nr_winning_tickets = 2;
+    let mut setup = LaunchpadSetup::new(nr_winning_tickets, &[1, 1], launchpad::contract_obj);
+    let users = setup.participants.clone();
+    for user in &users {
+        setup
+            .b_mock
+            .set_rewa_balance(user, &rust_biguint!(DUTCH_START_PRICE));
+    }
+
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.set_dutch_auction_endpoint(
+                managed_biguint!(DUTCH_START_PRICE),
+                managed_biguint!(DUTCH_FLOOR_PRICE),
+                CONFIRM_START_BLOCK,
+                WINNER_SELECTION_START_BLOCK,
+            );
+        })
+        .assert_ok();
+
+    // both users confirm at the start price, which sells out the auction
+    setup.b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+    for user in &users {
+        setup
+            .b_mock
+            .execute_tx(
+                user,
+                &setup.lp_wrapper,
+                &rust_biguint!(DUTCH_START_PRICE),
+                |sc| {
+                    sc.confirm_tickets(1);
+                },
+            )
+            .assert_ok();
+    }
+
+    // unconfirming resumes the price descent
+    setup
+        .b_mock
+        .set_block_nonce(WINNER_SELECTION_START_BLOCK - 1);
+    setup
+        .b_mock
+        .execute_tx(&users[1], &setup.lp_wrapper, &rust_zero, |sc| {
+            assert!(sc.dutch_auction_sold_out().get());
+
+            sc.unconfirm_tickets(1);
+
+            assert!(!sc.dutch_auction_sold_out().get());
+            assert!(sc.get_current_ticket_price() < managed_biguint!(DUTCH_START_PRICE));
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &users[1],
+            &setup.lp_wrapper,
+            &rust_biguint!(DUTCH_START_PRICE),
+            |sc| {
+                sc.confirm_tickets(1);
+
+                assert!(sc.dutch_auction_sold_out().get());
+            },
+        )
+        .assert_ok();
+
+    // so does blacklisting a user with confirmed tickets
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            let mut users_list = MultiValueEncoded::new();
+            users_list.push(managed_address!(&users[0]));
+            sc.add_users_to_blacklist_endpoint(BLACKLIST_REASON, users_list);
+
+            assert!(!sc.dutch_auction_sold_out().get());
+        })
+        .assert_ok();
+}
+
+#[test]
+fn launchpad_oracle_ticket_price_test() {
+    let _ = DebugApi::dummy();
+    let mut b_mock = BlockchainStateWrapper::new();
+    let rust_zero = rust_biguint!(0);
+
+    let owner = b_mock.create_user_account(&rust_zero);
+    let user = b_mock.create_user_account(&rust_biguint!(ORACLE_TICKET_PRICE * 3));
+    let price_feed_sc =
+        b_mock.create_sc_account(&rust_zero, None, PriceFeedMock::new, "price feed wasm");
+    let lp_sc = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        launchpad::contract_obj,
+        "launchpad wasm",
+    );
+
+    // setup
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.init(
+                managed_token_id!(LAUNCHPAD_TOKEN_ID),
+                managed_biguint!(LAUNCHPAD_TOKENS_PER_TICKET),
+                managed_rewa_token_id!(),
+                managed_biguint!(TICKET_PRICE),
+                NR_WINNING_TICKETS,
+                CONFIRM_START_BLOCK,
+                WINNER_SELECTION_START_BLOCK,
+                CLAIM_START_BLOCK,
+                OptionalValue::None,
+            );
+
+            let mut tickets = MultiValueEncoded::new();
+            tickets.push((managed_address!(&user), 2).into());
+            sc.add_tickets_endpoint(tickets);
+
+            sc.launchpad_tokens_deposited().set(true);
+
+            sc.set_reference_ticket_price(
+                managed_address!(price_feed_sc.address_ref()),
+                managed_buffer!(b"USD"),
+                managed_biguint!(REFERENCE_TICKET_PRICE),
+                PRICE_DECIMALS,
+                MAX_PRICE_AGE,
+            );
+            sc.set_oracle_payment_token(
+                managed_rewa_token_id!(),
+                managed_buffer!(b"REWA"),
+                PRICE_DECIMALS,
+            );
+        })
+        .assert_ok();
+
+    b_mock.set_dcdt_balance(
+        lp_sc.address_ref(),
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(NR_WINNING_TICKETS as u64 * LAUNCHPAD_TOKENS_PER_TICKET),
+    );
+
+    // tokens without a fixed price may not be priced through the oracle
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.set_oracle_payment_token(
+                managed_token_id_wrapped!(STABLE_TOKEN_ID),
+                managed_buffer!(b"STABLE"),
+                PRICE_DECIMALS,
+            );
+        })
+        .assert_user_error("Token not accepted for ticket payment");
+
+    // stale oracle prices are rejected
+    b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+    b_mock.set_block_timestamp(PRICE_FEED_TIMESTAMP + MAX_PRICE_AGE + 1);
+    b_mock
+        .execute_tx(
+            &user,
+            &lp_sc,
+            &rust_biguint!(ORACLE_TICKET_PRICE * 2),
+            |sc| {
+                sc.confirm_tickets(2);
+            },
+        )
+        .assert_user_error("Oracle price is stale");
+
+    // the fixed ticket price is not enough
+    b_mock.set_block_timestamp(PRICE_FEED_TIMESTAMP + MAX_PRICE_AGE);
+    b_mock
+        .execute_tx(&user, &lp_sc, &rust_biguint!(TICKET_PRICE * 2), |sc| {
+            sc.confirm_tickets(2);
+        })
+        .assert_user_error("Wrong amount sent");
+
+    // the excess payment is returned
+    b_mock
+        .execute_tx(
+            &user,
+            &lp_sc,
+            &rust_biguint!(ORACLE_TICKET_PRICE * 3),
+            |sc| {
+                sc.confirm_tickets(2);
+
+                let payments = sc.user_ticket_payments(&managed_address!(&user)).get();
+                assert_eq!(payments.len(), 1);
+                assert_eq!(
+                    payments.get(0).price_per_ticket,
+                    managed_biguint!(ORACLE_TICKET_PRICE)
+                );
+            },
+        )
+        .assert_ok();
+
+    b_mock.check_rewa_balance(&user, &rust_biguint!(ORACLE_TICKET_PRICE));
+
+    // filter + select winners
+    b_mock.set_block_nonce(WINNER_SELECTION_START_BLOCK);
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.filter_tickets();
+            sc.select_winners();
+        })
+        .assert_ok();
+
+    // the non-winning ticket is refunded at the price that was paid
+    b_mock.set_block_nonce(CLAIM_START_BLOCK);
+    b_mock
+        .execute_tx(&user, &lp_sc, &rust_zero, |sc| {
+            sc.claim_launchpad_tokens_endpoint();
+        })
+        .assert_ok();
+
+    b_mock.check_rewa_balance(&user, &rust_biguint!(ORACLE_TICKET_PRICE * 2));
+
+    b_mock
+        .execute_tx(&owner, &lp_sc, &rust_zero, |sc| {
+            sc.claim_ticket_payment_endpoint();
+        })
+        .assert_ok();
+
+    b_mock.check_rewa_balance(&owner, &rust_biguint!(ORACLE_TICKET_PRICE));
+}
+
+#[test]
+fn launchpad_roles_test() {
+    let rust_zero = rust_biguint!(0);
+    let mut setup = LaunchpadSetup::new(NR_WINNING_TICKETS, &[0], launchpad::contract_obj);
+    let user = setup.participants[0].clone();
+    let ticket_manager = setup.b_mock.create_user_account(&rust_zero);
+    let compliance = setup.b_mock.create_user_account(&rust_zero);
+
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            let mut ticket_managers = MultiValueEncoded::new();
+            ticket_managers.push(managed_address!(&ticket_manager));
+            sc.grant_role(Role::TicketManager, ticket_managers);
+
+            let mut compliance_members = MultiValueEncoded::new();
+            compliance_members.push(managed_address!(&compliance));
+            sc.grant_role(Role::Compliance, compliance_members);
+        })
+        .assert_ok();
+
+    // only the owner may grant roles
+    setup
+        .b_mock
+        .execute_tx(&ticket_manager, &setup.lp_wrapper, &rust_zero, |sc| {
+            let mut addresses = MultiValueEncoded::new();
+            addresses.push(managed_address!(&user));
+            sc.grant_role(Role::TicketManager, addresses);
+        })
+        .assert_user_error("Endpoint can only be called by owner");
+
+    // roles do not overlap
+    setup
+        .b_mock
+        .execute_tx(&compliance, &setup.lp_wrapper, &rust_zero, |sc| {
+            let mut tickets = MultiValueEncoded::new();
+            tickets.push((managed_address!(&user), 1).into());
+            sc.add_tickets_endpoint(tickets);
+        })
+        .assert_user_error("Permission denied");
+
+    setup
+        .b_mock
+        .execute_tx(&ticket_manager, &setup.lp_wrapper, &rust_zero, |sc| {
+            let mut tickets = MultiValueEncoded::new();
+            tickets.push((managed_address!(&user), 1).into());
+            sc.add_tickets_endpoint(tickets);
+
+            assert!(sc.has_role(Role::TicketManager, managed_address!(&ticket_manager)));
+            assert!(!sc.has_role(Role::Treasurer, managed_address!(&ticket_manager)));
+            assert!(sc.has_role(Role::Treasurer, managed_address!(&setup.owner_address)));
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(&ticket_manager, &setup.lp_wrapper, &rust_zero, |sc| {
+            let mut users = MultiValueEncoded::new();
+            users.push(managed_address!(&user));
+            sc.add_users_to_blacklist_endpoint(0, users);
+        })
+        .assert_user_error("Permission denied");
+
+    setup
+        .b_mock
+        .execute_tx(&compliance, &setup.lp_wrapper, &rust_zero, |sc| {
+            let mut users = MultiValueEncoded::new();
+            users.push(managed_address!(&user));
+            sc.add_users_to_blacklist_endpoint(0, users);
+        })
+        .assert_ok();
+
+    // revoked members lose access
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            let mut addresses = MultiValueEncoded::new();
+            addresses.push(managed_address!(&ticket_manager));
+            sc.revoke_role(Role::TicketManager, addresses);
+
+            assert!(sc.role_members(Role::TicketManager).is_empty());
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(&ticket_manager, &setup.lp_wrapper, &rust_zero, |sc| {
+            let mut tickets = MultiValueEncoded::new();
+            tickets.push((managed_address!(&user), 1).into());
+            sc.add_tickets_endpoint(tickets);
+        })
+        .assert_user_error("Permission denied");
+}
+
+#[test]
+fn launchpad_admin_action_queue_test() {
+    let rust_zero = rust_biguint!(0);
+    let mut setup = LaunchpadSetup::new(NR_WINNING_TICKETS, &[0], launchpad::contract_obj);
+    let user = setup.participants[0].clone();
+    let operator = setup.b_mock.create_user_account(&rust_zero);
+
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            let mut operators = MultiValueEncoded::new();
+            operators.push(managed_address!(&operator));
+            sc.grant_role(Role::Operator, operators);
+        })
+        .assert_ok();
+
+    // proposals are rejected until a non-zero delay is set
+    setup
+        .b_mock
+        .execute_tx(&operator, &setup.lp_wrapper, &rust_zero, |sc| {
+            let _ = sc.propose_admin_action_endpoint(AdminAction::SetClaimStart {
+                start: CLAIM_DEADLINE_BLOCK,
+            });
+        })
+        .assert_user_error("Admin action delay not set");
+
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.set_admin_action_delay(0);
+        })
+        .assert_user_error("Invalid admin action delay");
+
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.set_admin_action_delay(ADMIN_ACTION_DELAY);
+        })
+        .assert_ok();
+
+    // direct changes are no longer possible once the confirmation period starts
+    setup.b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.set_claim_start_block(CLAIM_DEADLINE_BLOCK);
+        })
+        .assert_user_error("Add tickets period has passed");
+
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.set_admin_action_delay(ADMIN_ACTION_DELAY + 1);
+        })
+        .assert_user_error("Add tickets period has passed");
+
+    setup
+        .b_mock
+        .execute_tx(&user, &setup.lp_wrapper, &rust_zero, |sc| {
+            let _ = sc.propose_admin_action_endpoint(AdminAction::SetClaimStart {
+                start: CLAIM_DEADLINE_BLOCK,
+            });
+        })
+        .assert_user_error("Permission denied");
+
+    setup
+        .b_mock
+        .execute_tx(&operator, &setup.lp_wrapper, &rust_zero, |sc| {
+            let action_id = sc.propose_admin_action_endpoint(AdminAction::SetClaimStart {
+                start: CLAIM_DEADLINE_BLOCK,
+            });
+            assert_eq!(action_id, 1);
+
+            let pending_action = sc.pending_admin_actions().get(&1).unwrap();
+            assert_eq!(
+                pending_action.executable_from_block,
+                CONFIRM_START_BLOCK + ADMIN_ACTION_DELAY
+            );
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(&operator, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.execute_admin_action_endpoint(1);
+        })
+        .assert_user_error("Action delay has not passed yet");
+
+    // a pending action may be cancelled
+    setup
+        .b_mock
+        .execute_tx(&operator, &setup.lp_wrapper, &rust_zero, |sc| {
+            let action_id =
+                sc.propose_admin_action_endpoint(AdminAction::SetWinnerSelectionStart {
+                    start: WINNER_SELECTION_START_BLOCK + 1,
+                });
+            sc.cancel_admin_action(action_id);
+
+            assert!(!sc.pending_admin_actions().contains_key(&action_id));
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .set_block_nonce(CONFIRM_START_BLOCK + ADMIN_ACTION_DELAY);
+
+    setup
+        .b_mock
+        .execute_tx(&operator, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.execute_admin_action_endpoint(2);
+        })
+        .assert_user_error("Unknown admin action");
+
+    setup
+        .b_mock
+        .execute_tx(&operator, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.execute_admin_action_endpoint(1);
+
+            assert_eq!(
+                sc.configuration().get().claim_start_block,
+                CLAIM_DEADLINE_BLOCK
+            );
+            assert!(sc.pending_admin_actions().is_empty());
+        })
+        .assert_ok();
+
+    // actions the contract can't execute are rejected when proposed
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            let _ = sc.propose_admin_action_endpoint(AdminAction::SetUnlockSchedule {
+                args: ManagedVec::new(),
+            });
+        })
+        .assert_user_error("Action not supported");
+
+    // a queued ticket price change may not apply once tickets may be confirmed
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            let action_id = sc.propose_admin_action_endpoint(AdminAction::SetTicketPrice {
+                token_id: managed_rewa_token_id!(),
+                amount: managed_biguint!(TICKET_PRICE * 2),
+            });
+            assert_eq!(action_id, 3);
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .set_block_nonce(CONFIRM_START_BLOCK + ADMIN_ACTION_DELAY * 2);
+
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.execute_admin_action_endpoint(3);
+        })
+        .assert_user_error("Add tickets period has passed");
+}
+
+#[test]
+fn launchpad_blacklist_entries_test() {
+    let rust_zero = rust_biguint!(0);
+    let mut setup = LaunchpadSetup::new(NR_WINNING_TICKETS, &[1, 1], launchpad::contract_obj);
+    let first_user = setup.participants[0].clone();
+    let second_user = setup.participants[1].clone();
+
+    let blacklist_block = CONFIRM_START_BLOCK / 2;
+    setup.b_mock.set_block_nonce(blacklist_block);
+
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            let mut first_users = MultiValueEncoded::new();
+            first_users.push(managed_address!(&first_user));
+            sc.add_users_to_blacklist_endpoint(BLACKLIST_REASON, first_users);
+
+            let mut second_users = MultiValueEncoded::new();
+            second_users.push(managed_address!(&second_user));
+            sc.add_users_to_blacklist_endpoint(BLACKLIST_REASON + 1, second_users);
+
+            let entry = sc.blacklist_entry(&managed_address!(&first_user)).get();
+            assert_eq!(entry.reason, BLACKLIST_REASON);
+            assert_eq!(entry.blacklisted_by, managed_address!(&setup.owner_address));
+            assert_eq!(entry.block, blacklist_block);
+
+            // pagination
+            assert_eq!(sc.get_nr_blacklisted_users(), 2);
+
+            let first_page: Vec<_> = sc
+                .get_blacklisted_users(0, 1)
+                .into_iter()
+                .map(|item| item.into_tuple())
+                .collect();
+            assert_eq!(first_page.len(), 1);
+            assert_eq!(first_page[0].0, managed_address!(&first_user));
+
+            let second_page: Vec<_> = sc
+                .get_blacklisted_users(1, 10)
+                .into_iter()
+                .map(|item| item.into_tuple())
+                .collect();
+            assert_eq!(second_page.len(), 1);
+            assert_eq!(second_page[0].0, managed_address!(&second_user));
+            assert_eq!(second_page[0].1.reason, BLACKLIST_REASON + 1);
+
+            assert!(sc.get_blacklisted_users(2, 10).is_empty());
+        })
+        .assert_ok();
+
+    setup.b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+
+    setup
+        .b_mock
+        .execute_tx(
+            &second_user,
+            &setup.lp_wrapper,
+            &rust_biguint!(TICKET_PRICE),
+            |sc| {
+                sc.confirm_tickets(1);
+            },
+        )
+        .assert_user_error("You have been put into the blacklist and may not confirm tickets");
+
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            let mut users = MultiValueEncoded::new();
+            users.push(managed_address!(&second_user));
+            sc.remove_users_from_blacklist_endpoint(users);
+
+            assert!(sc
+                .blacklist_entry(&managed_address!(&second_user))
+                .is_empty());
+            assert_eq!(sc.get_nr_blacklisted_users(), 1);
+            assert!(sc.is_user_blacklisted(&managed_address!(&first_user)));
+        })
+        .assert_ok();
+
+    setup.confirm(&second_user, 1).assert_ok();
+}
+
+#[test]
+fn launchpad_blacklist_winners_redraw_test() {
+    let rust_zero = rust_biguint!(0);
+    let mut setup = LaunchpadSetup::new(NR_WINNING_TICKETS, &[1, 1], launchpad::contract_obj);
+    let first_user = setup.participants[0].clone();
+    let second_user = setup.participants[1].clone();
+
+    setup.b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+    for user in [&first_user, &second_user] {
+        setup.confirm(user, 1).assert_ok();
+    }
+
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            let mut users = MultiValueEncoded::new();
+            users.push(managed_address!(&first_user));
+            sc.add_winners_to_blacklist_endpoint(BLACKLIST_REASON, users);
+        })
+        .assert_user_error("Not in winner selection period");
+
+    // filter + select winners
+    setup.b_mock.set_block_nonce(WINNER_SELECTION_START_BLOCK);
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.filter_tickets();
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            let mut users = MultiValueEncoded::new();
+            users.push(managed_address!(&first_user));
+            sc.add_winners_to_blacklist_endpoint(BLACKLIST_REASON, users);
+        })
+        .assert_user_error("Winners not selected yet");
+
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.select_winners();
+        })
+        .assert_ok();
+
+    let mut first_user_won = false;
+    setup
+        .b_mock
+        .execute_query(&setup.lp_wrapper, |sc| {
+            first_user_won =
+                sc.get_number_of_winning_tickets_for_address(managed_address!(&first_user)) == 1;
+        })
+        .assert_ok();
+    let (winner, loser) = if first_user_won {
+        (&first_user, &second_user)
+    } else {
+        (&second_user, &first_user)
+    };
+
+    // the winner is blacklisted late, and refunded
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            let mut users = MultiValueEncoded::new();
+            users.push(managed_address!(winner));
+            sc.add_winners_to_blacklist_endpoint(BLACKLIST_REASON, users);
+
+            assert!(sc.is_user_blacklisted(&managed_address!(winner)));
+            assert_eq!(sc.nr_winning_tickets().get(), 0);
+            assert_eq!(sc.nr_winning_tickets_to_redraw().get(), 1);
+            assert_eq!(sc.claimable_ticket_payment().get(), managed_biguint!(0));
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .check_rewa_balance(winner, &rust_biguint!(TICKET_PRICE));
+
+    // the claim period waits for the redraw
+    setup.b_mock.set_block_nonce(CLAIM_START_BLOCK);
+    setup
+        .b_mock
+        .execute_tx(loser, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.claim_launchpad_tokens_endpoint();
+        })
+        .assert_user_error("Not in claim period");
+
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.redraw_winners_endpoint();
+
+            assert_eq!(sc.nr_winning_tickets().get(), 1);
+            assert_eq!(sc.nr_winning_tickets_to_redraw().get(), 0);
+            assert_eq!(
+                sc.claimable_ticket_payment().get(),
+                managed_biguint!(TICKET_PRICE)
+            );
+            assert_eq!(
+                sc.get_number_of_winning_tickets_for_address(managed_address!(loser)),
+                1
+            );
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.redraw_winners_endpoint();
+        })
+        .assert_user_error("No winning tickets to redraw");
+
+    setup
+        .b_mock
+        .execute_tx(winner, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.claim_launchpad_tokens_endpoint();
+        })
+        .assert_user_error("You have no tickets");
+
+    setup
+        .b_mock
+        .execute_tx(loser, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.claim_launchpad_tokens_endpoint();
+        })
+        .assert_ok();
+
+    setup.b_mock.check_rewa_balance(loser, &rust_zero);
+    setup.b_mock.check_dcdt_balance(
+        loser,
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(LAUNCHPAD_TOKENS_PER_TICKET),
+    );
+
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.claim_ticket_payment_endpoint();
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .check_rewa_balance(&setup.owner_address, &rust_biguint!(TICKET_PRICE));
+}
+
+#[test]
+fn launchpad_address_groups_test() {
+    let rust_zero = rust_biguint!(0);
+    let mut setup = LaunchpadSetup::new(3, &[2, 1, 1], launchpad::contract_obj);
+    let first_user = setup.participants[0].clone();
+    let second_user = setup.participants[1].clone();
+    let third_user = setup.participants[2].clone();
+
+    // the first two users belong to the same entity
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            let mut addresses = MultiValueEncoded::new();
+            addresses.push(managed_address!(&first_user));
+            addresses.push(managed_address!(&second_user));
+            let group_id = sc.add_address_group_endpoint(2, 1, addresses);
+
+            assert_eq!(group_id, 1);
+            assert_eq!(
+                sc.user_address_group(&managed_address!(&second_user)).get(),
+                1
+            );
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            let mut addresses = MultiValueEncoded::new();
+            addresses.push(managed_address!(&third_user));
+            addresses.push(managed_address!(&first_user));
+            sc.add_address_group_endpoint(1, 1, addresses);
+        })
+        .assert_user_error("Address already in a group");
+
+    setup.b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            let mut addresses = MultiValueEncoded::new();
+            addresses.push(managed_address!(&third_user));
+            sc.add_address_group_endpoint(1, 1, addresses);
+        })
+        .assert_user_error("Add tickets period has passed");
+
+    let group_tickets_payment = rust_biguint!(TICKET_PRICE * 2);
+    setup
+        .b_mock
+        .execute_tx(
+            &first_user,
+            &setup.lp_wrapper,
+            &group_tickets_payment,
+            |sc| {
+                sc.confirm_tickets(2);
+            },
+        )
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &second_user,
+            &setup.lp_wrapper,
+            &rust_biguint!(TICKET_PRICE),
+            |sc| {
+                sc.confirm_tickets(1);
+            },
+        )
+        .assert_user_error("Address group may not confirm that many tickets");
+
+    setup
+        .b_mock
+        .execute_tx(&first_user, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.unconfirm_tickets(1);
+        })
+        .assert_ok();
+
+    for user in [&second_user, &third_user] {
+        setup.confirm(user, 1).assert_ok();
+    }
+
+    setup
+        .b_mock
+        .execute_query(&setup.lp_wrapper, |sc| {
+            assert_eq!(sc.group_nr_confirmed_tickets(1).get(), 2);
+        })
+        .assert_ok();
+
+    // only one of the group tickets may win, so the third winning ticket is dropped
+    setup.b_mock.set_block_nonce(WINNER_SELECTION_START_BLOCK);
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.filter_tickets();
+            sc.select_winners();
+
+            assert_eq!(sc.nr_winning_tickets().get(), 2);
+            assert_eq!(sc.group_nr_winning_tickets(1).get(), 1);
+            assert_eq!(
+                sc.claimable_ticket_payment().get(),
+                managed_biguint!(TICKET_PRICE * 2)
+            );
+
+            let first_user_wins =
+                sc.get_number_of_winning_tickets_for_address(managed_address!(&first_user));
+            let second_user_wins =
+                sc.get_number_of_winning_tickets_for_address(managed_address!(&second_user));
+            assert_eq!(first_user_wins + second_user_wins, 1);
+            assert_eq!(
+                sc.get_number_of_winning_tickets_for_address(managed_address!(&third_user)),
+                1
+            );
+        })
+        .assert_ok();
+}
+
+#[test]
+fn launchpad_treasurer_payouts_test() {
+    let rust_zero = rust_biguint!(0);
+    // ticket payments
+    let mut setup = LaunchpadSetup::new(NR_WINNING_TICKETS, &[1], launchpad::contract_obj);
+    let user = setup.participants[0].clone();
+    let treasurer = setup.b_mock.create_user_account(&rust_zero);
+    setup.grant_role(Role::Treasurer, &treasurer);
+
+    setup.b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+    setup.confirm(&user, 1).assert_ok();
+
+    setup.b_mock.set_block_nonce(WINNER_SELECTION_START_BLOCK);
+    setup.select_winners().assert_ok();
+
+    setup.b_mock.set_block_nonce(CLAIM_START_BLOCK);
+    setup
+        .b_mock
+        .execute_tx(&treasurer, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.claim_ticket_payment_endpoint();
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .check_rewa_balance(&setup.owner_address, &rust_biguint!(TICKET_PRICE));
+    setup.b_mock.check_rewa_balance(&treasurer, &rust_zero);
+
+    // pro-rata ticket payments
+    let mut setup = LaunchpadSetup::new(NR_WINNING_TICKETS, &[1], launchpad::contract_obj);
+    let user = setup.participants[0].clone();
+    let treasurer = setup.b_mock.create_user_account(&rust_zero);
+    setup.grant_role(Role::Treasurer, &treasurer);
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.set_pro_rata_allocation(true);
+            sc.total_launchpad_tokens_deposited().set(managed_biguint!(
+                NR_WINNING_TICKETS as u64 * LAUNCHPAD_TOKENS_PER_TICKET
+            ));
+        })
+        .assert_ok();
+
+    setup.b_mock.set_block_nonce(CONFIRM_START_BLOCK);
+    setup.confirm(&user, 1).assert_ok();
+
+    setup.b_mock.set_block_nonce(CLAIM_START_BLOCK);
+    setup
+        .b_mock
+        .execute_tx(&user, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.claim_launchpad_tokens_endpoint();
+        })
+        .assert_ok();
+    setup
+        .b_mock
+        .execute_tx(&treasurer, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.claim_ticket_payment_endpoint();
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .check_rewa_balance(&setup.owner_address, &rust_biguint!(TICKET_PRICE));
+    setup.b_mock.check_rewa_balance(&treasurer, &rust_zero);
+
+    // launchpad tokens withdrawn after cancellation
+    let mut setup = LaunchpadSetup::new(NR_WINNING_TICKETS, &[1], launchpad::contract_obj);
+    let treasurer = setup.b_mock.create_user_account(&rust_zero);
+    setup.grant_role(Role::Treasurer, &treasurer);
+    setup
+        .b_mock
+        .execute_tx(&setup.owner_address, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.total_launchpad_tokens_deposited().set(managed_biguint!(
+                NR_WINNING_TICKETS as u64 * LAUNCHPAD_TOKENS_PER_TICKET
+            ));
+            sc.cancel_launchpad();
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(&treasurer, &setup.lp_wrapper, &rust_zero, |sc| {
+            sc.withdraw_launchpad_tokens();
+        })
+        .assert_ok();
+
+    setup.b_mock.check_dcdt_balance(
+        &setup.owner_address,
+        LAUNCHPAD_TOKEN_ID,
+        &rust_biguint!(NR_WINNING_TICKETS as u64 * LAUNCHPAD_TOKENS_PER_TICKET),
+    );
+    setup
+        .b_mock
+        .check_dcdt_balance(&treasurer, LAUNCHPAD_TOKEN_ID, &rust_zero);
+}
+
+#[derive(Clone, Default)]
+pub struct PriceFeedMock {}
+
+impl ContractBase for PriceFeedMock {
+    type Api = DebugApi;
+}
+
+impl CallableContract for PriceFeedMock {
+    fn call(&self, fn_name: &str) -> bool {
+        if fn_name != PRICE_FEED_FN_NAME {
+            return false;
+        }
+
+        self.call_latest_price_feed_optional();
+
+        true
+    }
+}
+
+impl PriceFeedMock {
+    pub fn new() -> Self {
+        PriceFeedMock {}
+    }
+
+    fn call_latest_price_feed_optional(&self) {
+        let api = TxContextStack::static_peek();
+        let args = api.input_ref().args.clone();
+        if args.len() != 2 || args[0] != b"REWA" || args[1] != b"USD" {
+            panic!("Invalid args");
+        }
+
+        let mut round_id = Vec::new();
+        1u32.top_encode(&mut round_id).unwrap();
+        let mut timestamp = Vec::new();
+        PRICE_FEED_TIMESTAMP.top_encode(&mut timestamp).unwrap();
+        let mut price = Vec::new();
+        managed_biguint!(REWA_USD_PRICE)
+            .top_encode(&mut price)
+            .unwrap();
+        let mut decimals = Vec::new();
+        (PRICE_DECIMALS as u8).top_encode(&mut decimals).unwrap();
+
+        api.tx_result_cell
+            .try_lock()
+            .unwrap()
+            .result_values
+            .extend([
+                round_id,
+                args[0].clone(),
+                args[1].clone(),
+                timestamp,
+                price,
+                decimals,
+            ]);
+    }
+}